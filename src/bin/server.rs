@@ -0,0 +1,58 @@
+use std::env;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rustymanfred::component::Position;
+use rustymanfred::data::level::LevelDefinition;
+use rustymanfred::data::load_ron_file;
+use rustymanfred::net::conditioner::LinkConditions;
+use rustymanfred::net::server::Server;
+use rustymanfred::net::simulation::MovementRules;
+use rustymanfred::net::socket::NetSocket;
+use rustymanfred::plugin::level::LevelConfig;
+use rustymanfred::plugin::movement::MovementConfig;
+
+/// The server runs as many updates per second as the game runs frames.
+const UPDATE_INTERVAL: Duration = Duration::from_micros(1_000_000 / 60);
+
+/// Runs the authoritative server for online play, e.g.
+/// `cargo run --bin server -- --port 7777 --latency 100 --loss 5`.
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let port = match args.iter().position(|arg| arg == "--port") {
+        Some(index) => args.get(index + 1).cloned().unwrap_or_default(),
+        None => "7777".to_string(),
+    };
+
+    let conditions = LinkConditions::from_args(&args).unwrap_or_else(|message| exit(&message));
+    let mut socket = NetSocket::bind(&format!("0.0.0.0:{}", port), conditions)
+        .unwrap_or_else(|message| exit(&message));
+
+    let level = load_ron_file::<LevelDefinition>(&LevelConfig::default().path)
+        .unwrap_or_else(|message| exit(&message));
+    let spawn = Position::new(level.manfred_start.x as i32, level.manfred_start.y as i32);
+    let rules =
+        MovementRules::from_config(&MovementConfig::default()).with_obstacles(&level.obstacles);
+    let mut server = Server::new(rules, spawn);
+
+    println!("listening on port {}", port);
+    let mut client_count = 0;
+    loop {
+        let started = Instant::now();
+
+        server.update(&mut socket);
+        if server.client_count() != client_count {
+            client_count = server.client_count();
+            println!("{} players connected", client_count);
+        }
+
+        if let Some(rest) = UPDATE_INTERVAL.checked_sub(started.elapsed()) {
+            thread::sleep(rest);
+        }
+    }
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1);
+}
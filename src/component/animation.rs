@@ -0,0 +1,322 @@
+use std::mem;
+
+use crate::data::animation::{AnimationGraph, AnimationState, Condition};
+
+/// Plays the states of an animation graph and switches between them.
+#[derive(Debug, Clone)]
+pub struct Animator {
+    /// the name of the graph in the animation definitions
+    pub graph: String,
+    /// None until the first update enters the initial state
+    state: Option<String>,
+    /// the index into the frames of the state
+    frame: usize,
+    /// permille of a frame played since the current frame was reached
+    progress: u32,
+    finished: bool,
+    /// the events triggered since the last update
+    triggered: Vec<String>,
+}
+
+impl Animator {
+    pub fn new(graph: &str) -> Animator {
+        Animator {
+            graph: graph.to_string(),
+            state: None,
+            frame: 0,
+            progress: 0,
+            finished: false,
+            triggered: vec![],
+        }
+    }
+
+    pub fn state(&self) -> Option<&str> {
+        self.state.as_deref()
+    }
+
+    /// Lets `Event` transitions of the next update happen, e.g. to start an attack.
+    pub fn trigger(&mut self, event: &str) {
+        self.triggered.push(event.to_string());
+    }
+
+    /// The column in the sprite atlas of the frame to show.
+    pub fn column(&self, graph: &AnimationGraph) -> u32 {
+        self.current_state(graph)
+            .and_then(|state| state.frames.get(self.frame))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// One animation tick: takes the first transition whose condition holds, otherwise plays the
+    /// current state on. Returns the columns of the frames reached, in order.
+    pub fn update(&mut self, graph: &AnimationGraph, speed: i32) -> Vec<u32> {
+        let triggered = mem::take(&mut self.triggered);
+        let state = match self.current_state(graph) {
+            Some(state) => state,
+            None => return self.enter(graph, &graph.initial),
+        };
+
+        if state.interruptible || self.finished {
+            let any_state = graph
+                .any_state
+                .iter()
+                .filter(|transition| transition.to != state.name);
+            let transition = state
+                .transitions
+                .iter()
+                .chain(any_state)
+                .find(|transition| match &transition.when {
+                    Condition::SpeedAtLeast(threshold) => speed >= *threshold,
+                    Condition::SpeedBelow(threshold) => speed < *threshold,
+                    Condition::Event(event) => triggered.contains(event),
+                    Condition::Finished => self.finished,
+                });
+            if let Some(transition) = transition {
+                return self.enter(graph, &transition.to);
+            }
+        }
+
+        self.play(state, speed)
+    }
+
+    fn current_state<'a>(&self, graph: &'a AnimationGraph) -> Option<&'a AnimationState> {
+        self.state.as_deref().and_then(|name| graph.state(name))
+    }
+
+    fn enter(&mut self, graph: &AnimationGraph, name: &str) -> Vec<u32> {
+        self.state = Some(name.to_string());
+        self.frame = 0;
+        self.progress = 0;
+        self.finished = false;
+
+        self.current_state(graph)
+            .and_then(|state| state.frames.first())
+            .map_or(vec![], |column| vec![*column])
+    }
+
+    fn play(&mut self, state: &AnimationState, speed: i32) -> Vec<u32> {
+        let advance = match state.reference_speed {
+            Some(reference_speed) if reference_speed > 0 => {
+                state.playback as u64 * speed.unsigned_abs() as u64 / reference_speed as u64
+            }
+            _ => state.playback as u64,
+        };
+        // more than a whole round only repeats the frames already reached
+        let frames = state.frames.len() as u64;
+        self.progress = (self.progress as u64 + advance.min(frames * 1000)) as u32;
+
+        let mut reached = vec![];
+        while self.progress >= 1000 {
+            if self.frame + 1 < state.frames.len() {
+                self.frame += 1;
+            } else {
+                self.finished = true;
+                if !state.looping {
+                    self.progress = 0;
+                    break;
+                }
+                self.frame = 0;
+            }
+            self.progress -= 1000;
+            reached.push(state.frames[self.frame]);
+        }
+        reached
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::component::animation::Animator;
+    use crate::data::animation::{AnimationGraph, AnimationState, Condition, Transition};
+
+    fn transition(to: &str, when: Condition) -> Transition {
+        Transition {
+            to: to.to_string(),
+            when,
+        }
+    }
+
+    fn state(name: &str, frames: Vec<u32>) -> AnimationState {
+        AnimationState {
+            name: name.to_string(),
+            frames,
+            playback: 1000,
+            reference_speed: None,
+            looping: true,
+            interruptible: true,
+            frame_events: vec![],
+            transitions: vec![],
+        }
+    }
+
+    /// Idle, walking and running, attacking which cannot be interrupted and picking up which can.
+    fn graph() -> AnimationGraph {
+        AnimationGraph {
+            name: "test".to_string(),
+            initial: "idle".to_string(),
+            states: vec![
+                AnimationState {
+                    transitions: vec![transition("walk", Condition::SpeedAtLeast(1))],
+                    ..state("idle", vec![0])
+                },
+                AnimationState {
+                    reference_speed: Some(4),
+                    transitions: vec![
+                        transition("idle", Condition::SpeedBelow(1)),
+                        transition("run", Condition::SpeedAtLeast(8)),
+                    ],
+                    ..state("walk", vec![1, 2, 3, 4])
+                },
+                AnimationState {
+                    playback: 2000,
+                    transitions: vec![transition("walk", Condition::SpeedBelow(8))],
+                    ..state("run", vec![1, 2, 3, 4])
+                },
+                AnimationState {
+                    looping: false,
+                    interruptible: false,
+                    transitions: vec![transition("idle", Condition::Finished)],
+                    ..state("attack", vec![5, 6, 7])
+                },
+                AnimationState {
+                    looping: false,
+                    transitions: vec![transition("idle", Condition::Finished)],
+                    ..state("pick_up", vec![8, 9])
+                },
+            ],
+            any_state: vec![
+                transition("attack", Condition::Event("attack".to_string())),
+                transition("pick_up", Condition::Event("pick_up".to_string())),
+            ],
+            fidgets: vec![],
+        }
+    }
+
+    fn animator_in(state: &str) -> Animator {
+        let graph = graph();
+        let mut animator = Animator::new("test");
+        animator.update(&graph, 0);
+        if state != "idle" {
+            animator.trigger(state);
+            animator.update(&graph, 0);
+        }
+        animator
+    }
+
+    #[test]
+    fn starts_in_the_initial_state() {
+        let mut under_test = Animator::new("test");
+
+        assert_eq!(vec![0], under_test.update(&graph(), 5));
+        assert_eq!(Some("idle"), under_test.state());
+    }
+
+    #[rstest]
+    #[case(0, "idle")]
+    #[case(1, "walk")]
+    #[case(7, "walk")]
+    #[case(8, "run")]
+    fn speed_picks_the_movement_state(#[case] speed: i32, #[case] expected: &str) {
+        let graph = graph();
+        let mut under_test = animator_in("idle");
+
+        for _ in 0..3 {
+            under_test.update(&graph, speed);
+        }
+
+        assert_eq!(Some(expected), under_test.state());
+    }
+
+    #[rstest]
+    #[case(4, vec![2, 3, 4, 1, 2])]
+    #[case(2, vec![2, 3])]
+    #[case(6, vec![2, 3, 4, 1, 2, 3, 4])]
+    fn playback_is_scaled_by_the_speed(#[case] speed: i32, #[case] expected: Vec<u32>) {
+        let graph = graph();
+        let mut under_test = animator_in("idle");
+        assert_eq!(vec![1], under_test.update(&graph, speed));
+
+        let reached: Vec<u32> = (0..5)
+            .flat_map(|_| under_test.update(&graph, speed))
+            .collect();
+
+        assert_eq!(expected, reached);
+    }
+
+    #[test]
+    fn one_shots_stop_at_their_last_frame_and_finish() {
+        let mut graph = graph();
+        graph.states[3].transitions.clear();
+        let mut under_test = animator_in("attack");
+
+        let reached: Vec<u32> = (0..5).flat_map(|_| under_test.update(&graph, 0)).collect();
+
+        assert_eq!(vec![6, 7], reached);
+        assert_eq!(7, under_test.column(&graph));
+        assert_eq!(Some("attack"), under_test.state());
+    }
+
+    #[test]
+    fn finished_one_shots_return_to_idle() {
+        let graph = graph();
+        let mut under_test = animator_in("pick_up");
+
+        under_test.update(&graph, 0);
+        assert_eq!(Some("pick_up"), under_test.state());
+        under_test.update(&graph, 0);
+        assert_eq!(Some("pick_up"), under_test.state());
+
+        assert_eq!(vec![0], under_test.update(&graph, 0));
+        assert_eq!(Some("idle"), under_test.state());
+    }
+
+    #[test]
+    fn uninterruptible_actions_ignore_speed_and_events_until_finished() {
+        let graph = graph();
+        let mut under_test = animator_in("attack");
+
+        for _ in 0..3 {
+            under_test.trigger("pick_up");
+            under_test.update(&graph, 10);
+            assert_eq!(Some("attack"), under_test.state());
+        }
+
+        under_test.update(&graph, 10);
+        assert_eq!(Some("idle"), under_test.state());
+    }
+
+    #[test]
+    fn interruptible_actions_can_be_interrupted_by_events() {
+        let graph = graph();
+        let mut under_test = animator_in("pick_up");
+
+        under_test.trigger("attack");
+        under_test.update(&graph, 0);
+
+        assert_eq!(Some("attack"), under_test.state());
+    }
+
+    #[test]
+    fn triggering_the_playing_action_does_not_restart_it() {
+        let graph = graph();
+        let mut under_test = animator_in("pick_up");
+
+        under_test.trigger("pick_up");
+
+        assert_eq!(vec![9], under_test.update(&graph, 0));
+    }
+
+    #[test]
+    fn events_are_forgotten_after_the_update() {
+        let graph = graph();
+        let mut under_test = animator_in("idle");
+
+        under_test.trigger("dance");
+        under_test.update(&graph, 0);
+        under_test.update(&graph, 0);
+
+        assert_eq!(Some("idle"), under_test.state());
+    }
+}
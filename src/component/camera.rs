@@ -0,0 +1,2 @@
+/// Marks the camera that looks at the game world, as opposed to the UI camera.
+pub struct MainCamera;
@@ -0,0 +1,7 @@
+use crate::types::chunk::ChunkCoordinate;
+
+/// Marks entities that were spawned from a chunk of a streamed level and are despawned with it.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ChunkObject {
+    pub chunk: ChunkCoordinate,
+}
@@ -0,0 +1,85 @@
+use bevy::prelude::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// An axis aligned box around the entity's position. The offset moves the box relative to the
+/// center of the sprite, e.g. to the trunk of a tree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Collider {
+    pub size: Vec2,
+    pub offset: Vec2,
+}
+
+impl Collider {
+    pub fn new(width: f32, height: f32) -> Collider {
+        Collider {
+            size: Vec2::new(width, height),
+            offset: Vec2::ZERO,
+        }
+    }
+
+    pub fn with_offset(mut self, x: f32, y: f32) -> Collider {
+        self.offset = Vec2::new(x, y);
+        self
+    }
+
+    pub fn center(&self, position: Vec2) -> Vec2 {
+        position + self.offset
+    }
+
+    /// Returns the lower left and the upper right corner of the box.
+    pub fn bounds(&self, position: Vec2) -> (Vec2, Vec2) {
+        let center = self.center(position);
+        let half_size = self.size / 2.0;
+        (center - half_size, center + half_size)
+    }
+
+    pub fn overlaps(&self, position: Vec2, other: &Collider, other_position: Vec2) -> bool {
+        let (min, max) = self.bounds(position);
+        let (other_min, other_max) = other.bounds(other_position);
+
+        min.x < other_max.x && other_min.x < max.x && min.y < other_max.y && other_min.y < max.y
+    }
+}
+
+/// The box around Manfred's feet.
+pub fn manfred_collider() -> Collider {
+    Collider::new(30.0, 20.0).with_offset(0.0, -30.0)
+}
+
+/// The box around the trunk of a tree.
+pub fn tree_collider() -> Collider {
+    Collider::new(30.0, 20.0).with_offset(0.0, -90.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Vec2;
+    use rstest::*;
+
+    use crate::component::collider::Collider;
+
+    #[test]
+    fn bounds_are_centered_around_position_plus_offset() {
+        let under_test = Collider::new(20.0, 10.0).with_offset(0.0, -5.0);
+
+        let (min, max) = under_test.bounds(Vec2::new(100.0, 100.0));
+
+        assert_eq!(Vec2::new(90.0, 90.0), min);
+        assert_eq!(Vec2::new(110.0, 100.0), max);
+    }
+
+    #[rstest]
+    #[case(Vec2::new(0.0, 0.0), true)]
+    #[case(Vec2::new(9.0, 9.0), true)]
+    #[case(Vec2::new(10.0, 0.0), false)]
+    #[case(Vec2::new(0.0, -10.0), false)]
+    #[case(Vec2::new(30.0, 30.0), false)]
+    fn overlap(#[case] other_position: Vec2, #[case] expected: bool) {
+        let under_test = Collider::new(10.0, 10.0);
+
+        assert_eq!(
+            expected,
+            under_test.overlaps(Vec2::ZERO, &Collider::new(10.0, 10.0), other_position)
+        );
+    }
+}
@@ -0,0 +1,5 @@
+/// Marks the background of the drop-down console.
+pub struct ConsolePanel;
+
+/// Marks the text node that shows the console history and input.
+pub struct ConsoleText;
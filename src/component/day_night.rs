@@ -0,0 +1,9 @@
+use bevy::prelude::Color;
+
+/// Keeps the sprite out of the day and night tint, e.g. for debug shapes.
+pub struct Untinted;
+
+/// The colour of a texture atlas sprite before the day and night tint is applied, e.g. to tell
+/// the players apart. Atlas sprites without it are white.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BaseColor(pub Color);
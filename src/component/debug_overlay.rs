@@ -0,0 +1,6 @@
+/// Marks the sprites that are drawn by the debug overlay. They are moved to the current shapes
+/// every frame and hidden while they are not needed.
+pub struct DebugShape;
+
+/// Marks the text node that shows the debug information.
+pub struct DebugText;
@@ -0,0 +1,6 @@
+/// Sits next to a `TriggerZone` and leads to a spawn point of another level.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Door {
+    pub target_level: String,
+    pub target_spawn_point: String,
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+/// How high and how long Manfred jumps, in whole pixels per frame like his velocity.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JumpConfig {
+    /// the upward speed a jump starts with
+    pub speed: i32,
+    /// how much the upward speed drops every frame
+    pub gravity: i32,
+}
+
+impl Default for JumpConfig {
+    fn default() -> Self {
+        JumpConfig {
+            speed: 8,
+            gravity: 1,
+        }
+    }
+}
+
+/// How high above the ground an entity is, e.g. while jumping or being thrown. The `Position`
+/// stays on the ground, so depth sorting and collisions with the ground follow where the entity
+/// is going to land, only the sprite is drawn further up.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Elevation {
+    height: i32,
+    /// pixels per frame, upwards is positive
+    vertical_speed: i32,
+}
+
+impl Elevation {
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn vertical_speed(&self) -> i32 {
+        self.vertical_speed
+    }
+
+    pub fn is_grounded(&self) -> bool {
+        self.height == 0 && self.vertical_speed <= 0
+    }
+
+    /// Sends the entity upwards, whether it is on the ground or not.
+    pub fn launch(&mut self, speed: i32) {
+        self.vertical_speed = speed;
+    }
+
+    /// Like `launch`, but only from the ground. Returns whether the jump started.
+    pub fn jump(&mut self, speed: i32) -> bool {
+        if !self.is_grounded() {
+            return false;
+        }
+        self.launch(speed);
+        true
+    }
+
+    /// Rises or falls for one frame and stops on the ground.
+    pub fn update(&mut self, gravity: i32) {
+        if self.is_grounded() {
+            return;
+        }
+
+        self.height += self.vertical_speed;
+        self.vertical_speed -= gravity;
+        if self.height <= 0 {
+            self.height = 0;
+            self.vertical_speed = 0;
+        }
+    }
+}
+
+/// A blob on the ground below its parent, which shrinks the higher the parent is.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Shadow;
+
+#[cfg(test)]
+mod tests {
+    use crate::component::elevation::Elevation;
+
+    fn heights(under_test: &mut Elevation, gravity: i32) -> Vec<i32> {
+        let mut heights = vec![];
+        loop {
+            under_test.update(gravity);
+            heights.push(under_test.height());
+            if under_test.is_grounded() {
+                return heights;
+            }
+        }
+    }
+
+    #[test]
+    fn a_jump_rises_slower_and_slower_and_lands_on_the_ground() {
+        let mut under_test = Elevation::default();
+
+        assert!(under_test.jump(3));
+
+        assert_eq!(vec![3, 5, 6, 6, 5, 3, 0], heights(&mut under_test, 1));
+    }
+
+    #[test]
+    fn there_is_no_jumping_in_the_air() {
+        let mut under_test = Elevation::default();
+        under_test.jump(3);
+        under_test.update(1);
+
+        assert!(!under_test.jump(3));
+
+        assert_eq!(vec![5, 6, 6, 5, 3, 0], heights(&mut under_test, 1));
+    }
+
+    #[test]
+    fn a_launch_in_the_air_sends_higher() {
+        let mut under_test = Elevation::default();
+        under_test.jump(3);
+        under_test.update(1);
+
+        under_test.launch(3);
+
+        assert_eq!(vec![6, 8, 9, 9, 8, 6, 3, 0], heights(&mut under_test, 1));
+    }
+
+    #[test]
+    fn the_ground_stays_put() {
+        let mut under_test = Elevation::default();
+
+        under_test.update(1);
+
+        assert_eq!(Elevation::default(), under_test);
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::item::{DrawOrder, EquipmentSlot};
+
+/// The items Manfred wears, at most one per slot. Worn items are not in the inventory.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Equipment {
+    items: BTreeMap<EquipmentSlot, String>,
+}
+
+impl Equipment {
+    pub fn get(&self, slot: EquipmentSlot) -> Option<&str> {
+        self.items.get(&slot).map(String::as_str)
+    }
+
+    /// The ids of the worn items.
+    pub fn items(&self) -> impl Iterator<Item = &str> {
+        self.items.values().map(String::as_str)
+    }
+
+    /// Wears the item in the slot. Returns the item that was worn there before.
+    pub fn put_on(&mut self, slot: EquipmentSlot, item: &str) -> Option<String> {
+        self.items.insert(slot, item.to_string())
+    }
+
+    pub fn take_off(&mut self, slot: EquipmentSlot) -> Option<String> {
+        self.items.remove(&slot)
+    }
+}
+
+/// A sprite drawn on top of or behind its parent's sprite, always showing the same frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquipmentLayer {
+    /// the id of the worn item
+    pub item: String,
+    pub draw_order: DrawOrder,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::equipment::Equipment;
+    use crate::data::item::EquipmentSlot;
+
+    #[test]
+    fn putting_on_replaces_the_item_in_the_slot() {
+        let mut under_test = Equipment::default();
+
+        assert_eq!(None, under_test.put_on(EquipmentSlot::Head, "straw_hat"));
+        assert_eq!(None, under_test.put_on(EquipmentSlot::Back, "backpack"));
+        assert_eq!(
+            Some("straw_hat".to_string()),
+            under_test.put_on(EquipmentSlot::Head, "helmet")
+        );
+
+        assert_eq!(Some("helmet"), under_test.get(EquipmentSlot::Head));
+        assert_eq!(
+            vec!["helmet", "backpack"],
+            under_test.items().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn taking_off_empties_the_slot() {
+        let mut under_test = Equipment::default();
+        under_test.put_on(EquipmentSlot::Head, "straw_hat");
+
+        assert_eq!(
+            Some("straw_hat".to_string()),
+            under_test.take_off(EquipmentSlot::Head)
+        );
+        assert_eq!(None, under_test.take_off(EquipmentSlot::Head));
+        assert_eq!(None, under_test.get(EquipmentSlot::Head));
+    }
+
+    #[test]
+    fn equipment_survives_a_round_trip_through_ron() {
+        let mut under_test = Equipment::default();
+        under_test.put_on(EquipmentSlot::Back, "backpack");
+
+        let serialized = ron::to_string(&under_test).unwrap();
+
+        assert_eq!(under_test, ron::from_str(&serialized).unwrap());
+    }
+}
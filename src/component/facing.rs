@@ -0,0 +1,283 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::Direction;
+use crate::Velocity;
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+/// How eagerly a walking character turns.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FacingConfig {
+    /// how many acceleration steps more the new direction needs than the current one before
+    /// turning, 0 turns on every tie
+    pub hysteresis: i32,
+    /// the frames to keep facing a direction after turning, unless walking away from it
+    pub min_hold_frames: u32,
+    /// face the most recently pressed direction as long as it is held and walked towards
+    pub prefer_last_pressed: bool,
+}
+
+impl Default for FacingConfig {
+    fn default() -> Self {
+        FacingConfig {
+            hysteresis: 1,
+            min_hold_frames: 6,
+            prefer_last_pressed: false,
+        }
+    }
+}
+
+/// Remembers what the facing depends on besides the velocity, so that the sprite does not flip
+/// back and forth when walking almost diagonally.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Facing {
+    frames_since_turn: u32,
+    /// the held directions, the most recently pressed last
+    pressed: Vec<Direction>,
+}
+
+impl Facing {
+    /// Returns the direction to face after this frame's movement.
+    pub fn update(
+        &mut self,
+        current: Direction,
+        velocity: &Velocity,
+        is_pressed: impl Fn(Direction) -> bool,
+        config: &FacingConfig,
+    ) -> Direction {
+        self.pressed.retain(|direction| is_pressed(*direction));
+        for direction in DIRECTIONS.iter() {
+            if is_pressed(*direction) && !self.pressed.contains(direction) {
+                self.pressed.push(*direction);
+            }
+        }
+        self.frames_since_turn = self.frames_since_turn.saturating_add(1);
+
+        if !velocity.is_moving() {
+            return current;
+        }
+
+        let last_pressed = self.pressed.last().copied().filter(|direction| {
+            config.prefer_last_pressed && velocity.steps_towards(*direction) > 0
+        });
+        let candidate = last_pressed.unwrap_or_else(|| velocity.get_direction());
+        if candidate == current {
+            return current;
+        }
+
+        let current_steps = velocity.steps_towards(current);
+        let margin = velocity.steps_towards(candidate) - current_steps;
+        let turns = current_steps <= 0
+            || (self.frames_since_turn >= config.min_hold_frames
+                && (last_pressed.is_some() || margin >= config.hysteresis));
+
+        match turns {
+            true => {
+                self.frames_since_turn = 0;
+                candidate
+            }
+            false => current,
+        }
+    }
+}
+
+impl Default for Facing {
+    /// Free to turn right away.
+    fn default() -> Self {
+        Facing {
+            frames_since_turn: u32::MAX,
+            pressed: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::component::facing::{Facing, FacingConfig};
+    use crate::types::Direction;
+    use crate::Velocity;
+
+    fn velocity(x_steps: i32, y_steps: i32) -> Velocity {
+        let mut velocity = Velocity::new(10);
+        velocity.set_steps(x_steps, y_steps);
+        velocity
+    }
+
+    fn config(hysteresis: i32, min_hold_frames: u32, prefer_last_pressed: bool) -> FacingConfig {
+        FacingConfig {
+            hysteresis,
+            min_hold_frames,
+            prefer_last_pressed,
+        }
+    }
+
+    fn nothing_pressed(_: Direction) -> bool {
+        false
+    }
+
+    // the diagonal cases of `direction_of_velocity`, turning from the opposite direction
+    #[rstest]
+    #[case(0, -1, Direction::Up, Direction::Down)]
+    #[case(1, -1, Direction::Up, Direction::Right)]
+    #[case(1, 0, Direction::Left, Direction::Right)]
+    #[case(1, 1, Direction::Down, Direction::Up)]
+    #[case(0, 1, Direction::Down, Direction::Up)]
+    #[case(-1, 1, Direction::Down, Direction::Left)]
+    #[case(-1, 0, Direction::Right, Direction::Left)]
+    #[case(-1, -1, Direction::Up, Direction::Down)]
+    fn walking_away_turns_like_the_velocity_regardless_of_hysteresis(
+        #[case] x_steps: i32,
+        #[case] y_steps: i32,
+        #[case] current: Direction,
+        #[case] expected: Direction,
+    ) {
+        let velocity = velocity(x_steps, y_steps);
+
+        let facing =
+            Facing::default().update(current, &velocity, nothing_pressed, &config(5, 0, false));
+
+        assert_eq!(expected, facing);
+        assert_eq!(velocity.get_direction(), facing);
+    }
+
+    // on the diagonals, both directions are walked towards equally
+    #[rstest]
+    #[case(1, -1, Direction::Down, Direction::Right)]
+    #[case(1, -1, Direction::Right, Direction::Right)]
+    #[case(1, 1, Direction::Right, Direction::Up)]
+    #[case(1, 1, Direction::Up, Direction::Up)]
+    #[case(-1, 1, Direction::Up, Direction::Left)]
+    #[case(-1, 1, Direction::Left, Direction::Left)]
+    #[case(-1, -1, Direction::Left, Direction::Down)]
+    #[case(-1, -1, Direction::Down, Direction::Down)]
+    fn diagonals_without_hysteresis_break_ties_like_the_velocity(
+        #[case] x_steps: i32,
+        #[case] y_steps: i32,
+        #[case] current: Direction,
+        #[case] expected: Direction,
+    ) {
+        let facing = Facing::default().update(
+            current,
+            &velocity(x_steps, y_steps),
+            nothing_pressed,
+            &config(0, 0, false),
+        );
+
+        assert_eq!(expected, facing);
+    }
+
+    #[rstest]
+    #[case(1, -1, Direction::Down)]
+    #[case(1, -1, Direction::Right)]
+    #[case(1, 1, Direction::Right)]
+    #[case(1, 1, Direction::Up)]
+    #[case(-1, 1, Direction::Up)]
+    #[case(-1, 1, Direction::Left)]
+    #[case(-1, -1, Direction::Left)]
+    #[case(-1, -1, Direction::Down)]
+    fn diagonals_with_hysteresis_keep_facing(
+        #[case] x_steps: i32,
+        #[case] y_steps: i32,
+        #[case] current: Direction,
+    ) {
+        let facing = Facing::default().update(
+            current,
+            &velocity(x_steps, y_steps),
+            nothing_pressed,
+            &config(1, 0, false),
+        );
+
+        assert_eq!(current, facing);
+    }
+
+    #[test]
+    fn wobbling_around_the_diagonal_does_not_flicker() {
+        let mut under_test = Facing::default();
+        let mut current = Direction::Right;
+        let config = config(2, 0, false);
+
+        for (x_steps, y_steps) in vec![(5, 4), (5, 5), (5, 6), (5, 5), (4, 5), (5, 6)] {
+            current = under_test.update(
+                current,
+                &velocity(x_steps, y_steps),
+                nothing_pressed,
+                &config,
+            );
+            assert_eq!(Direction::Right, current, "at ({}, {})", x_steps, y_steps);
+        }
+
+        current = under_test.update(current, &velocity(5, 7), nothing_pressed, &config);
+        assert_eq!(Direction::Up, current);
+    }
+
+    #[test]
+    fn turns_are_held_for_the_minimum_frames() {
+        let mut under_test = Facing::default();
+        let config = config(1, 3, false);
+
+        let mut current =
+            under_test.update(Direction::Down, &velocity(4, 2), nothing_pressed, &config);
+        assert_eq!(Direction::Right, current);
+
+        current = under_test.update(current, &velocity(2, 4), nothing_pressed, &config);
+        current = under_test.update(current, &velocity(2, 4), nothing_pressed, &config);
+        assert_eq!(Direction::Right, current);
+
+        current = under_test.update(current, &velocity(2, 4), nothing_pressed, &config);
+        assert_eq!(Direction::Up, current);
+    }
+
+    #[test]
+    fn walking_away_ignores_the_minimum_frames() {
+        let mut under_test = Facing::default();
+        let config = config(1, 10, false);
+
+        let current = under_test.update(Direction::Down, &velocity(4, 0), nothing_pressed, &config);
+        let current = under_test.update(current, &velocity(-1, 0), nothing_pressed, &config);
+
+        assert_eq!(Direction::Left, current);
+    }
+
+    #[rstest]
+    #[case(true, Direction::Up)]
+    #[case(false, Direction::Right)]
+    fn most_recently_pressed_direction_can_be_preferred(
+        #[case] prefer_last_pressed: bool,
+        #[case] expected: Direction,
+    ) {
+        let mut under_test = Facing::default();
+        let config = config(1, 0, prefer_last_pressed);
+
+        let current = under_test.update(
+            Direction::Down,
+            &velocity(5, 0),
+            |direction| direction == Direction::Right,
+            &config,
+        );
+        assert_eq!(Direction::Right, current);
+
+        let current = under_test.update(
+            current,
+            &velocity(6, 1),
+            |direction| direction == Direction::Right || direction == Direction::Up,
+            &config,
+        );
+        assert_eq!(expected, current);
+
+        // after releasing it, Manfred faces where he walks again
+        let current = under_test.update(
+            current,
+            &velocity(7, 1),
+            |direction| direction == Direction::Right,
+            &config,
+        );
+        assert_eq!(Direction::Right, current);
+    }
+}
@@ -0,0 +1,202 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::data::animation::Fidget;
+
+/// When a character left alone starts to fidget.
+#[derive(Debug, Clone)]
+pub struct FidgetConfig {
+    /// seconds without input before the first fidget
+    pub delay: f32,
+    /// seconds from one fidget to the next while there still is no input
+    pub interval: f32,
+    /// the same seed picks the same fidgets, every further local player adds one to it
+    pub seed: u64,
+}
+
+impl Default for FidgetConfig {
+    fn default() -> Self {
+        FidgetConfig {
+            delay: 5.0,
+            interval: 4.0,
+            seed: 0,
+        }
+    }
+}
+
+/// Counts how long a character has been left alone and picks the idle animations to pass the
+/// time with.
+#[derive(Debug, Clone)]
+pub struct Fidgeting {
+    rng: ChaCha8Rng,
+    /// seconds since the last input or the last fidget
+    idle_seconds: f32,
+    /// whether a fidget was started since the last input
+    fidgeted: bool,
+    /// whether input arrived after a fidget was started, until that was taken
+    interrupted: bool,
+}
+
+impl Fidgeting {
+    pub fn new(seed: u64) -> Fidgeting {
+        Fidgeting {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            idle_seconds: 0.0,
+            fidgeted: false,
+            interrupted: false,
+        }
+    }
+
+    /// Starts the idle time over, called for every frame with input.
+    pub fn reset(&mut self) {
+        self.idle_seconds = 0.0;
+        self.interrupted |= self.fidgeted;
+        self.fidgeted = false;
+    }
+
+    /// Whether a fidget has to be cancelled because of input. Answers true only once.
+    pub fn take_interruption(&mut self) -> bool {
+        let interrupted = self.interrupted;
+        self.interrupted = false;
+        interrupted
+    }
+
+    /// Advances the idle time and returns the event of the fidget to start, if it is time for
+    /// one. While `can_start` is false, e.g. because some other animation plays, the fidget waits.
+    pub fn update<'a>(
+        &mut self,
+        delta_seconds: f32,
+        config: &FidgetConfig,
+        fidgets: &'a [Fidget],
+        can_start: bool,
+    ) -> Option<&'a str> {
+        self.idle_seconds += delta_seconds;
+
+        let wait = match self.fidgeted {
+            true => config.interval,
+            false => config.delay,
+        };
+        if self.idle_seconds < wait || !can_start {
+            return None;
+        }
+
+        let total_weight: u32 = fidgets.iter().map(|fidget| fidget.weight).sum();
+        if total_weight == 0 {
+            return None;
+        }
+        let mut roll = self.rng.gen_range(0..total_weight);
+        let fidget = fidgets.iter().find(|fidget| match roll < fidget.weight {
+            true => true,
+            false => {
+                roll -= fidget.weight;
+                false
+            }
+        })?;
+
+        self.idle_seconds = 0.0;
+        self.fidgeted = true;
+        Some(fidget.event.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::component::fidget::{FidgetConfig, Fidgeting};
+    use crate::data::animation::Fidget;
+
+    fn config() -> FidgetConfig {
+        FidgetConfig {
+            delay: 2.0,
+            interval: 1.0,
+            seed: 0,
+        }
+    }
+
+    fn fidgets() -> Vec<Fidget> {
+        vec![Fidget::new("yawn", 1), Fidget::new("look_around", 3)]
+    }
+
+    /// The fidgets started in `seconds` of idle time at ten frames per second.
+    fn started(under_test: &mut Fidgeting, fidgets: &[Fidget], seconds: u32) -> Vec<String> {
+        (0..seconds * 10)
+            .filter_map(|_| under_test.update(0.1, &config(), fidgets, true))
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn first_fidget_waits_for_the_delay_and_the_next_ones_for_the_interval() {
+        let fidgets = fidgets();
+        let mut under_test = Fidgeting::new(0);
+
+        for _ in 0..19 {
+            assert_eq!(None, under_test.update(0.1, &config(), &fidgets, true));
+        }
+        assert!(under_test.update(0.1, &config(), &fidgets, true).is_some());
+
+        for _ in 0..9 {
+            assert_eq!(None, under_test.update(0.1, &config(), &fidgets, true));
+        }
+        assert!(under_test.update(0.1, &config(), &fidgets, true).is_some());
+    }
+
+    #[test]
+    fn fidgets_wait_until_they_can_start() {
+        let fidgets = fidgets();
+        let mut under_test = Fidgeting::new(0);
+
+        assert_eq!(None, under_test.update(5.0, &config(), &fidgets, false));
+
+        assert!(under_test.update(0.1, &config(), &fidgets, true).is_some());
+    }
+
+    #[test]
+    fn input_starts_the_idle_time_over_and_interrupts_once() {
+        let fidgets = fidgets();
+        let mut under_test = Fidgeting::new(0);
+
+        under_test.update(1.5, &config(), &fidgets, true);
+        under_test.reset();
+        assert!(!under_test.take_interruption());
+        assert_eq!(None, under_test.update(1.5, &config(), &fidgets, true));
+
+        assert!(under_test.update(0.5, &config(), &fidgets, true).is_some());
+        under_test.reset();
+        under_test.reset();
+        assert!(under_test.take_interruption());
+        assert!(!under_test.take_interruption());
+    }
+
+    #[test]
+    fn the_same_seed_picks_the_same_fidgets() {
+        let fidgets = fidgets();
+
+        let first = started(&mut Fidgeting::new(7), &fidgets, 60);
+        let second = started(&mut Fidgeting::new(7), &fidgets, 60);
+
+        assert_eq!(first, second);
+        assert!(first.iter().any(|event| event == "yawn"));
+        assert!(first.iter().any(|event| event == "look_around"));
+    }
+
+    #[rstest]
+    #[case(vec![])]
+    #[case(vec![Fidget::new("yawn", 0)])]
+    fn nothing_to_pick_starts_nothing(#[case] fidgets: Vec<Fidget>) {
+        let mut under_test = Fidgeting::new(0);
+
+        assert!(started(&mut under_test, &fidgets, 10).is_empty());
+    }
+
+    #[test]
+    fn fidgets_without_weight_are_never_picked() {
+        let fidgets = vec![Fidget::new("yawn", 0), Fidget::new("sit_down", 1)];
+
+        let started = started(&mut Fidgeting::new(3), &fidgets, 30);
+
+        assert!(!started.is_empty());
+        assert!(started.iter().all(|event| event == "sit_down"));
+    }
+}
@@ -0,0 +1,6 @@
+/// Damages everything with health that touches its collider on the ground.
+#[derive(Debug, Clone)]
+pub struct Hazard {
+    pub name: String,
+    pub damage: u32,
+}
@@ -0,0 +1,75 @@
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Health {
+    current: u32,
+    max: u32,
+}
+
+impl Health {
+    pub fn new(max: u32) -> Health {
+        Health { current: max, max }
+    }
+
+    pub fn current(&self) -> u32 {
+        self.current
+    }
+
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current == 0
+    }
+
+    pub fn take_damage(&mut self, amount: u32) {
+        self.current = self.current.saturating_sub(amount);
+    }
+
+    pub fn heal(&mut self, amount: u32) {
+        self.current = self.current.saturating_add(amount).min(self.max);
+    }
+}
+
+/// While this is present, the entity takes no damage and its sprite flashes.
+#[derive(Debug, Clone)]
+pub struct Invulnerability {
+    pub remaining_seconds: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::health::Health;
+
+    #[test]
+    fn damage_reduces_health_down_to_zero() {
+        let mut under_test = Health::new(3);
+
+        under_test.take_damage(2);
+        assert_eq!(1, under_test.current());
+        assert!(!under_test.is_dead());
+
+        under_test.take_damage(2);
+        assert_eq!(0, under_test.current());
+        assert!(under_test.is_dead());
+    }
+
+    #[test]
+    fn healing_does_not_exceed_max() {
+        let mut under_test = Health::new(3);
+
+        under_test.take_damage(2);
+        under_test.heal(5);
+
+        assert_eq!(3, under_test.current());
+    }
+
+    #[test]
+    fn healing_by_the_largest_amount_fills_up_to_max() {
+        let mut under_test = Health::new(3);
+
+        under_test.take_damage(2);
+        under_test.heal(u32::MAX);
+
+        assert_eq!(3, under_test.current());
+    }
+}
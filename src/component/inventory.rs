@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ItemStack {
+    /// the id of the item definition
+    pub item: String,
+    pub count: u32,
+}
+
+impl ItemStack {
+    pub fn new(item: &str, count: u32) -> ItemStack {
+        ItemStack {
+            item: item.to_string(),
+            count,
+        }
+    }
+}
+
+/// A fixed number of slots, each holding a stack of one kind of item.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Inventory {
+    slots: Vec<Option<ItemStack>>,
+}
+
+impl Inventory {
+    pub fn new(capacity: usize) -> Inventory {
+        Inventory {
+            slots: vec![None; capacity],
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn slots(&self) -> &[Option<ItemStack>] {
+        &self.slots
+    }
+
+    pub fn count(&self, item: &str) -> u32 {
+        self.slots
+            .iter()
+            .flatten()
+            .filter(|stack| stack.item == item)
+            .map(|stack| stack.count)
+            .sum()
+    }
+
+    /// Adds as many of the items as fit, topping up existing stacks before using empty slots.
+    /// Returns how many items did not fit. A stack always holds at least one item.
+    pub fn add(&mut self, item: &str, count: u32, max_stack: u32) -> u32 {
+        let max_stack = max_stack.max(1);
+        let mut remaining = count;
+
+        for stack in self.slots.iter_mut().flatten() {
+            if stack.item == item && stack.count < max_stack {
+                let added = remaining.min(max_stack - stack.count);
+                stack.count += added;
+                remaining -= added;
+            }
+        }
+
+        for slot in self.slots.iter_mut().filter(|slot| slot.is_none()) {
+            if remaining == 0 {
+                break;
+            }
+            let added = remaining.min(max_stack);
+            *slot = Some(ItemStack::new(item, added));
+            remaining -= added;
+        }
+
+        remaining
+    }
+
+    /// Removes the items only if the inventory holds enough of them, emptying the last stacks
+    /// first.
+    pub fn remove(&mut self, item: &str, count: u32) -> bool {
+        if self.count(item) < count {
+            return false;
+        }
+
+        let mut remaining = count;
+        for slot in self.slots.iter_mut().rev() {
+            if let Some(stack) = slot.as_mut().filter(|stack| stack.item == item) {
+                let removed = remaining.min(stack.count);
+                stack.count -= removed;
+                remaining -= removed;
+
+                if stack.count == 0 {
+                    *slot = None;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// Marks the background of the inventory panel.
+pub struct InventoryPanel;
+
+/// Marks the text node that lists the inventory slots.
+pub struct InventoryText;
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::component::inventory::{Inventory, ItemStack};
+
+    #[rstest]
+    #[case(3, 0, 3)]
+    #[case(10, 0, 10)]
+    #[case(25, 5, 20)]
+    #[case(30, 10, 20)]
+    fn adding_fills_stacks_up_to_capacity(
+        #[case] added: u32,
+        #[case] expected_left_over: u32,
+        #[case] expected_count: u32,
+    ) {
+        let mut under_test = Inventory::new(2);
+
+        let left_over = under_test.add("apple", added, 10);
+
+        assert_eq!(expected_left_over, left_over);
+        assert_eq!(expected_count, under_test.count("apple"));
+    }
+
+    #[test]
+    fn adding_tops_up_existing_stacks_first() {
+        let mut under_test = Inventory::new(3);
+        under_test.add("apple", 4, 5);
+        under_test.add("stick", 1, 5);
+
+        under_test.add("apple", 3, 5);
+
+        assert_eq!(
+            &[
+                Some(ItemStack::new("apple", 5)),
+                Some(ItemStack::new("stick", 1)),
+                Some(ItemStack::new("apple", 2)),
+            ],
+            under_test.slots()
+        );
+    }
+
+    #[test]
+    fn items_without_a_stack_size_take_one_slot_each() {
+        let mut under_test = Inventory::new(2);
+
+        let left_over = under_test.add("apple", 3, 0);
+
+        assert_eq!(1, left_over);
+        assert_eq!(
+            &[
+                Some(ItemStack::new("apple", 1)),
+                Some(ItemStack::new("apple", 1)),
+            ],
+            under_test.slots()
+        );
+    }
+
+    #[test]
+    fn removing_more_than_held_changes_nothing() {
+        let mut under_test = Inventory::new(2);
+        under_test.add("apple", 3, 10);
+
+        assert!(!under_test.remove("apple", 4));
+        assert_eq!(3, under_test.count("apple"));
+    }
+
+    #[test]
+    fn removing_empties_slots() {
+        let mut under_test = Inventory::new(2);
+        under_test.add("apple", 7, 5);
+
+        assert!(under_test.remove("apple", 3));
+
+        assert_eq!(
+            &[Some(ItemStack::new("apple", 4)), None],
+            under_test.slots()
+        );
+    }
+
+    #[test]
+    fn inventory_survives_a_round_trip_through_ron() {
+        let mut under_test = Inventory::new(3);
+        under_test.add("apple", 3, 10);
+        under_test.add("rusty_key", 1, 1);
+
+        let serialized = ron::to_string(&under_test).unwrap();
+
+        assert_eq!(under_test, ron::from_str(&serialized).unwrap());
+    }
+}
@@ -0,0 +1,7 @@
+use crate::component::inventory::ItemStack;
+
+/// An item lying in the world, waiting to be picked up.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Item {
+    pub stack: ItemStack,
+}
@@ -0,0 +1,5 @@
+/// Marks entities that belong to the current level and are removed when another level is loaded.
+pub struct LevelObject;
+
+/// Marks the black screen that is faded in and out when switching levels.
+pub struct FadeOverlay;
@@ -1,5 +1,6 @@
-use crate::Direction;
+use crate::types::Direction;
 
+#[derive(Debug)]
 pub struct Manfred {
     pub view_direction: Direction,
 }
@@ -1,6 +1,39 @@
+use bevy::prelude::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::system::position::FromXAndY;
+
+pub mod animation;
+pub mod camera;
+pub mod chunk;
+pub mod collider;
+pub mod console;
+pub mod day_night;
+#[cfg(feature = "debug_overlay")]
+pub mod debug_overlay;
+pub mod door;
+pub mod elevation;
+pub mod equipment;
+pub mod facing;
+pub mod fidget;
+pub mod hazard;
+pub mod health;
+pub mod inventory;
+pub mod item;
+pub mod level;
 pub mod manfred;
+pub mod network;
+pub mod npc;
+pub mod obstacle;
+pub mod player;
+pub mod quest;
+pub mod surface;
+pub mod trigger;
 pub mod velocity;
 
+/// Where an entity is in whole pixels. For everything that moves, this is what the movement is
+/// computed on, the `Transform` merely follows it, so that no floating point errors accumulate.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Position {
     pub x: i32,
     pub y: i32,
@@ -10,4 +43,14 @@ impl Position {
     pub fn new(x: i32, y: i32) -> Position {
         Position { x, y }
     }
+
+    pub fn translation(&self) -> Vec3 {
+        Vec3::compute_from_x_y(self.x as f32, self.y as f32)
+    }
+
+    /// The translation of a sprite that far above the position. It is sorted by depth where it
+    /// stands on the ground.
+    pub fn elevated_translation(&self, height: i32) -> Vec3 {
+        self.translation() + Vec3::new(0.0, height as f32, 0.0)
+    }
 }
@@ -0,0 +1,9 @@
+use crate::types::Direction;
+
+/// The Manfred of a player on another machine, moved by the states the server sends. It only
+/// looks like Manfred, so it does not open doors, trigger quests or get an inventory here.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct RemotePlayer {
+    pub id: u32,
+    pub view_direction: Direction,
+}
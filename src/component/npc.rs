@@ -0,0 +1,5 @@
+/// Someone Manfred can talk to.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Npc {
+    pub name: String,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Keeps everyone with a collider from walking through its collider, unless they are at least as
+/// high in the air as the obstacle, e.g. when jumping over a fence.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Obstacle {
+    /// in pixels, like `Elevation`
+    pub height: i32,
+}
+
+impl Obstacle {
+    pub fn blocks(&self, elevation: i32) -> bool {
+        elevation < self.height
+    }
+}
@@ -0,0 +1,32 @@
+use bevy::prelude::{Color, Vec2};
+
+/// How far apart the Manfreds of the players start.
+const PLAYER_SPACING: f32 = 40.0;
+
+/// Tells the Manfreds of the players apart.
+const PLAYER_COLORS: [Color; 4] = [
+    Color::WHITE,
+    Color::rgb(0.6, 0.8, 1.0),
+    Color::rgb(1.0, 0.75, 0.6),
+    Color::rgb(0.7, 1.0, 0.65),
+];
+
+/// The colour the Manfred of the player with the given index is tinted with.
+pub fn player_color(index: usize) -> Color {
+    PLAYER_COLORS[index % PLAYER_COLORS.len()]
+}
+
+/// Marks the character of a local player. The index selects the player's input bindings in the
+/// `MovementConfig`. Characters without it are controlled by the first player.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Player {
+    pub index: usize,
+}
+
+impl Player {
+    /// Where the player's Manfred is put relative to a start or spawn point, so the Manfreds do
+    /// not stand on top of each other.
+    pub fn start_offset(&self) -> Vec2 {
+        Vec2::new(PLAYER_SPACING * self.index as f32, 0.0)
+    }
+}
@@ -0,0 +1,5 @@
+/// Marks the background of the quest log.
+pub struct QuestLogPanel;
+
+/// Marks the text node that lists the quests.
+pub struct QuestLogText;
@@ -0,0 +1,18 @@
+use bevy::prelude::Vec2;
+
+use crate::types::surface::Surface;
+
+/// An area around the entity's position where the ground differs from the rest of the level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfaceArea {
+    pub surface: Surface,
+    pub size: Vec2,
+}
+
+impl SurfaceArea {
+    pub fn contains(&self, position: Vec2, point: Vec2) -> bool {
+        let half_size = self.size / 2.0;
+        let (min, max) = (position - half_size, position + half_size);
+        min.x <= point.x && point.x <= max.x && min.y <= point.y && point.y <= max.y
+    }
+}
@@ -0,0 +1,78 @@
+use bevy::prelude::{Entity, Vec2};
+
+/// An invisible area around the entity's position that reports Manfred entering, staying in and
+/// leaving it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerZone {
+    pub name: String,
+    pub size: Vec2,
+    occupants: Vec<Occupant>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Occupant {
+    pub entity: Entity,
+    pub seconds_inside: f32,
+}
+
+impl TriggerZone {
+    pub fn new(name: &str, width: f32, height: f32) -> TriggerZone {
+        TriggerZone {
+            name: name.to_string(),
+            size: Vec2::new(width, height),
+            occupants: vec![],
+        }
+    }
+
+    /// Returns the lower left and the upper right corner of the zone.
+    pub fn bounds(&self, position: Vec2) -> (Vec2, Vec2) {
+        let half_size = self.size / 2.0;
+        (position - half_size, position + half_size)
+    }
+
+    pub fn contains(&self, position: Vec2, point: Vec2) -> bool {
+        let (min, max) = self.bounds(position);
+        min.x <= point.x && point.x <= max.x && min.y <= point.y && point.y <= max.y
+    }
+
+    pub fn occupants(&self) -> &[Occupant] {
+        &self.occupants
+    }
+
+    pub fn is_occupied_by(&self, entity: Entity) -> bool {
+        self.occupants
+            .iter()
+            .any(|occupant| occupant.entity == entity)
+    }
+
+    pub(crate) fn set_occupants(&mut self, occupants: Vec<Occupant>) {
+        self.occupants = occupants;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Vec2;
+    use rstest::*;
+
+    use crate::component::trigger::TriggerZone;
+
+    #[rstest]
+    #[case(100.0, 100.0, true)]
+    #[case(80.0, 90.0, true)]
+    #[case(120.0, 110.0, true)]
+    #[case(79.0, 100.0, false)]
+    #[case(100.0, 111.0, false)]
+    fn zone_contains_points_within_its_bounds(
+        #[case] x: f32,
+        #[case] y: f32,
+        #[case] expected: bool,
+    ) {
+        let under_test = TriggerZone::new("old well", 40.0, 20.0);
+
+        assert_eq!(
+            expected,
+            under_test.contains(Vec2::new(100.0, 100.0), Vec2::new(x, y))
+        );
+    }
+}
@@ -1,13 +1,23 @@
 use std::cmp::{max, Ordering};
 
+use bevy::prelude::Vec2;
+
+use crate::types::easing::{Easing, MovementProfile};
+use crate::types::integer_math::{divide_by_sqrt_rounded, divide_rounded, integer_sqrt_rounded};
 use crate::types::limited_int::LimitedInt;
 use crate::types::Direction;
 
+/// Larger movements are halved until they fit, so their squares never overflow.
+const MAX_MOVEMENT: u64 = 1 << 20;
+
 #[derive(Debug, Clone)]
 pub struct Velocity<const ACCELERATION_STEPS: u16> {
     x: LimitedInt<ACCELERATION_STEPS>,
     y: LimitedInt<ACCELERATION_STEPS>,
     max_speed: i32,
+    profile: MovementProfile,
+    /// whether the last change of the steps made it slower, which selects the easing curve
+    slowing_down: bool,
 }
 
 impl<const ACCELERATION_STEPS: u16> Velocity<ACCELERATION_STEPS> {
@@ -16,9 +26,24 @@ impl<const ACCELERATION_STEPS: u16> Velocity<ACCELERATION_STEPS> {
             x: LimitedInt::new(0),
             y: LimitedInt::new(0),
             max_speed: max_speed as i32,
+            profile: MovementProfile::default(),
+            slowing_down: false,
         }
     }
 
+    pub fn with_profile(mut self, profile: MovementProfile) -> Velocity<ACCELERATION_STEPS> {
+        self.profile = profile;
+        self
+    }
+
+    pub fn set_profile(&mut self, profile: MovementProfile) {
+        self.profile = profile;
+    }
+
+    pub fn profile(&self) -> &MovementProfile {
+        &self.profile
+    }
+
     pub fn x(&self) -> i32 {
         match self.y.value() {
             0 => self.scale_coordinate_by_acceleration_steps(self.x.value()),
@@ -33,46 +58,249 @@ impl<const ACCELERATION_STEPS: u16> Velocity<ACCELERATION_STEPS> {
         }
     }
 
-    fn scale_coordinate_by_acceleration_steps(&self, coordinate: i32) -> i32 {
-        let acceleration_steps = ACCELERATION_STEPS as i32;
+    pub fn max_speed(&self) -> i32 {
+        self.max_speed
+    }
 
-        if coordinate == acceleration_steps {
-            self.max_speed
-        } else if coordinate == -acceleration_steps {
-            -self.max_speed
-        } else {
-            coordinate * self.max_speed / acceleration_steps
+    /// The speed in pixels per tick. Diagonal movement has the same speed as movement along an
+    /// axis with as many acceleration steps, so this never exceeds the max speed.
+    pub fn speed(&self) -> i32 {
+        self.easing().speed(
+            self.longest_steps(),
+            ACCELERATION_STEPS as i32,
+            self.max_speed,
+        )
+    }
+
+    fn easing(&self) -> &Easing {
+        match self.slowing_down {
+            true => &self.profile.deceleration,
+            false => &self.profile.acceleration,
+        }
+    }
+
+    fn longest_steps(&self) -> i32 {
+        max(self.x.abs(), self.y.abs())
+    }
+
+    /// The heading in radians, counterclockwise with 0 pointing right. 0 as well when standing.
+    pub fn angle(&self) -> f32 {
+        (self.y.value() as f32).atan2(self.x.value() as f32)
+    }
+
+    /// The movement per tick as a vector.
+    pub fn to_vec2(&self) -> Vec2 {
+        Vec2::new(self.x() as f32, self.y() as f32)
+    }
+
+    /// A velocity moving by the given vector per tick, as far as the max speed allows.
+    pub fn from_vec2(vector: Vec2, max_speed: u16) -> Velocity<ACCELERATION_STEPS> {
+        let mut velocity = Velocity::new(max_speed);
+        velocity.set_from_vector(vector);
+        velocity
+    }
+
+    pub fn set_max_speed(&mut self, max_speed: u16) {
+        self.max_speed = max_speed as i32;
+    }
+
+    /// The number of acceleration steps taken in x direction, negative when moving left.
+    pub fn x_steps(&self) -> i32 {
+        self.x.value()
+    }
+
+    /// The number of acceleration steps taken in y direction, negative when moving down.
+    pub fn y_steps(&self) -> i32 {
+        self.y.value()
+    }
+
+    /// The number of acceleration steps taken towards the direction, negative when moving away.
+    pub fn steps_towards(&self, direction: Direction) -> i32 {
+        match direction {
+            Direction::Up => self.y.value(),
+            Direction::Down => -self.y.value(),
+            Direction::Left => -self.x.value(),
+            Direction::Right => self.x.value(),
         }
     }
 
+    fn scale_coordinate_by_acceleration_steps(&self, coordinate: i32) -> i32 {
+        coordinate.signum() * self.speed()
+    }
+
     fn scale_coordinate_by_length(&self, coord: i32, other_coord: i32) -> i32 {
-        let actual_acceleration_step = max(coord.abs(), other_coord.abs());
-        let current_speed = self.max_speed * actual_acceleration_step / ACCELERATION_STEPS as i32;
+        let current_speed = self.speed();
 
-        let length = ((coord * coord + other_coord * other_coord) as f32).sqrt();
+        // integer arithmetic only, so that every machine computes the very same movement
+        let length_squared = (coord as i64).pow(2) + (other_coord as i64).pow(2);
 
-        ((coord * current_speed) as f32 / length).round() as i32
+        divide_by_sqrt_rounded(coord as i64 * current_speed as i64, length_squared as u64) as i32
     }
 
     pub fn accelerate(&mut self, direction: Direction) {
         match direction {
-            Direction::Up => self.y += 1,
-            Direction::Down => self.y -= 1,
-            Direction::Left => self.x -= 1,
-            Direction::Right => self.x += 1,
+            Direction::Up => self.change_steps(self.x, self.y + 1),
+            Direction::Down => self.change_steps(self.x, self.y - 1),
+            Direction::Left => self.change_steps(self.x - 1, self.y),
+            Direction::Right => self.change_steps(self.x + 1, self.y),
         }
     }
 
     pub fn decelerate(&mut self, direction: Direction) {
         match direction {
-            Direction::Up if self.y > 0 => self.y -= 1,
-            Direction::Down if self.y < 0 => self.y += 1,
-            Direction::Left if self.x < 0 => self.x += 1,
-            Direction::Right if self.x > 0 => self.x -= 1,
+            Direction::Up if self.y > 0 => self.change_steps(self.x, self.y - 1),
+            Direction::Down if self.y < 0 => self.change_steps(self.x, self.y + 1),
+            Direction::Left if self.x < 0 => self.change_steps(self.x + 1, self.y),
+            Direction::Right if self.x > 0 => self.change_steps(self.x - 1, self.y),
             _ => {}
         }
     }
 
+    /// Every change of the steps goes through here to keep track of whether it sped up or slowed
+    /// down.
+    fn change_steps(
+        &mut self,
+        x: LimitedInt<ACCELERATION_STEPS>,
+        y: LimitedInt<ACCELERATION_STEPS>,
+    ) {
+        let longest_before = self.longest_steps();
+        self.x = x;
+        self.y = y;
+
+        match self.longest_steps().cmp(&longest_before) {
+            Ordering::Less => self.slowing_down = true,
+            Ordering::Greater => self.slowing_down = false,
+            Ordering::Equal => {}
+        }
+    }
+
+    /// Sets the velocity to full speed in the given direction, e.g. `(3, -1)`. Without further
+    /// acceleration, it slows down step by step as usual.
+    pub fn knock_back(&mut self, direction: (i32, i32)) {
+        if direction != (0, 0) {
+            self.set_heading(
+                direction.0 as i64,
+                direction.1 as i64,
+                ACCELERATION_STEPS as i32,
+            );
+        }
+    }
+
+    /// Moves by the given vector per tick, or as close to it as the acceleration steps and the max
+    /// speed allow, e.g. for NPCs or conveyor belts.
+    pub fn set_from_vector(&mut self, vector: Vec2) {
+        match whole_pixels(vector) {
+            Some((x, y)) => self.set_movement(x, y),
+            None => self.stop(),
+        }
+    }
+
+    /// Adds the impulse to the current movement, limited by the max speed like any velocity.
+    pub fn apply_impulse(&mut self, impulse: Vec2) {
+        match whole_pixels(impulse) {
+            Some((x, y)) => self.set_movement(
+                (self.x() as i64).saturating_add(x),
+                (self.y() as i64).saturating_add(y),
+            ),
+            None => self.stop(),
+        }
+    }
+
+    /// Moves by the given pixels per tick, or as close to it as the acceleration steps, the easing
+    /// curves and the max speed allow.
+    pub fn set_movement(&mut self, mut x: i64, mut y: i64) {
+        while x.unsigned_abs().max(y.unsigned_abs()) > MAX_MOVEMENT {
+            x /= 2;
+            y /= 2;
+        }
+        let mut candidate = self.clone();
+        if (x, y) == (0, 0) {
+            candidate.stop();
+        } else {
+            // the curve is inverted by trying every number of steps, the one closest to the
+            // current steps wins among equally fast ones
+            let length = integer_sqrt_rounded((x * x + y * y) as u128) as i32;
+            let current = self.longest_steps();
+            let longest_steps = (0..=ACCELERATION_STEPS as i32)
+                .min_by_key(|steps| {
+                    (
+                        (self.speed_after_change(*steps) - length).abs(),
+                        (steps - current).abs(),
+                    )
+                })
+                .unwrap_or(current);
+            candidate.set_heading(x, y, longest_steps);
+        }
+
+        // the rounding of slow diagonal movement can point the candidate elsewhere, so the current
+        // steps are kept if they come as close, e.g. when adding no impulse at all
+        let distance =
+            |velocity: &Self| (velocity.x() as i64 - x).pow(2) + (velocity.y() as i64 - y).pow(2);
+        if distance(&candidate) < distance(self) {
+            *self = candidate;
+        }
+    }
+
+    /// The speed once the longer axis is changed to the given steps, following the curve that
+    /// change selects.
+    fn speed_after_change(&self, longest_steps: i32) -> i32 {
+        let easing = match longest_steps.cmp(&self.longest_steps()) {
+            Ordering::Less => &self.profile.deceleration,
+            Ordering::Greater => &self.profile.acceleration,
+            Ordering::Equal => self.easing(),
+        };
+        easing.speed(longest_steps, ACCELERATION_STEPS as i32, self.max_speed)
+    }
+
+    /// Slows down evenly, so that calling this once per tick while counting the remaining ticks
+    /// down to 1 stops in exactly that many ticks.
+    pub fn brake(&mut self, remaining_ticks: u32) {
+        if remaining_ticks <= 1 {
+            self.stop();
+            return;
+        }
+
+        let remaining_ticks = remaining_ticks as i64;
+        let slow_down = |steps: i32| {
+            let scaled = steps as i64 * (remaining_ticks - 1);
+            // rounded to the nearest step, halves away from zero
+            ((scaled * 2 + scaled.signum() * remaining_ticks) / (remaining_ticks * 2)) as i32
+        };
+        self.change_steps(
+            LimitedInt::new(slow_down(self.x.value())),
+            LimitedInt::new(slow_down(self.y.value())),
+        );
+    }
+
+    /// Points the steps into the direction, with the longer axis taking the given number of
+    /// steps.
+    fn set_heading(&mut self, x: i64, y: i64, longest_steps: i32) {
+        let longest = x.abs().max(y.abs());
+        let scale = |coordinate: i64| {
+            LimitedInt::new(divide_rounded(coordinate * longest_steps as i64, longest) as i32)
+        };
+        self.change_steps(scale(x), scale(y));
+    }
+
+    /// Sets the acceleration steps directly, e.g. to the ones received from the server.
+    pub fn set_steps(&mut self, x_steps: i32, y_steps: i32) {
+        self.change_steps(LimitedInt::new(x_steps), LimitedInt::new(y_steps));
+    }
+
+    /// Whether the steps last went down, which makes the speed follow the deceleration curve.
+    pub fn is_slowing_down(&self) -> bool {
+        self.slowing_down
+    }
+
+    /// Picks the easing curve directly, e.g. the one the server's velocity follows.
+    pub fn set_slowing_down(&mut self, slowing_down: bool) {
+        self.slowing_down = slowing_down;
+    }
+
+    pub fn stop(&mut self) {
+        self.change_steps(LimitedInt::new(0), LimitedInt::new(0));
+    }
+
     pub fn is_moving(&self) -> bool {
         self.x != 0 || self.y != 0
     }
@@ -98,11 +326,23 @@ impl<const ACCELERATION_STEPS: u16> Velocity<ACCELERATION_STEPS> {
     }
 }
 
+/// The vector in whole pixels, nothing if it is not finite. Movement is computed in integers from
+/// there on.
+fn whole_pixels(vector: Vec2) -> Option<(i64, i64)> {
+    match vector.x.is_finite() && vector.y.is_finite() {
+        true => Some((vector.x.round() as i64, vector.y.round() as i64)),
+        false => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use bevy::prelude::Vec2;
+    use quickcheck_macros::quickcheck;
     use rstest::*;
 
     use crate::component::velocity::Velocity;
+    use crate::types::easing::{Easing, MovementProfile};
     use crate::types::Direction;
 
     #[rstest]
@@ -203,6 +443,37 @@ mod tests {
         assert!(!under_test.is_moving());
     }
 
+    #[rstest]
+    #[case((1, 0), 10, 0)]
+    #[case((0, -3), 0, -10)]
+    #[case((-2, 2), -7, 7)]
+    #[case((4, -2), 9, -5)]
+    #[case((i32::MIN, i32::MAX), -7, 7)]
+    fn knock_back_moves_at_full_speed_away(
+        #[case] direction: (i32, i32),
+        #[case] expected_x: i32,
+        #[case] expected_y: i32,
+    ) {
+        let mut under_test = Velocity::<5>::new(10);
+        under_test.accelerate(Direction::Up);
+
+        under_test.knock_back(direction);
+
+        assert_eq!(expected_x, under_test.x());
+        assert_eq!(expected_y, under_test.y());
+    }
+
+    #[test]
+    fn stop_stops_moving() {
+        let mut under_test = Velocity::<5>::new(10);
+        under_test.accelerate(Direction::Up);
+        under_test.accelerate(Direction::Left);
+
+        under_test.stop();
+
+        assert!(!under_test.is_moving());
+    }
+
     #[test]
     fn is_not_moving_initally() {
         let under_test = Velocity::<5>::new(5);
@@ -343,4 +614,274 @@ mod tests {
         assert!(2251000 > x * x + y * y, "x*x+y*y: {}", x * x + y * y);
         assert!(2249000 < x * x + y * y, "x*x+y*y: {}", x * x + y * y);
     }
+
+    #[quickcheck]
+    fn speed_never_exceeds_max_speed(accelerations: Vec<(bool, bool)>, max_speed: u16) {
+        let mut under_test = Velocity::<10>::new(max_speed);
+        for (horizontal, positive) in accelerations {
+            under_test.accelerate(match (horizontal, positive) {
+                (true, true) => Direction::Right,
+                (true, false) => Direction::Left,
+                (false, true) => Direction::Up,
+                (false, false) => Direction::Down,
+            });
+
+            let x = under_test.x() as i64;
+            let y = under_test.y() as i64;
+            let limit = max_speed as i64 + 1;
+            assert!(x * x + y * y <= limit * limit, "({}, {})", x, y);
+        }
+    }
+
+    #[rstest]
+    #[case(vec![], 0, 0.0)]
+    #[case(vec![Direction::Right; 4], 40, 0.0)]
+    #[case(vec![Direction::Up; 10], 100, std::f32::consts::FRAC_PI_2)]
+    #[case(vec![Direction::Left, Direction::Down], 10, -3.0 * std::f32::consts::FRAC_PI_4)]
+    #[case(vec![Direction::Left, Direction::Left, Direction::Up], 20, 2.6779451)]
+    fn speed_and_angle(
+        #[case] acceleration_steps: Vec<Direction>,
+        #[case] expected_speed: i32,
+        #[case] expected_angle: f32,
+    ) {
+        let mut under_test = Velocity::<10>::new(100);
+
+        for acceleration in acceleration_steps {
+            under_test.accelerate(acceleration);
+        }
+
+        assert_eq!(expected_speed, under_test.speed());
+        assert!((expected_angle - under_test.angle()).abs() < 1e-5);
+    }
+
+    #[rstest]
+    #[case(Vec2::new(3.0, 0.0), 3, 0)]
+    #[case(Vec2::new(0.0, -30.0), 0, -10)]
+    #[case(Vec2::new(100.0, 100.0), 7, 7)]
+    #[case(Vec2::new(-2.0, 4.0), -2, 4)]
+    #[case(Vec2::ZERO, 0, 0)]
+    #[case(Vec2::new(f32::NAN, 1.0), 0, 0)]
+    #[case(Vec2::new(f32::MAX, f32::MAX), 7, 7)]
+    #[case(Vec2::new(f32::MIN, 0.4), -10, 0)]
+    #[case(Vec2::new(2.6, -0.4), 3, 0)]
+    fn set_from_vector_moves_as_close_as_possible(
+        #[case] vector: Vec2,
+        #[case] expected_x: i32,
+        #[case] expected_y: i32,
+    ) {
+        let mut under_test = Velocity::<10>::new(10);
+        under_test.accelerate(Direction::Up);
+
+        under_test.set_from_vector(vector);
+
+        assert_eq!(expected_x, under_test.x());
+        assert_eq!(expected_y, under_test.y());
+    }
+
+    #[test]
+    fn vectors_along_the_axes_survive_the_conversion() {
+        for vector in vec![Vec2::new(4.0, 0.0), Vec2::new(0.0, -7.0), Vec2::ZERO] {
+            assert_eq!(vector, Velocity::<10>::from_vec2(vector, 10).to_vec2());
+        }
+    }
+
+    #[test]
+    fn impulse_adds_to_the_movement() {
+        let mut under_test = Velocity::<10>::from_vec2(Vec2::new(4.0, 0.0), 10);
+
+        under_test.apply_impulse(Vec2::new(0.0, -4.0));
+        assert_eq!(Vec2::new(4.0, -4.0), under_test.to_vec2());
+
+        under_test.apply_impulse(Vec2::new(-4.0, 4.0));
+        assert!(!under_test.is_moving());
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(3)]
+    #[case(5)]
+    #[case(25)]
+    fn braking_stops_in_exactly_the_given_ticks(#[case] ticks: u32) {
+        let mut under_test = Velocity::<10>::new(100);
+        under_test.set_steps(10, -6);
+
+        let mut speed = under_test.speed();
+        for remaining_ticks in (2..=ticks).rev() {
+            under_test.brake(remaining_ticks);
+            assert!(under_test.speed() <= speed);
+            speed = under_test.speed();
+        }
+        if ticks > 2 {
+            assert!(under_test.is_moving());
+        }
+        under_test.brake(1);
+
+        assert!(!under_test.is_moving());
+    }
+
+    fn assert_within_max_speed(velocity: &Velocity<10>) {
+        assert!(
+            velocity.speed() <= velocity.max_speed(),
+            "{} > {}",
+            velocity.speed(),
+            velocity.max_speed()
+        );
+        // every coordinate is rounded on its own, which adds at most half a pixel each
+        let limit = velocity.max_speed() as f32 + 1.0;
+        assert!(
+            velocity.to_vec2().length() <= limit,
+            "{:?} is faster than {}",
+            velocity.to_vec2(),
+            limit
+        );
+    }
+
+    #[quickcheck]
+    fn set_from_vector_never_exceeds_max_speed(x: f32, y: f32, max_speed: u16) {
+        let mut under_test = Velocity::<10>::new(max_speed);
+
+        under_test.set_from_vector(Vec2::new(x, y));
+
+        assert_within_max_speed(&under_test);
+    }
+
+    #[quickcheck]
+    fn impulses_never_exceed_max_speed(impulses: Vec<(i16, i16)>, max_speed: u16) {
+        let mut under_test = Velocity::<10>::new(max_speed);
+
+        for (x, y) in impulses {
+            under_test.apply_impulse(Vec2::new(x as f32, y as f32));
+            assert_within_max_speed(&under_test);
+        }
+    }
+
+    #[quickcheck]
+    fn braking_never_speeds_up(x_steps: i8, y_steps: i8, ticks: u8) {
+        let mut under_test = Velocity::<10>::new(50);
+        under_test.set_steps(x_steps as i32, y_steps as i32);
+
+        for remaining_ticks in (1..=ticks as u32).rev() {
+            let speed = under_test.speed();
+            under_test.brake(remaining_ticks);
+            assert!(under_test.speed() <= speed);
+            assert_within_max_speed(&under_test);
+        }
+    }
+
+    #[rstest]
+    #[case(Easing::Linear)]
+    #[case(Easing::QuadraticIn)]
+    #[case(Easing::QuadraticOut)]
+    #[case(Easing::CubicIn)]
+    #[case(Easing::CubicOut)]
+    #[case(Easing::Table(vec![0, 50, 900, 1000]))]
+    fn every_curve_reaches_max_speed_after_all_acceleration_steps(#[case] easing: Easing) {
+        let profile = MovementProfile {
+            acceleration: easing.clone(),
+            deceleration: easing,
+        };
+
+        for direction in vec![
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ] {
+            let mut under_test = Velocity::<4>::new(40).with_profile(profile.clone());
+            for _ in 0..4 {
+                under_test.accelerate(direction);
+            }
+            assert_eq!(
+                direction.unit_vector() * 40.0,
+                under_test.to_vec2(),
+                "{:?}",
+                direction
+            );
+
+            under_test.accelerate(direction);
+            assert_eq!(40, under_test.speed());
+
+            for _ in 0..4 {
+                under_test.decelerate(direction);
+            }
+            assert!(!under_test.is_moving());
+        }
+    }
+
+    #[test]
+    fn speeding_up_and_slowing_down_follow_their_own_curves() {
+        let mut under_test = Velocity::<4>::new(100).with_profile(MovementProfile::smooth());
+
+        let mut speeds = vec![];
+        for _ in 0..4 {
+            under_test.accelerate(Direction::Right);
+            speeds.push(under_test.x());
+        }
+        for _ in 0..4 {
+            under_test.decelerate(Direction::Right);
+            speeds.push(under_test.x());
+        }
+
+        assert_eq!(vec![6, 25, 56, 100, 93, 75, 43, 0], speeds);
+    }
+
+    fn easing_of(index: u8) -> Easing {
+        match index % 7 {
+            0 => Easing::Linear,
+            1 => Easing::QuadraticIn,
+            2 => Easing::QuadraticOut,
+            3 => Easing::CubicIn,
+            4 => Easing::CubicOut,
+            5 => Easing::Table(vec![0, 50, 900, 1000]),
+            _ => Easing::Table(vec![0, 800, 1000]),
+        }
+    }
+
+    #[rstest]
+    #[case(MovementProfile::smooth(), 25)]
+    #[case(MovementProfile { acceleration: Easing::CubicOut, deceleration: Easing::Linear }, 87)]
+    fn vectors_are_mapped_to_steps_along_the_easing_curve(
+        #[case] profile: MovementProfile,
+        #[case] expected_speed: i32,
+    ) {
+        let mut under_test = Velocity::<10>::new(100).with_profile(profile);
+
+        under_test.set_from_vector(Vec2::new(expected_speed as f32, 0.0));
+
+        assert_eq!(5, under_test.x_steps());
+        assert_eq!(expected_speed, under_test.speed());
+    }
+
+    #[quickcheck]
+    fn applying_no_impulse_changes_nothing_on_any_curve(
+        x_steps: i8,
+        y_steps: i8,
+        slow_down: bool,
+        curves: (u8, u8),
+        max_speed: u16,
+    ) {
+        let profile = MovementProfile {
+            acceleration: easing_of(curves.0),
+            deceleration: easing_of(curves.1),
+        };
+        let mut under_test = Velocity::<10>::new(max_speed).with_profile(profile);
+        under_test.set_steps(x_steps as i32, y_steps as i32);
+        if slow_down {
+            under_test.brake(10);
+        }
+        let (steps, movement) = (
+            (under_test.x_steps(), under_test.y_steps()),
+            under_test.to_vec2(),
+        );
+
+        under_test.apply_impulse(Vec2::ZERO);
+
+        assert_eq!(
+            steps,
+            (under_test.x_steps(), under_test.y_steps()),
+            "{:?}",
+            under_test
+        );
+        assert_eq!(movement, under_test.to_vec2());
+    }
 }
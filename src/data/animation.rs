@@ -0,0 +1,281 @@
+use serde::{Deserialize, Serialize};
+
+/// The animations of one kind of character and when to switch between them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimationGraph {
+    /// animators refer to their graph by this name
+    pub name: String,
+    /// the state animators start in
+    pub initial: String,
+    pub states: Vec<AnimationState>,
+    /// transitions that can leave every state, checked after the state's own transitions and
+    /// ignored when they lead to the state that is already playing
+    #[serde(default)]
+    pub any_state: Vec<Transition>,
+    /// the idle animations to pick from when the character is left alone
+    #[serde(default)]
+    pub fidgets: Vec<Fidget>,
+}
+
+impl AnimationGraph {
+    pub fn state(&self, name: &str) -> Option<&AnimationState> {
+        self.states.iter().find(|state| state.name == name)
+    }
+
+    /// Checks that every state the graph refers to exists and has frames to show, and that every
+    /// fidget starts something.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.state(&self.initial).is_none() {
+            return Err(format!(
+                "{}: the initial state {} does not exist",
+                self.name, self.initial
+            ));
+        }
+
+        for (from, transition) in self.transitions() {
+            if self.state(&transition.to).is_none() {
+                return Err(format!(
+                    "{}: {} leads to {}, which does not exist",
+                    self.name, from, transition.to
+                ));
+            }
+        }
+
+        let triggered_by = |event: &str| {
+            self.transitions()
+                .any(|(_, transition)| matches!(&transition.when, Condition::Event(name) if name == event))
+        };
+        if let Some(fidget) = self
+            .fidgets
+            .iter()
+            .find(|fidget| !triggered_by(&fidget.event))
+        {
+            return Err(format!(
+                "{}: no transition is triggered by the fidget {}",
+                self.name, fidget.event
+            ));
+        }
+
+        match self.states.iter().find(|state| state.frames.is_empty()) {
+            Some(state) => Err(format!("{}: {} has no frames", self.name, state.name)),
+            None => Ok(()),
+        }
+    }
+
+    /// The transitions of every state and of any state, with the name of the state they leave.
+    fn transitions(&self) -> impl Iterator<Item = (&str, &Transition)> {
+        let transitions = self.states.iter().flat_map(|state| {
+            state
+                .transitions
+                .iter()
+                .map(move |transition| (state.name.as_str(), transition))
+        });
+        let any_state = self
+            .any_state
+            .iter()
+            .map(|transition| ("any state", transition));
+
+        transitions.chain(any_state)
+    }
+}
+
+/// One animation, e.g. walking or picking something up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimationState {
+    pub name: String,
+    /// the columns of the frames in the sprite atlas, the row follows the view direction
+    pub frames: Vec<u32>,
+    /// how far the animation advances per animation tick, in permille of a frame
+    #[serde(default = "one_frame")]
+    pub playback: u32,
+    /// When set, `playback` applies at this speed and the animation plays proportionally faster
+    /// or slower at other speeds, so the feet do not slide over the ground.
+    #[serde(default)]
+    pub reference_speed: Option<i32>,
+    /// one-shot animations stop at their last frame instead of starting over
+    #[serde(default = "yes")]
+    pub looping: bool,
+    /// whether transitions may leave the state before its frames were played through once
+    #[serde(default = "yes")]
+    pub interruptible: bool,
+    /// events sent when the animation reaches certain frames, so sounds stay in sync with the
+    /// animation whatever its playback speed is
+    #[serde(default)]
+    pub frame_events: Vec<FrameEvent>,
+    /// checked in order, the first one whose condition holds is taken
+    #[serde(default)]
+    pub transitions: Vec<Transition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameEvent {
+    /// the column of the frame in the sprite atlas
+    pub frame: u32,
+    pub name: String,
+}
+
+impl FrameEvent {
+    pub fn new(frame: u32, name: &str) -> FrameEvent {
+        FrameEvent {
+            frame,
+            name: name.to_string(),
+        }
+    }
+}
+
+/// An idle animation, started by triggering its event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fidget {
+    pub event: String,
+    /// how likely the fidget is picked compared to the others
+    pub weight: u32,
+}
+
+impl Fidget {
+    pub fn new(event: &str, weight: u32) -> Fidget {
+        Fidget {
+            event: event.to_string(),
+            weight,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transition {
+    pub to: String,
+    pub when: Condition,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Condition {
+    /// the speed of the velocity is at least this
+    SpeedAtLeast(i32),
+    SpeedBelow(i32),
+    /// the event was triggered on the animator since the last animation tick, e.g. "pick_up"
+    Event(String),
+    /// the frames of the state were played through at least once
+    Finished,
+}
+
+/// The animation graphs of all characters. They are stored in `assets/animations/animations.ron`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AnimationDefinitions {
+    pub graphs: Vec<AnimationGraph>,
+}
+
+impl AnimationDefinitions {
+    pub fn get(&self, name: &str) -> Option<&AnimationGraph> {
+        self.graphs.iter().find(|graph| graph.name == name)
+    }
+}
+
+fn one_frame() -> u32 {
+    1000
+}
+
+fn yes() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::data::animation::{
+        AnimationDefinitions, AnimationGraph, AnimationState, Condition, Fidget, Transition,
+    };
+    use crate::data::load_ron_file;
+
+    fn state(name: &str, frames: Vec<u32>, transitions: Vec<Transition>) -> AnimationState {
+        AnimationState {
+            name: name.to_string(),
+            frames,
+            playback: 1000,
+            reference_speed: None,
+            looping: true,
+            interruptible: true,
+            frame_events: vec![],
+            transitions,
+        }
+    }
+
+    fn to(state: &str) -> Transition {
+        Transition {
+            to: state.to_string(),
+            when: Condition::Finished,
+        }
+    }
+
+    #[test]
+    fn animation_definitions_can_be_loaded() {
+        let definitions =
+            load_ron_file::<AnimationDefinitions>("assets/animations/animations.ron").unwrap();
+
+        for graph in definitions.graphs.iter() {
+            assert_eq!(Ok(()), graph.validate());
+        }
+        assert!(definitions.get("manfred").is_some());
+    }
+
+    #[rstest]
+    #[case("idle", vec![], vec![to("walk")], true)]
+    #[case("run", vec![], vec![], false)]
+    #[case("idle", vec![to("jump")], vec![], false)]
+    #[case("idle", vec![], vec![to("jump")], false)]
+    fn validation_finds_missing_states(
+        #[case] initial: &str,
+        #[case] idle_transitions: Vec<Transition>,
+        #[case] any_state: Vec<Transition>,
+        #[case] valid: bool,
+    ) {
+        let graph = AnimationGraph {
+            name: "test".to_string(),
+            initial: initial.to_string(),
+            states: vec![
+                state("idle", vec![0], idle_transitions),
+                state("walk", vec![0, 1], vec![]),
+            ],
+            any_state,
+            fidgets: vec![],
+        };
+
+        assert_eq!(valid, graph.validate().is_ok());
+    }
+
+    #[test]
+    fn validation_finds_states_without_frames() {
+        let graph = AnimationGraph {
+            name: "test".to_string(),
+            initial: "idle".to_string(),
+            states: vec![state("idle", vec![], vec![])],
+            any_state: vec![],
+            fidgets: vec![],
+        };
+
+        assert_eq!(
+            Err("test: idle has no frames".to_string()),
+            graph.validate()
+        );
+    }
+
+    #[rstest]
+    #[case("yawn", true)]
+    #[case("sneeze", false)]
+    fn validation_finds_fidgets_that_start_nothing(#[case] event: &str, #[case] valid: bool) {
+        let graph = AnimationGraph {
+            name: "test".to_string(),
+            initial: "idle".to_string(),
+            states: vec![
+                state("idle", vec![0], vec![]),
+                state("yawn", vec![1], vec![]),
+            ],
+            any_state: vec![Transition {
+                to: "yawn".to_string(),
+                when: Condition::Event("yawn".to_string()),
+            }],
+            fidgets: vec![Fidget::new(event, 1)],
+        };
+
+        assert_eq!(valid, graph.validate().is_ok());
+    }
+}
@@ -0,0 +1,139 @@
+use std::str::FromStr;
+
+use bevy::prelude::Vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::types::Direction;
+
+/// How an item lying in the world gets into an inventory.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PickupMode {
+    /// picked up as soon as it is touched
+    Contact,
+    /// picked up when the interact key is pressed close to it
+    Interact,
+}
+
+/// Describes a kind of item. Item definitions are stored in `assets/items/items.ron`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemDefinition {
+    pub id: String,
+    pub name: String,
+    /// how many of the item fit into one inventory slot
+    pub max_stack: u32,
+    pub pickup: PickupMode,
+    pub color: (f32, f32, f32),
+    pub size: Vec2,
+    /// how the item looks when Manfred wears it, None for items that cannot be worn
+    #[serde(default)]
+    pub equipment: Option<EquipmentDefinition>,
+}
+
+/// Where an item is worn, Manfred wears at most one item per slot.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+pub enum EquipmentSlot {
+    Head,
+    Body,
+    Back,
+    Hand,
+}
+
+impl FromStr for EquipmentSlot {
+    type Err = String;
+
+    /// Parses the RON notation, e.g. `Head`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        ron::from_str(text).map_err(|error| error.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EquipmentDefinition {
+    pub slot: EquipmentSlot,
+    /// a sprite atlas laid out like Manfred's, relative to `assets`
+    pub sprite_atlas: String,
+    pub draw_order: DrawOrder,
+}
+
+/// Where a layer is drawn for every view direction, relative to the body at 0. Higher layers are
+/// drawn on top of lower ones, e.g. a backpack is -1 when facing down and 1 when facing up.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DrawOrder {
+    pub down: i8,
+    pub left: i8,
+    pub right: i8,
+    pub up: i8,
+}
+
+impl DrawOrder {
+    pub fn facing(&self, direction: Direction) -> i8 {
+        match direction {
+            Direction::Down => self.down,
+            Direction::Left => self.left,
+            Direction::Right => self.right,
+            Direction::Up => self.up,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ItemDefinitions {
+    pub items: Vec<ItemDefinition>,
+}
+
+impl ItemDefinitions {
+    pub fn get(&self, id: &str) -> Option<&ItemDefinition> {
+        self.items.iter().find(|item| item.id == id)
+    }
+
+    pub fn name_of<'a>(&'a self, id: &'a str) -> &'a str {
+        self.get(id).map_or(id, |item| item.name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::data::item::{EquipmentSlot, ItemDefinitions, PickupMode};
+    use crate::data::load_ron_file;
+
+    #[test]
+    fn item_definitions_can_be_loaded() {
+        let definitions = load_ron_file::<ItemDefinitions>("assets/items/items.ron").unwrap();
+
+        let apple = definitions.get("apple").unwrap();
+        assert_eq!("Apple", apple.name);
+        assert_eq!(PickupMode::Contact, apple.pickup);
+        assert!(definitions.items.iter().all(|item| item.max_stack > 0));
+    }
+
+    #[test]
+    fn equipment_has_existing_sprite_atlases() {
+        let definitions = load_ron_file::<ItemDefinitions>("assets/items/items.ron").unwrap();
+
+        let equipment: Vec<_> = definitions
+            .items
+            .iter()
+            .filter_map(|item| item.equipment.as_ref())
+            .collect();
+
+        assert!(equipment
+            .iter()
+            .any(|equipment| equipment.slot == EquipmentSlot::Head));
+        for equipment in equipment {
+            assert!(
+                Path::new("assets").join(&equipment.sprite_atlas).exists(),
+                "{} is missing",
+                equipment.sprite_atlas
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_items_are_named_by_their_id() {
+        let definitions = ItemDefinitions::default();
+
+        assert_eq!("pebble", definitions.name_of("pebble"));
+    }
+}
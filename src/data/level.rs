@@ -0,0 +1,222 @@
+use bevy::prelude::Vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::types::chunk::ChunkCoordinate;
+use crate::types::surface::Surface;
+
+/// Everything that is placed in a level. Levels are stored as RON files in `assets/levels`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct LevelDefinition {
+    /// chunks of streamed levels leave it out
+    #[serde(default)]
+    pub manfred_start: Vec2,
+    #[serde(default)]
+    pub trees: Vec<Vec2>,
+    #[serde(default)]
+    pub hazards: Vec<HazardDefinition>,
+    #[serde(default)]
+    pub obstacles: Vec<ObstacleDefinition>,
+    #[serde(default)]
+    pub items: Vec<ItemPlacement>,
+    #[serde(default)]
+    pub npcs: Vec<NpcDefinition>,
+    #[serde(default)]
+    pub triggers: Vec<TriggerDefinition>,
+    #[serde(default)]
+    pub spawn_points: Vec<SpawnPoint>,
+    #[serde(default)]
+    pub doors: Vec<DoorDefinition>,
+    #[serde(default)]
+    pub streaming: Option<StreamingDefinition>,
+    /// the ground everywhere outside of the surface areas
+    #[serde(default)]
+    pub default_surface: Surface,
+    #[serde(default)]
+    pub surfaces: Vec<SurfaceDefinition>,
+    /// the sound file looped while Manfred is in the level, relative to `assets`
+    #[serde(default)]
+    pub ambient: Option<String>,
+}
+
+impl LevelDefinition {
+    pub fn spawn_point(&self, name: &str) -> Option<Vec2> {
+        self.spawn_points
+            .iter()
+            .find(|spawn_point| spawn_point.name == name)
+            .map(|spawn_point| spawn_point.position)
+    }
+}
+
+/// Something that hurts on contact, e.g. a thorn bush.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HazardDefinition {
+    pub name: String,
+    pub position: Vec2,
+    pub size: Vec2,
+    pub damage: u32,
+}
+
+/// Something in the way that can be jumped over if it is low enough, e.g. a fence.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObstacleDefinition {
+    pub name: String,
+    pub position: Vec2,
+    pub size: Vec2,
+    /// how high Manfred has to jump to get over it
+    pub height: i32,
+}
+
+/// Items lying around in the level, see `assets/items/items.ron` for the known items.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemPlacement {
+    pub item: String,
+    pub position: Vec2,
+    #[serde(default = "one")]
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NpcDefinition {
+    /// quests refer to NPCs by this name
+    pub name: String,
+    pub position: Vec2,
+    /// the colours to draw Manfred's sprites in, see `assets/palettes/palettes.ron`, without one
+    /// the NPC is a plain rectangle
+    #[serde(default)]
+    pub palette: Option<String>,
+}
+
+/// An invisible area, e.g. for starting a dialogue when Manfred walks into it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TriggerDefinition {
+    pub name: String,
+    pub position: Vec2,
+    pub size: Vec2,
+}
+
+/// Large levels are split into square chunks which are only spawned around Manfred. The chunk at
+/// (x, y) is read from `<directory>/<x>_<y>.ron`, a level file whose positions are in the
+/// coordinates of the whole level, so depth sorting works the same across chunk borders. Chunks
+/// without a file are empty.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamingDefinition {
+    pub chunk_size: f32,
+    pub directory: String,
+}
+
+impl StreamingDefinition {
+    pub fn chunk_path(&self, chunk: ChunkCoordinate) -> String {
+        format!("{}/{}_{}.ron", self.directory, chunk.x, chunk.y)
+    }
+
+    pub fn chunk_at(&self, position: Vec2) -> ChunkCoordinate {
+        ChunkCoordinate::containing(position, self.chunk_size)
+    }
+}
+
+/// A named place where Manfred arrives when coming through a door.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpawnPoint {
+    pub name: String,
+    pub position: Vec2,
+}
+
+/// Takes Manfred to a spawn point of another level as soon as he steps into it. The spawn point
+/// should be outside of the doors of the target level, otherwise he walks right back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoorDefinition {
+    pub position: Vec2,
+    pub size: Vec2,
+    /// the path of the level file
+    pub target_level: String,
+    pub target_spawn_point: String,
+}
+
+/// An area with different ground than the rest of the level, e.g. a dirt path across a meadow.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SurfaceDefinition {
+    pub surface: Surface,
+    pub position: Vec2,
+    pub size: Vec2,
+}
+
+fn one() -> u32 {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Vec2;
+
+    use crate::data::level::LevelDefinition;
+    use crate::data::load_ron_file;
+    use crate::types::surface::Surface;
+
+    #[test]
+    fn meadow_level_can_be_loaded() {
+        let level = load_ron_file::<LevelDefinition>("assets/levels/meadow.ron").unwrap();
+
+        assert_eq!(Vec2::new(0.0, 0.0), level.manfred_start);
+        assert!(!level.trees.is_empty());
+        assert!(!level.hazards.is_empty());
+        assert!(!level.obstacles.is_empty());
+        assert!(!level.items.is_empty());
+        assert!(!level.npcs.is_empty());
+        assert!(level.npcs.iter().any(|npc| npc.palette.is_some()));
+        assert!(!level.triggers.is_empty());
+    }
+
+    #[test]
+    fn doors_lead_to_spawn_points_of_existing_levels() {
+        for path in [
+            "assets/levels/meadow.ron",
+            "assets/levels/farmhouse.ron",
+            "assets/levels/wilderness.ron",
+        ]
+        .iter()
+        {
+            let level = load_ron_file::<LevelDefinition>(path).unwrap();
+            assert!(!level.doors.is_empty(), "{} has no doors", path);
+
+            for door in level.doors {
+                let target = load_ron_file::<LevelDefinition>(&door.target_level).unwrap();
+                assert!(
+                    target.spawn_point(&door.target_spawn_point).is_some(),
+                    "{} has no spawn point {}",
+                    door.target_level,
+                    door.target_spawn_point
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn chunks_of_streamed_levels_can_be_loaded() {
+        let level = load_ron_file::<LevelDefinition>("assets/levels/wilderness.ron").unwrap();
+        let streaming = level.streaming.unwrap();
+
+        let chunk = load_ron_file::<LevelDefinition>(
+            &streaming.chunk_path(streaming.chunk_at(level.manfred_start)),
+        )
+        .unwrap();
+
+        assert!(!chunk.trees.is_empty());
+    }
+
+    #[test]
+    fn missing_lists_default_to_empty() {
+        let level = ron::from_str::<LevelDefinition>("(manfred_start: (1.0, 2.0))").unwrap();
+
+        assert_eq!(Vec2::new(1.0, 2.0), level.manfred_start);
+        assert!(level.trees.is_empty());
+        assert!(level.hazards.is_empty());
+        assert!(level.items.is_empty());
+        assert!(level.npcs.is_empty());
+        assert!(level.triggers.is_empty());
+        assert!(level.doors.is_empty());
+        assert_eq!(None, level.streaming);
+        assert_eq!(Surface::Grass, level.default_surface);
+        assert!(level.surfaces.is_empty());
+        assert_eq!(None, level.ambient);
+    }
+}
@@ -0,0 +1,19 @@
+use std::fs;
+
+use serde::de::DeserializeOwned;
+
+pub mod animation;
+pub mod item;
+pub mod level;
+pub mod palette;
+pub mod quest;
+pub mod save;
+pub mod sound;
+
+/// Reads a RON file, relative to the working directory, e.g. `assets/levels/meadow.ron`.
+pub fn load_ron_file<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+    let content =
+        fs::read_to_string(path).map_err(|error| format!("cannot read {}: {}", path, error))?;
+
+    ron::from_str(&content).map_err(|error| format!("cannot parse {}: {}", path, error))
+}
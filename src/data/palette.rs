@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+/// An opaque colour as red, green and blue.
+pub type Rgb = [u8; 3];
+
+/// Colour variants of a sprite atlas, so NPCs can look different from Manfred without sprites of
+/// their own. They are stored in `assets/palettes/palettes.ron`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PaletteDefinitions {
+    /// the recoloured image, relative to `assets`
+    pub sprite_atlas: String,
+    /// the colours of the image that palettes replace
+    pub source: Vec<Rgb>,
+    pub palettes: Vec<Palette>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Palette {
+    /// NPCs refer to their palette by this name
+    pub name: String,
+    /// replace the source colours at the same positions
+    pub colors: Vec<Rgb>,
+}
+
+impl PaletteDefinitions {
+    pub fn get(&self, name: &str) -> Option<&Palette> {
+        self.palettes.iter().find(|palette| palette.name == name)
+    }
+
+    /// Checks that every palette replaces each source colour.
+    pub fn validate(&self) -> Result<(), String> {
+        match self
+            .palettes
+            .iter()
+            .find(|palette| palette.colors.len() != self.source.len())
+        {
+            Some(palette) => Err(format!(
+                "{} has {} colours, but there are {} source colours",
+                palette.name,
+                palette.colors.len(),
+                self.source.len()
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Replaces the source colours in RGBA pixels with those of the palette. Transparency is
+    /// kept and colours that are not in the source stay as they are.
+    pub fn recolor(&self, palette: &Palette, rgba: &mut [u8]) {
+        for pixel in rgba.chunks_exact_mut(4) {
+            if let Some(index) = self.source.iter().position(|color| color[..] == pixel[..3]) {
+                if let Some(color) = palette.colors.get(index) {
+                    pixel[..3].copy_from_slice(color);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use rstest::*;
+
+    use crate::data::load_ron_file;
+    use crate::data::palette::{Palette, PaletteDefinitions};
+
+    fn definitions() -> PaletteDefinitions {
+        PaletteDefinitions {
+            sprite_atlas: "images/manfred_sprite_atlas.png".to_string(),
+            source: vec![[72, 36, 0], [255, 0, 0]],
+            palettes: vec![Palette {
+                name: "blue".to_string(),
+                colors: vec![[0, 0, 72], [0, 0, 255]],
+            }],
+        }
+    }
+
+    #[test]
+    fn palette_definitions_can_be_loaded() {
+        let definitions =
+            load_ron_file::<PaletteDefinitions>("assets/palettes/palettes.ron").unwrap();
+
+        assert_eq!(Ok(()), definitions.validate());
+        assert!(!definitions.palettes.is_empty());
+        assert!(Path::new("assets").join(&definitions.sprite_atlas).exists());
+    }
+
+    #[rstest]
+    #[case([72, 36, 0, 255], [0, 0, 72, 255])]
+    #[case([255, 0, 0, 128], [0, 0, 255, 128])]
+    #[case([0, 0, 0, 255], [0, 0, 0, 255])]
+    #[case([255, 255, 255, 0], [255, 255, 255, 0])]
+    fn recoloring_replaces_source_colours_only(#[case] pixel: [u8; 4], #[case] expected: [u8; 4]) {
+        let definitions = definitions();
+        let mut rgba = pixel;
+
+        definitions.recolor(definitions.get("blue").unwrap(), &mut rgba);
+
+        assert_eq!(expected, rgba);
+    }
+
+    #[test]
+    fn validation_finds_palettes_with_missing_colours() {
+        let mut definitions = definitions();
+        definitions.palettes[0].colors.pop();
+
+        assert_eq!(
+            Err("blue has 1 colours, but there are 2 source colours".to_string()),
+            definitions.validate()
+        );
+    }
+}
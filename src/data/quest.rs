@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+
+/// A quest as stored in `assets/quests/quests.ron`. A quest starts as soon as its required world
+/// flag is set, or right away if it has none, and is finished once all its stages are done.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestDefinition {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub requires_flag: Option<String>,
+    pub stages: Vec<QuestStage>,
+    #[serde(default)]
+    pub rewards: Vec<Reward>,
+}
+
+/// A step of a quest. All objectives of a stage have to be fulfilled to get to the next one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestStage {
+    pub description: String,
+    pub objectives: Vec<Objective>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Objective {
+    /// talk to the NPC with the given name
+    TalkTo(String),
+    /// pick up a number of items, identified by the item definition id
+    Collect { item: String, count: u32 },
+    /// enter the trigger zone with the given name
+    Reach(String),
+}
+
+impl Objective {
+    /// How much progress is needed to fulfill the objective.
+    pub fn required(&self) -> u32 {
+        match self {
+            Objective::Collect { count, .. } => *count,
+            _ => 1,
+        }
+    }
+
+    /// How much the event advances the objective.
+    pub fn progress_from(&self, event: &ObjectiveEvent) -> u32 {
+        match (self, event) {
+            (Objective::TalkTo(npc), ObjectiveEvent::TalkedTo(talked_to)) if npc == talked_to => 1,
+            (
+                Objective::Collect { item, .. },
+                ObjectiveEvent::Collected {
+                    item: collected,
+                    count,
+                },
+            ) if item == collected => *count,
+            (Objective::Reach(zone), ObjectiveEvent::Reached(reached)) if zone == reached => 1,
+            _ => 0,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Objective::TalkTo(npc) => format!("talk to {}", npc),
+            Objective::Collect { item, count } => format!("collect {} {}", count, item),
+            Objective::Reach(zone) => format!("reach the {}", zone),
+        }
+    }
+}
+
+/// Something that happened in the game which might advance quest objectives.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ObjectiveEvent {
+    TalkedTo(String),
+    Collected { item: String, count: u32 },
+    Reached(String),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Reward {
+    /// puts items into Manfred's inventory
+    Item { item: String, count: u32 },
+    /// sets a world flag, e.g. to start follow-up quests
+    Flag { name: String, value: i32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct QuestDefinitions {
+    pub quests: Vec<QuestDefinition>,
+}
+
+impl QuestDefinitions {
+    pub fn get(&self, id: &str) -> Option<&QuestDefinition> {
+        self.quests.iter().find(|quest| quest.id == id)
+    }
+
+    /// Drops the quests without stages, which could never be played. Returns why each was
+    /// dropped.
+    pub fn remove_invalid(&mut self) -> Vec<String> {
+        let (valid, invalid): (Vec<_>, Vec<_>) = self
+            .quests
+            .drain(..)
+            .partition(|quest| !quest.stages.is_empty());
+        self.quests = valid;
+
+        invalid
+            .iter()
+            .map(|quest| format!("quest {} has no stages", quest.id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::data::load_ron_file;
+    use crate::data::quest::{
+        Objective, ObjectiveEvent, QuestDefinition, QuestDefinitions, QuestStage,
+    };
+
+    #[test]
+    fn quest_definitions_can_be_loaded() {
+        let definitions = load_ron_file::<QuestDefinitions>("assets/quests/quests.ron").unwrap();
+
+        let lost_key = definitions.get("lost_key").unwrap();
+        assert_eq!(None, lost_key.requires_flag);
+        assert!(!lost_key.stages.is_empty());
+        assert!(!lost_key.rewards.is_empty());
+    }
+
+    #[test]
+    fn quests_without_stages_are_removed() {
+        let quest = |id: &str, stages: Vec<QuestStage>| QuestDefinition {
+            id: id.to_string(),
+            title: id.to_string(),
+            requires_flag: None,
+            stages,
+            rewards: vec![],
+        };
+        let stage = QuestStage {
+            description: "talk".to_string(),
+            objectives: vec![Objective::TalkTo("Farmer".to_string())],
+        };
+        let mut under_test = QuestDefinitions {
+            quests: vec![quest("empty", vec![]), quest("chat", vec![stage])],
+        };
+
+        let messages = under_test.remove_invalid();
+
+        assert_eq!(vec!["quest empty has no stages".to_string()], messages);
+        assert!(under_test.get("empty").is_none());
+        assert!(under_test.get("chat").is_some());
+    }
+
+    #[test]
+    fn shipped_quests_are_valid() {
+        let mut definitions =
+            load_ron_file::<QuestDefinitions>("assets/quests/quests.ron").unwrap();
+
+        assert!(definitions.remove_invalid().is_empty());
+    }
+
+    #[rstest]
+    #[case(Objective::TalkTo("Farmer".to_string()), ObjectiveEvent::TalkedTo("Farmer".to_string()), 1)]
+    #[case(Objective::TalkTo("Farmer".to_string()), ObjectiveEvent::TalkedTo("Miller".to_string()), 0)]
+    #[case(
+        Objective::Collect { item: "stick".to_string(), count: 5 },
+        ObjectiveEvent::Collected { item: "stick".to_string(), count: 2 },
+        2
+    )]
+    #[case(
+        Objective::Collect { item: "stick".to_string(), count: 5 },
+        ObjectiveEvent::Collected { item: "apple".to_string(), count: 2 },
+        0
+    )]
+    #[case(Objective::Reach("old well".to_string()), ObjectiveEvent::Reached("old well".to_string()), 1)]
+    #[case(Objective::Reach("old well".to_string()), ObjectiveEvent::TalkedTo("old well".to_string()), 0)]
+    fn events_only_advance_matching_objectives(
+        #[case] objective: Objective,
+        #[case] event: ObjectiveEvent,
+        #[case] expected_progress: u32,
+    ) {
+        assert_eq!(expected_progress, objective.progress_from(&event));
+    }
+}
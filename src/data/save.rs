@@ -0,0 +1,79 @@
+use bevy::prelude::Vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::component::equipment::Equipment;
+use crate::component::inventory::Inventory;
+use crate::resource::quest_log::QuestLog;
+use crate::resource::world_clock::WorldClock;
+use crate::resource::world_flags::WorldFlags;
+use crate::types::Direction;
+
+/// Everything needed to continue a game later. Level objects are not part of it, they are
+/// respawned from the level file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveGame {
+    /// the level file Manfred is in
+    pub level: String,
+    /// the Manfred of every player, ordered by player index
+    pub players: Vec<PlayerSave>,
+    #[serde(default)]
+    pub flags: WorldFlags,
+    #[serde(default)]
+    pub quests: QuestLog,
+    #[serde(default)]
+    pub clock: WorldClock,
+}
+
+/// The Manfred of one player.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSave {
+    /// the index of the `Player`, 0 without co-op
+    pub index: usize,
+    pub position: Vec2,
+    pub view_direction: Direction,
+    #[serde(default)]
+    pub inventory: Option<Inventory>,
+    #[serde(default)]
+    pub equipment: Option<Equipment>,
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Vec2;
+
+    use crate::component::equipment::Equipment;
+    use crate::component::inventory::Inventory;
+    use crate::data::item::EquipmentSlot;
+    use crate::data::save::{PlayerSave, SaveGame};
+    use crate::resource::quest_log::QuestLog;
+    use crate::resource::world_clock::WorldClock;
+    use crate::resource::world_flags::WorldFlags;
+    use crate::types::Direction;
+
+    #[test]
+    fn save_game_survives_a_round_trip_through_ron() {
+        let mut inventory = Inventory::new(4);
+        inventory.add("apple", 3, 10);
+        let mut flags = WorldFlags::default();
+        flags.set("farmer_trusts_manfred", 1);
+        let mut equipment = Equipment::default();
+        equipment.put_on(EquipmentSlot::Head, "straw_hat");
+        let under_test = SaveGame {
+            level: "assets/levels/farmhouse.ron".to_string(),
+            players: vec![PlayerSave {
+                index: 0,
+                position: Vec2::new(10.0, -20.0),
+                view_direction: Direction::Left,
+                inventory: Some(inventory),
+                equipment: Some(equipment),
+            }],
+            flags,
+            quests: QuestLog::default(),
+            clock: WorldClock::new(2, 21.5),
+        };
+
+        let serialized = ron::to_string(&under_test).unwrap();
+
+        assert_eq!(under_test, ron::from_str(&serialized).unwrap());
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::surface::Surface;
+
+/// The footstep sounds for every surface. They are stored in `assets/sounds/footsteps.ron`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct FootstepDefinitions {
+    pub surfaces: Vec<SurfaceSounds>,
+}
+
+/// Several sounds per surface, which are played in turn so steps do not sound all the same.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SurfaceSounds {
+    pub surface: Surface,
+    /// sound files relative to `assets`
+    pub sounds: Vec<String>,
+}
+
+impl FootstepDefinitions {
+    pub fn sounds_for(&self, surface: Surface) -> &[String] {
+        self.surfaces
+            .iter()
+            .find(|sounds| sounds.surface == surface)
+            .map_or(&[], |sounds| sounds.sounds.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::data::load_ron_file;
+    use crate::data::sound::FootstepDefinitions;
+    use crate::types::surface::Surface;
+
+    #[test]
+    fn every_surface_has_existing_footstep_sounds() {
+        let definitions =
+            load_ron_file::<FootstepDefinitions>("assets/sounds/footsteps.ron").unwrap();
+
+        for surface in [Surface::Grass, Surface::Dirt, Surface::Wood, Surface::Stone].iter() {
+            let sounds = definitions.sounds_for(*surface);
+            assert!(!sounds.is_empty(), "{:?} has no footsteps", surface);
+            for sound in sounds {
+                assert!(
+                    Path::new("assets").join(sound).exists(),
+                    "{} is missing",
+                    sound
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use bevy::prelude::Entity;
+
+/// Sent when an animation reaches a frame that has an event attached, e.g. a footstep when a foot
+/// touches the ground in the walk cycle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationEvent {
+    pub entity: Entity,
+    pub name: String,
+    /// the column of the frame in the sprite atlas
+    pub frame: u32,
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::{Entity, Vec2};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DamageSource {
+    /// what caused the damage, e.g. "thorn bush"
+    pub name: String,
+    /// the entity is knocked back away from this point
+    pub position: Vec2,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub amount: u32,
+    pub source: DamageSource,
+}
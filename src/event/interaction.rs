@@ -0,0 +1,8 @@
+use bevy::prelude::Entity;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TalkedToEvent {
+    pub talker: Entity,
+    /// the name of the NPC that was talked to
+    pub npc: String,
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::Entity;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemPickedUpEvent {
+    pub picker: Entity,
+    pub item: String,
+    pub count: u32,
+}
@@ -0,0 +1,7 @@
+pub mod animation;
+pub mod damage;
+pub mod interaction;
+pub mod item;
+pub mod sound;
+pub mod trigger;
+pub mod world_clock;
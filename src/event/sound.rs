@@ -0,0 +1,6 @@
+/// Plays a sound effect once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaySoundEvent {
+    /// the sound file relative to `assets`
+    pub sound: String,
+}
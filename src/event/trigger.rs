@@ -0,0 +1,27 @@
+use bevy::prelude::Entity;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerEnteredEvent {
+    pub entity: Entity,
+    /// the entity of the trigger zone
+    pub trigger: Entity,
+    /// the name of the trigger zone that was entered
+    pub zone: String,
+}
+
+/// Sent every tick an entity remains inside a trigger zone after entering it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerStayEvent {
+    pub entity: Entity,
+    pub trigger: Entity,
+    pub zone: String,
+    pub seconds_inside: f32,
+}
+
+/// Also sent when an entity inside a zone is despawned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerExitedEvent {
+    pub entity: Entity,
+    pub trigger: Entity,
+    pub zone: String,
+}
@@ -0,0 +1,8 @@
+use crate::resource::world_clock::TimeOfDay;
+
+/// Sent when the world clock passes from one time of day to the next, e.g. from dusk to night.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct TimeOfDayChangedEvent {
+    pub time_of_day: TimeOfDay,
+    pub day: u32,
+}
@@ -0,0 +1,254 @@
+use bevy::prelude::Vec2;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::component::collider::tree_collider;
+use crate::data::level::{ItemPlacement, LevelDefinition};
+use crate::generation::poisson_disk::poisson_disk_sample;
+
+/// Describes a forest to generate, see `assets/generation/forest.ron`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForestSettings {
+    pub seed: u64,
+    /// the lower left corner of the forest
+    pub min: Vec2,
+    /// the upper right corner of the forest
+    pub max: Vec2,
+    /// the smallest distance between two trees
+    pub tree_spacing: f32,
+    #[serde(default)]
+    pub density: DensityMap,
+    #[serde(default)]
+    pub props: Vec<PropDefinition>,
+    #[serde(default)]
+    pub paths: Vec<ForestPath>,
+}
+
+/// Scales the tree density over the area of the forest. The rows go from the bottom to the top of
+/// the forest, each from left to right. 0 means no trees at all, 1 as many as the spacing allows.
+/// Without rows, the density is 1 everywhere.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct DensityMap {
+    pub rows: Vec<Vec<f32>>,
+}
+
+impl DensityMap {
+    pub fn at(&self, min: Vec2, max: Vec2, point: Vec2) -> f32 {
+        let relative = (point - min) / (max - min);
+
+        let row = match self.rows.len() {
+            0 => return 1.0,
+            rows => &self.rows[cell_index(relative.y, rows)],
+        };
+        match row.len() {
+            0 => 1.0,
+            columns => row[cell_index(relative.x, columns)],
+        }
+    }
+}
+
+fn cell_index(relative: f32, cells: usize) -> usize {
+    ((relative * cells as f32).max(0.0) as usize).min(cells - 1)
+}
+
+/// Items scattered between the trees, e.g. mushrooms.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropDefinition {
+    pub item: String,
+    /// the smallest distance between two props of this kind
+    pub spacing: f32,
+    /// how likely a possible place gets a prop, from 0 to 1
+    pub chance: f32,
+}
+
+/// A way through the forest that is kept free of trees. To be walkable, the width has to be at
+/// least the diagonal of Manfred's collider.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForestPath {
+    pub points: Vec<Vec2>,
+    pub width: f32,
+}
+
+impl ForestPath {
+    pub fn distance_to(&self, point: Vec2) -> f32 {
+        match self.points.len() {
+            0 => f32::INFINITY,
+            1 => self.points[0].distance(point),
+            _ => self
+                .points
+                .windows(2)
+                .map(|segment| distance_to_segment(point, segment[0], segment[1]))
+                .fold(f32::INFINITY, f32::min),
+        }
+    }
+}
+
+fn distance_to_segment(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+    if length_squared == 0.0 {
+        return start.distance(point);
+    }
+
+    let t = ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+    (start + segment * t).distance(point)
+}
+
+/// Generates a level with trees and props. Manfred starts at the beginning of the first path. The
+/// same settings always give the same level.
+pub fn generate_forest(settings: &ForestSettings) -> LevelDefinition {
+    let mut rng = ChaCha8Rng::seed_from_u64(settings.seed);
+    let (min, max) = (settings.min, settings.max);
+
+    let collider = tree_collider();
+    let trunk_radius = collider.size.length() / 2.0;
+    let blocks_path = |tree: Vec2| {
+        let trunk = collider.center(tree);
+        settings
+            .paths
+            .iter()
+            .any(|path| path.distance_to(trunk) < path.width / 2.0 + trunk_radius)
+    };
+
+    let trees = poisson_disk_sample(&mut rng, min, max, settings.tree_spacing)
+        .into_iter()
+        .filter(|tree| rng.gen::<f32>() < settings.density.at(min, max, *tree))
+        .filter(|tree| !blocks_path(*tree))
+        .collect();
+
+    let mut items = vec![];
+    for prop in settings.props.iter() {
+        for position in poisson_disk_sample(&mut rng, min, max, prop.spacing) {
+            if rng.gen::<f32>() < prop.chance {
+                items.push(ItemPlacement {
+                    item: prop.item.clone(),
+                    position,
+                    count: 1,
+                });
+            }
+        }
+    }
+
+    let manfred_start = settings
+        .paths
+        .first()
+        .and_then(|path| path.points.first().copied())
+        .unwrap_or((min + max) / 2.0);
+
+    LevelDefinition {
+        manfred_start,
+        trees,
+        items,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Vec2;
+    use rstest::*;
+
+    use crate::component::collider::{manfred_collider, tree_collider};
+    use crate::data::level::LevelDefinition;
+    use crate::data::load_ron_file;
+    use crate::generation::forest::{
+        generate_forest, DensityMap, ForestPath, ForestSettings, PropDefinition,
+    };
+
+    fn settings(seed: u64) -> ForestSettings {
+        ForestSettings {
+            seed,
+            min: Vec2::new(0.0, 0.0),
+            max: Vec2::new(800.0, 600.0),
+            tree_spacing: 60.0,
+            density: DensityMap::default(),
+            props: vec![PropDefinition {
+                item: "mushroom".to_string(),
+                spacing: 100.0,
+                chance: 0.5,
+            }],
+            paths: vec![ForestPath {
+                points: vec![
+                    Vec2::new(0.0, 300.0),
+                    Vec2::new(400.0, 350.0),
+                    Vec2::new(400.0, 600.0),
+                ],
+                width: 50.0,
+            }],
+        }
+    }
+
+    #[test]
+    fn same_seed_generates_the_same_forest() {
+        assert_eq!(generate_forest(&settings(3)), generate_forest(&settings(3)));
+        assert_ne!(
+            generate_forest(&settings(3)).trees,
+            generate_forest(&settings(4)).trees
+        );
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    #[case(4)]
+    fn paths_stay_walkable(#[case] seed: u64) {
+        let settings = settings(seed);
+        let forest = generate_forest(&settings);
+        let manfred = manfred_collider();
+        let tree = tree_collider();
+
+        for segment in settings.paths[0].points.windows(2) {
+            for step in 0..=100 {
+                // put the center of Manfred's collider, i.e. his feet, on the path
+                let feet = segment[0] + (segment[1] - segment[0]) * (step as f32 / 100.0);
+                let position = feet - manfred.offset;
+
+                for trunk in forest.trees.iter() {
+                    assert!(
+                        !manfred.overlaps(position, &tree, *trunk),
+                        "tree at {:?} blocks the path at {:?}",
+                        trunk,
+                        feet
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn density_map_thins_out_trees() {
+        let mut sparse_top = settings(5);
+        sparse_top.paths.clear();
+        sparse_top.density = DensityMap {
+            rows: vec![vec![1.0], vec![0.0]],
+        };
+
+        let forest = generate_forest(&sparse_top);
+
+        assert!(forest.trees.iter().any(|tree| tree.y < 300.0));
+        assert!(forest.trees.iter().all(|tree| tree.y < 300.0));
+    }
+
+    #[test]
+    fn forest_is_a_level_that_survives_a_round_trip_through_ron() {
+        let forest = generate_forest(&settings(6));
+        assert!(!forest.items.is_empty());
+        assert_eq!(Vec2::new(0.0, 300.0), forest.manfred_start);
+
+        let serialized = ron::to_string(&forest).unwrap();
+
+        assert_eq!(
+            forest,
+            ron::from_str::<LevelDefinition>(&serialized).unwrap()
+        );
+    }
+
+    #[test]
+    fn forest_settings_can_be_loaded() {
+        let settings = load_ron_file::<ForestSettings>("assets/generation/forest.ron").unwrap();
+
+        assert!(!generate_forest(&settings).trees.is_empty());
+    }
+}
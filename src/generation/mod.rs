@@ -0,0 +1,4 @@
+//! Procedural content that is generated once from a seed and saved as an ordinary level file.
+
+pub mod forest;
+pub mod poisson_disk;
@@ -0,0 +1,127 @@
+use bevy::prelude::Vec2;
+use rand::Rng;
+
+/// How many candidates around an active point are tried before giving up on it.
+const CANDIDATES_PER_POINT: usize = 30;
+
+/// Scatters points in the rectangle from `min` to `max` so that no two points are closer than
+/// `radius`, while leaving no gaps where another point would fit (Bridson's algorithm). The result
+/// only depends on the state of the random number generator.
+pub fn poisson_disk_sample(rng: &mut impl Rng, min: Vec2, max: Vec2, radius: f32) -> Vec<Vec2> {
+    let size = max - min;
+    if radius <= 0.0 || size.x <= 0.0 || size.y <= 0.0 {
+        return vec![];
+    }
+
+    // every cell of the grid holds at most one point
+    let cell_size = radius / 2f32.sqrt();
+    let columns = (size.x / cell_size).ceil() as usize;
+    let rows = (size.y / cell_size).ceil() as usize;
+    let mut grid: Vec<Option<usize>> = vec![None; columns * rows];
+    let cell_of = |point: Vec2| {
+        let column = (((point.x - min.x) / cell_size) as usize).min(columns - 1);
+        let row = (((point.y - min.y) / cell_size) as usize).min(rows - 1);
+        (column, row)
+    };
+
+    let mut points = vec![];
+    let mut active = vec![];
+
+    let first = Vec2::new(rng.gen_range(min.x..max.x), rng.gen_range(min.y..max.y));
+    let (column, row) = cell_of(first);
+    grid[row * columns + column] = Some(0);
+    points.push(first);
+    active.push(0);
+
+    while !active.is_empty() {
+        let active_index = rng.gen_range(0..active.len());
+        let center = points[active[active_index]];
+        let mut found = false;
+
+        for _ in 0..CANDIDATES_PER_POINT {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let distance = rng.gen_range(radius..2.0 * radius);
+            let candidate = center + Vec2::new(angle.cos(), angle.sin()) * distance;
+
+            if candidate.x < min.x
+                || candidate.y < min.y
+                || candidate.x >= max.x
+                || candidate.y >= max.y
+            {
+                continue;
+            }
+
+            let (column, row) = cell_of(candidate);
+            let too_close = (row.saturating_sub(2)..(row + 3).min(rows)).any(|neighbour_row| {
+                (column.saturating_sub(2)..(column + 3).min(columns)).any(|neighbour_column| {
+                    grid[neighbour_row * columns + neighbour_column]
+                        .map_or(false, |index| points[index].distance(candidate) < radius)
+                })
+            });
+            if too_close {
+                continue;
+            }
+
+            grid[row * columns + column] = Some(points.len());
+            active.push(points.len());
+            points.push(candidate);
+            found = true;
+            break;
+        }
+
+        if !found {
+            active.swap_remove(active_index);
+        }
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Vec2;
+    use quickcheck_macros::quickcheck;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::generation::poisson_disk::poisson_disk_sample;
+
+    fn sample(seed: u64) -> Vec<Vec2> {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        poisson_disk_sample(
+            &mut rng,
+            Vec2::new(-200.0, -100.0),
+            Vec2::new(200.0, 300.0),
+            40.0,
+        )
+    }
+
+    #[quickcheck]
+    fn points_keep_their_distance_and_stay_inside(seed: u64) {
+        let points = sample(seed);
+
+        for (index, point) in points.iter().enumerate() {
+            assert!(point.x >= -200.0 && point.x < 200.0, "{:?}", point);
+            assert!(point.y >= -100.0 && point.y < 300.0, "{:?}", point);
+
+            for other in points[index + 1..].iter() {
+                assert!(point.distance(*other) >= 40.0, "{:?} {:?}", point, other);
+            }
+        }
+    }
+
+    #[test]
+    fn area_is_filled_without_large_gaps() {
+        let points = sample(7);
+
+        // the sampling only stops once no further point fits, which leaves roughly 60 to 80 points
+        // in this area, while the densest packing has about 115
+        assert!(points.len() > 50, "only {} points", points.len());
+    }
+
+    #[test]
+    fn same_seed_gives_same_points() {
+        assert_eq!(sample(42), sample(42));
+        assert_ne!(sample(42), sample(43));
+    }
+}
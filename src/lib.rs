@@ -0,0 +1,15 @@
+pub mod component;
+pub mod data;
+pub mod event;
+pub mod generation;
+pub mod net;
+pub mod plugin;
+pub mod resource;
+pub mod system;
+#[cfg(any(test, feature = "test_support"))]
+pub mod test_support;
+pub mod types;
+
+pub use crate::plugin::ManfredGamePlugin;
+
+pub type Velocity = crate::component::velocity::Velocity<10>;
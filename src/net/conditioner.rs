@@ -0,0 +1,207 @@
+use std::net::SocketAddr;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Simulated bad network conditions for testing on one machine.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkConditions {
+    /// seconds every packet is delayed
+    pub latency: f64,
+    /// up to this many seconds are randomly added to the latency, which reorders packets
+    pub jitter: f64,
+    /// the share of packets that is dropped, from 0 to 1
+    pub loss: f32,
+}
+
+impl LinkConditions {
+    /// Reads `--latency <ms>`, `--jitter <ms>` and `--loss <percent>` from the command line
+    /// arguments. Without any of them the link is left alone.
+    pub fn from_args(args: &[String]) -> Result<Option<LinkConditions>, String> {
+        let value = |name: &str| -> Result<Option<f64>, String> {
+            match args.iter().position(|arg| arg == name) {
+                None => Ok(None),
+                Some(index) => args
+                    .get(index + 1)
+                    .and_then(|value| value.parse::<f64>().ok())
+                    .map(Some)
+                    .ok_or_else(|| format!("{} needs a number", name)),
+            }
+        };
+
+        let (latency, jitter, loss) = (value("--latency")?, value("--jitter")?, value("--loss")?);
+        if latency.is_none() && jitter.is_none() && loss.is_none() {
+            return Ok(None);
+        }
+
+        Ok(Some(LinkConditions {
+            latency: latency.unwrap_or(0.0) / 1000.0,
+            jitter: jitter.unwrap_or(0.0) / 1000.0,
+            loss: (loss.unwrap_or(0.0) / 100.0) as f32,
+        }))
+    }
+}
+
+/// Delays and drops outgoing packets according to the link conditions. The same seed gives the
+/// same delays and losses.
+#[derive(Debug)]
+pub struct LinkConditioner {
+    conditions: LinkConditions,
+    rng: ChaCha8Rng,
+    queue: Vec<DelayedPacket>,
+}
+
+#[derive(Debug)]
+struct DelayedPacket {
+    deliver_at: f64,
+    target: SocketAddr,
+    bytes: Vec<u8>,
+}
+
+impl LinkConditioner {
+    pub fn new(conditions: LinkConditions, seed: u64) -> LinkConditioner {
+        LinkConditioner {
+            conditions,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            queue: vec![],
+        }
+    }
+
+    /// Queues the packet sent at `now` seconds, or drops it.
+    pub fn push(&mut self, now: f64, target: SocketAddr, bytes: Vec<u8>) {
+        if self.rng.gen::<f32>() < self.conditions.loss {
+            return;
+        }
+
+        let jitter = self.rng.gen::<f64>() * self.conditions.jitter;
+        self.queue.push(DelayedPacket {
+            deliver_at: now + self.conditions.latency + jitter,
+            target,
+            bytes,
+        });
+    }
+
+    /// Removes the packets that are due at `now` seconds, in the order they are delivered.
+    pub fn pop_due(&mut self, now: f64) -> Vec<(SocketAddr, Vec<u8>)> {
+        let (mut due, waiting): (Vec<_>, Vec<_>) = self
+            .queue
+            .drain(..)
+            .partition(|packet| packet.deliver_at <= now);
+        self.queue = waiting;
+
+        due.sort_by(|a, b| a.deliver_at.partial_cmp(&b.deliver_at).unwrap());
+        due.into_iter()
+            .map(|packet| (packet.target, packet.bytes))
+            .collect()
+    }
+
+    pub fn waiting(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use crate::net::conditioner::{LinkConditioner, LinkConditions};
+
+    fn target() -> SocketAddr {
+        "127.0.0.1:7777".parse().unwrap()
+    }
+
+    #[test]
+    fn conditions_are_read_from_the_arguments() {
+        let args: Vec<String> = ["game", "--latency", "120", "--loss", "5"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+
+        assert_eq!(
+            Ok(Some(LinkConditions {
+                latency: 0.12,
+                jitter: 0.0,
+                loss: 0.05,
+            })),
+            LinkConditions::from_args(&args)
+        );
+        assert_eq!(Ok(None), LinkConditions::from_args(&args[..1]));
+        assert!(LinkConditions::from_args(&args[..2]).is_err());
+    }
+
+    #[test]
+    fn packets_arrive_after_the_latency() {
+        let mut under_test = LinkConditioner::new(
+            LinkConditions {
+                latency: 0.1,
+                jitter: 0.0,
+                loss: 0.0,
+            },
+            1,
+        );
+
+        under_test.push(1.0, target(), vec![1]);
+        under_test.push(1.05, target(), vec![2]);
+
+        assert!(under_test.pop_due(1.09).is_empty());
+        assert_eq!(vec![(target(), vec![1])], under_test.pop_due(1.1));
+        assert_eq!(vec![(target(), vec![2])], under_test.pop_due(2.0));
+        assert_eq!(0, under_test.waiting());
+    }
+
+    #[test]
+    fn share_of_lost_packets_matches_the_loss() {
+        let mut under_test = LinkConditioner::new(
+            LinkConditions {
+                latency: 0.0,
+                jitter: 0.0,
+                loss: 0.25,
+            },
+            42,
+        );
+
+        for _ in 0..1000 {
+            under_test.push(0.0, target(), vec![]);
+        }
+
+        let arrived = under_test.pop_due(0.0).len();
+        assert!((700..800).contains(&arrived), "{} arrived", arrived);
+    }
+
+    #[test]
+    fn jitter_stays_within_its_bounds() {
+        let mut under_test = LinkConditioner::new(
+            LinkConditions {
+                latency: 0.05,
+                jitter: 0.02,
+                loss: 0.0,
+            },
+            7,
+        );
+
+        for i in 0..100 {
+            under_test.push(0.0, target(), vec![i]);
+        }
+
+        assert!(under_test.pop_due(0.0499).is_empty());
+        assert_eq!(100, under_test.pop_due(0.07).len());
+    }
+
+    #[test]
+    fn same_seed_loses_the_same_packets() {
+        let conditions = LinkConditions {
+            latency: 0.0,
+            jitter: 0.0,
+            loss: 0.5,
+        };
+        let arrived = |seed| {
+            let mut conditioner = LinkConditioner::new(conditions.clone(), seed);
+            for i in 0..50 {
+                conditioner.push(0.0, target(), vec![i]);
+            }
+            conditioner.pop_due(0.0)
+        };
+
+        assert_eq!(arrived(3), arrived(3));
+    }
+}
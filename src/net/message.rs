@@ -0,0 +1,112 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::component::elevation::Elevation;
+use crate::component::facing::Facing;
+use crate::component::Position;
+use crate::net::simulation::{MovementRules, PlayerInput};
+use crate::types::Direction;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ClientMessage {
+    /// Sent until the server answers with a welcome.
+    Connect,
+    /// The newest inputs by sequence number. Inputs are sent more than once, so a lost packet
+    /// rarely loses an input.
+    Input {
+        inputs: Vec<(u32, PlayerInput)>,
+    },
+    Disconnect,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ServerMessage {
+    Welcome {
+        id: u32,
+        rules: MovementRules,
+    },
+    /// The state of all players after the server applied the receiver's inputs up to the
+    /// acknowledged sequence number.
+    State {
+        acknowledged: u32,
+        players: Vec<PlayerState>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerState {
+    pub id: u32,
+    pub position: Position,
+    pub x_steps: i32,
+    pub y_steps: i32,
+    /// whether the speed follows the deceleration curve
+    pub slowing_down: bool,
+    pub view_direction: Direction,
+    /// what turning depends on besides the steps
+    pub facing: Facing,
+    pub elevation: Elevation,
+}
+
+pub fn encode<T: Serialize>(message: &T) -> Result<Vec<u8>, String> {
+    ron::to_string(message)
+        .map(String::into_bytes)
+        .map_err(|error| format!("cannot encode message: {}", error))
+}
+
+pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, String> {
+    let text =
+        std::str::from_utf8(bytes).map_err(|error| format!("cannot decode message: {}", error))?;
+
+    ron::from_str(text).map_err(|error| format!("cannot decode message: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::elevation::Elevation;
+    use crate::component::facing::Facing;
+    use crate::component::Position;
+    use crate::net::message::{decode, encode, ClientMessage, PlayerState, ServerMessage};
+    use crate::net::simulation::PlayerInput;
+    use crate::types::Direction;
+
+    #[test]
+    fn messages_survive_encoding() {
+        let input = ClientMessage::Input {
+            inputs: vec![(
+                7,
+                PlayerInput {
+                    left: true,
+                    jump: true,
+                    ..Default::default()
+                },
+            )],
+        };
+        let state = ServerMessage::State {
+            acknowledged: 7,
+            players: vec![PlayerState {
+                id: 1,
+                position: Position::new(-3, 4),
+                x_steps: -2,
+                y_steps: 0,
+                slowing_down: true,
+                view_direction: Direction::Left,
+                facing: Facing::default(),
+                elevation: Elevation::default(),
+            }],
+        };
+
+        assert_eq!(
+            input,
+            decode::<ClientMessage>(&encode(&input).unwrap()).unwrap()
+        );
+        assert_eq!(
+            state,
+            decode::<ServerMessage>(&encode(&state).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn garbage_is_rejected() {
+        assert!(decode::<ClientMessage>(b"\xff\x00garbage").is_err());
+    }
+}
@@ -0,0 +1,10 @@
+//! Online play over UDP. The server runs the movement of every player authoritatively. Clients
+//! send their input every frame, predict their own movement with the same code the server runs and
+//! reconcile it whenever the server's state arrives. Only movement is synchronised so far.
+
+pub mod conditioner;
+pub mod message;
+pub mod prediction;
+pub mod server;
+pub mod simulation;
+pub mod socket;
@@ -0,0 +1,135 @@
+use std::collections::VecDeque;
+
+use crate::net::simulation::{MovementRules, PlayerInput, SimulatedPlayer};
+
+/// Inputs older than this are forgotten, e.g. while the server does not answer.
+pub const MAX_PENDING: usize = 600;
+
+/// Remembers the inputs the server has not acknowledged yet, so they can be replayed on top of the
+/// server's state.
+#[derive(Debug, Default)]
+pub struct Prediction {
+    last_sequence: u32,
+    pending: VecDeque<(u32, PlayerInput)>,
+}
+
+impl Prediction {
+    /// Remembers the input of this frame and returns its sequence number.
+    pub fn record(&mut self, input: PlayerInput) -> u32 {
+        self.last_sequence += 1;
+        self.pending.push_back((self.last_sequence, input));
+        if self.pending.len() > MAX_PENDING {
+            self.pending.pop_front();
+        }
+        self.last_sequence
+    }
+
+    /// The newest unacknowledged inputs, oldest first.
+    pub fn newest(&self, count: usize) -> Vec<(u32, PlayerInput)> {
+        let skipped = self.pending.len().saturating_sub(count);
+        self.pending.iter().skip(skipped).copied().collect()
+    }
+
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Forgets the inputs the server has applied and replays the others on top of the server's
+    /// state, which gives where the player is now.
+    pub fn reconcile(
+        &mut self,
+        acknowledged: u32,
+        server: SimulatedPlayer,
+        rules: &MovementRules,
+    ) -> SimulatedPlayer {
+        while let Some((sequence, _)) = self.pending.front() {
+            if *sequence > acknowledged {
+                break;
+            }
+            self.pending.pop_front();
+        }
+
+        let mut player = server;
+        for (_, input) in self.pending.iter() {
+            player.step(*input, rules);
+        }
+        player
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::Position;
+    use crate::net::prediction::Prediction;
+    use crate::net::simulation::{MovementRules, PlayerInput, SimulatedPlayer};
+
+    fn rules() -> MovementRules {
+        MovementRules {
+            max_speed: 5,
+            ..Default::default()
+        }
+    }
+
+    fn right() -> PlayerInput {
+        PlayerInput {
+            right: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reconciling_with_an_agreeing_server_changes_nothing() {
+        let mut under_test = Prediction::default();
+        let mut local = SimulatedPlayer::new(Position::default(), &rules());
+        let mut server = SimulatedPlayer::new(Position::default(), &rules());
+
+        for frame in 1..=10 {
+            let sequence = under_test.record(right());
+            local.step(right(), &rules());
+            // the server is three frames behind
+            if frame > 3 {
+                server.step(right(), &rules());
+            }
+            assert_eq!(frame, sequence);
+        }
+
+        let reconciled = under_test.reconcile(7, server, &rules());
+
+        assert_eq!(local.position, reconciled.position);
+        assert_eq!(local.velocity.x(), reconciled.velocity.x());
+        assert_eq!(3, under_test.pending());
+    }
+
+    #[test]
+    fn disagreeing_server_corrects_the_prediction() {
+        let mut under_test = Prediction::default();
+        for _ in 0..4 {
+            under_test.record(right());
+        }
+        // the server put the player somewhere else, e.g. after a collision
+        let server = SimulatedPlayer::new(Position::new(100, 0), &rules());
+
+        let reconciled = under_test.reconcile(2, server.clone(), &rules());
+
+        let mut expected = server;
+        expected.step(right(), &rules());
+        expected.step(right(), &rules());
+        assert_eq!(expected.position, reconciled.position);
+    }
+
+    #[test]
+    fn newest_inputs_are_sent_oldest_first() {
+        let mut under_test = Prediction::default();
+        for _ in 0..5 {
+            under_test.record(right());
+        }
+
+        let sequences: Vec<u32> = under_test
+            .newest(3)
+            .into_iter()
+            .map(|(sequence, _)| sequence)
+            .collect();
+
+        assert_eq!(vec![3, 4, 5], sequences);
+    }
+}
@@ -0,0 +1,365 @@
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+
+use crate::component::Position;
+use crate::net::message::{ClientMessage, ServerMessage};
+use crate::net::prediction::MAX_PENDING;
+use crate::net::simulation::{MovementRules, PlayerInput, SimulatedPlayer};
+use crate::net::socket::NetSocket;
+
+/// Clients that did not send anything for this many seconds are dropped.
+const TIMEOUT_SECONDS: f64 = 5.0;
+/// How many ticks a client can fall behind, e.g. when its inputs arrive late, and still catch up
+/// in a single tick.
+const MAX_CATCH_UP: usize = 8;
+
+#[derive(Debug)]
+struct Client {
+    id: u32,
+    player: SimulatedPlayer,
+    /// the sequence number of the last applied input
+    acknowledged: u32,
+    /// never more than `MAX_PENDING`, as only the sequence numbers right after the acknowledged
+    /// one are kept
+    inputs: BTreeMap<u32, PlayerInput>,
+    /// how many inputs may still be applied, one more for every tick up to `MAX_CATCH_UP`, so
+    /// nobody moves faster than the server ticks by sending more inputs
+    budget: usize,
+    last_heard: f64,
+}
+
+/// Runs the movement of all connected players. Every input is applied once, in order, so the
+/// clients can predict exactly what the server does.
+#[derive(Debug)]
+pub struct Server {
+    rules: MovementRules,
+    spawn: Position,
+    clients: HashMap<SocketAddr, Client>,
+    next_id: u32,
+}
+
+impl Server {
+    pub fn new(rules: MovementRules, spawn: Position) -> Server {
+        Server {
+            rules,
+            spawn,
+            clients: HashMap::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Handles a message received at `now` seconds. Returns the answer, if there is one.
+    pub fn handle(
+        &mut self,
+        from: SocketAddr,
+        message: ClientMessage,
+        now: f64,
+    ) -> Option<ServerMessage> {
+        if let Some(client) = self.clients.get_mut(&from) {
+            client.last_heard = now;
+        }
+
+        match message {
+            ClientMessage::Connect => {
+                let (rules, spawn) = (&self.rules, self.spawn);
+                let next_id = &mut self.next_id;
+                // a repeated connect gets the same id, the welcome may have been lost
+                let client = self.clients.entry(from).or_insert_with(|| {
+                    *next_id += 1;
+                    Client {
+                        id: *next_id - 1,
+                        player: SimulatedPlayer::new(spawn, rules),
+                        acknowledged: 0,
+                        inputs: BTreeMap::new(),
+                        budget: 0,
+                        last_heard: now,
+                    }
+                });
+                Some(ServerMessage::Welcome {
+                    id: client.id,
+                    rules: rules.clone(),
+                })
+            }
+            ClientMessage::Input { inputs } => {
+                if let Some(client) = self.clients.get_mut(&from) {
+                    // clients never get further ahead, sequences beyond would hold back all
+                    // inputs before them
+                    let newest = client.acknowledged.saturating_add(MAX_PENDING as u32);
+                    for (sequence, input) in inputs {
+                        if sequence > client.acknowledged && sequence <= newest {
+                            client.inputs.insert(sequence, input);
+                        }
+                    }
+                }
+                None
+            }
+            ClientMessage::Disconnect => {
+                self.clients.remove(&from);
+                None
+            }
+        }
+    }
+
+    /// Runs one tick: applies the received inputs of every client as far as their budget allows
+    /// and drops clients that timed out.
+    pub fn simulate(&mut self, now: f64) {
+        self.clients
+            .retain(|_, client| now - client.last_heard < TIMEOUT_SECONDS);
+
+        for client in self.clients.values_mut() {
+            client.budget = (client.budget + 1).min(MAX_CATCH_UP);
+
+            while client.budget > 0 {
+                let (sequence, input) = match client.inputs.iter().next() {
+                    Some((sequence, input)) => (*sequence, *input),
+                    None => break,
+                };
+                client.inputs.remove(&sequence);
+                client.player.step(input, &self.rules);
+                client.acknowledged = sequence;
+                client.budget -= 1;
+            }
+        }
+    }
+
+    /// The state of all players as seen by the client at the address.
+    pub fn state_for(&self, address: SocketAddr) -> Option<ServerMessage> {
+        let client = self.clients.get(&address)?;
+
+        let mut players: Vec<_> = self
+            .clients
+            .values()
+            .map(|client| client.player.state(client.id))
+            .collect();
+        players.sort_by_key(|player| player.id);
+
+        Some(ServerMessage::State {
+            acknowledged: client.acknowledged,
+            players,
+        })
+    }
+
+    pub fn client_count(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Receives everything that arrived, runs the simulation and sends every client the new
+    /// state.
+    pub fn update(&mut self, socket: &mut NetSocket) {
+        let now = socket.now();
+
+        for (from, message) in socket.receive::<ClientMessage>() {
+            if let Some(answer) = self.handle(from, message, now) {
+                socket.send(from, &answer);
+            }
+        }
+
+        self.simulate(now);
+
+        let addresses: Vec<SocketAddr> = self.clients.keys().copied().collect();
+        for address in addresses {
+            if let Some(state) = self.state_for(address) {
+                socket.send(address, &state);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use crate::component::Position;
+    use crate::net::message::{ClientMessage, ServerMessage};
+    use crate::net::prediction::MAX_PENDING;
+    use crate::net::server::Server;
+    use crate::net::simulation::{MovementRules, PlayerInput};
+
+    fn rules() -> MovementRules {
+        MovementRules {
+            max_speed: 5,
+            ..Default::default()
+        }
+    }
+
+    fn address(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    fn right() -> PlayerInput {
+        PlayerInput {
+            right: true,
+            ..Default::default()
+        }
+    }
+
+    fn acknowledged_and_x(server: &Server, client: SocketAddr, id: u32) -> (u32, i32) {
+        match server.state_for(client).unwrap() {
+            ServerMessage::State {
+                acknowledged,
+                players,
+            } => {
+                let player = players.iter().find(|player| player.id == id).unwrap();
+                (acknowledged, player.position.x)
+            }
+            message => panic!("unexpected {:?}", message),
+        }
+    }
+
+    #[test]
+    fn connecting_twice_keeps_the_id() {
+        let mut under_test = Server::new(rules(), Position::default());
+
+        let first = under_test.handle(address(1), ClientMessage::Connect, 0.0);
+        let second = under_test.handle(address(1), ClientMessage::Connect, 0.1);
+        let other = under_test.handle(address(2), ClientMessage::Connect, 0.1);
+
+        assert_eq!(
+            Some(ServerMessage::Welcome {
+                id: 1,
+                rules: rules()
+            }),
+            first
+        );
+        assert_eq!(first, second);
+        assert_eq!(
+            Some(ServerMessage::Welcome {
+                id: 2,
+                rules: rules()
+            }),
+            other
+        );
+    }
+
+    #[test]
+    fn inputs_are_applied_once_and_in_order() {
+        let mut under_test = Server::new(rules(), Position::default());
+        under_test.handle(address(1), ClientMessage::Connect, 0.0);
+
+        under_test.handle(
+            address(1),
+            ClientMessage::Input {
+                inputs: vec![(2, right()), (1, right())],
+            },
+            0.0,
+        );
+        under_test.simulate(0.0);
+        under_test.simulate(0.0);
+        let (acknowledged, x) = acknowledged_and_x(&under_test, address(1), 1);
+        assert_eq!(2, acknowledged);
+        assert!(x > 0);
+
+        // resent inputs are ignored
+        under_test.handle(
+            address(1),
+            ClientMessage::Input {
+                inputs: vec![(1, right()), (2, right())],
+            },
+            0.0,
+        );
+        under_test.simulate(0.0);
+        assert_eq!((2, x), acknowledged_and_x(&under_test, address(1), 1));
+    }
+
+    #[test]
+    fn sequences_far_ahead_are_ignored() {
+        let mut under_test = Server::new(rules(), Position::default());
+        under_test.handle(address(1), ClientMessage::Connect, 0.0);
+
+        under_test.handle(
+            address(1),
+            ClientMessage::Input {
+                inputs: vec![(u32::MAX, right()), (1, right())],
+            },
+            0.0,
+        );
+        under_test.simulate(0.0);
+
+        assert_eq!(1, acknowledged_and_x(&under_test, address(1), 1).0);
+    }
+
+    #[test]
+    fn no_more_inputs_than_a_client_remembers_are_kept() {
+        let mut under_test = Server::new(rules(), Position::default());
+        under_test.handle(address(1), ClientMessage::Connect, 0.0);
+
+        let flood = (1..=2 * MAX_PENDING as u32).map(|sequence| (sequence, right()));
+        under_test.handle(
+            address(1),
+            ClientMessage::Input {
+                inputs: flood.collect(),
+            },
+            0.0,
+        );
+        for _ in 0..MAX_PENDING {
+            under_test.simulate(0.0);
+        }
+
+        assert_eq!(
+            MAX_PENDING as u32,
+            acknowledged_and_x(&under_test, address(1), 1).0
+        );
+    }
+
+    #[test]
+    fn flooding_inputs_moves_no_faster_than_the_server_ticks() {
+        let mut under_test = Server::new(rules(), Position::default());
+        under_test.handle(address(1), ClientMessage::Connect, 0.0);
+        under_test.handle(address(2), ClientMessage::Connect, 0.0);
+
+        let flood = (1..=100).map(|sequence| (sequence, right()));
+        under_test.handle(
+            address(1),
+            ClientMessage::Input {
+                inputs: flood.collect(),
+            },
+            0.0,
+        );
+        under_test.handle(
+            address(2),
+            ClientMessage::Input {
+                inputs: vec![(1, right())],
+            },
+            0.0,
+        );
+        under_test.simulate(0.0);
+
+        let (acknowledged, x) = acknowledged_and_x(&under_test, address(1), 1);
+        assert_eq!(1, acknowledged);
+        assert_eq!(acknowledged_and_x(&under_test, address(2), 2).1, x);
+    }
+
+    #[test]
+    fn late_inputs_catch_up_the_ticks_they_missed() {
+        let mut under_test = Server::new(rules(), Position::default());
+        under_test.handle(address(1), ClientMessage::Connect, 0.0);
+        for _ in 0..3 {
+            under_test.simulate(0.0);
+        }
+
+        let late = (1..=100).map(|sequence| (sequence, right()));
+        under_test.handle(
+            address(1),
+            ClientMessage::Input {
+                inputs: late.collect(),
+            },
+            0.0,
+        );
+        under_test.simulate(0.0);
+
+        assert_eq!(4, acknowledged_and_x(&under_test, address(1), 1).0);
+    }
+
+    #[test]
+    fn silent_clients_are_dropped() {
+        let mut under_test = Server::new(rules(), Position::default());
+        under_test.handle(address(1), ClientMessage::Connect, 0.0);
+        under_test.handle(address(2), ClientMessage::Connect, 0.0);
+
+        under_test.handle(address(2), ClientMessage::Input { inputs: vec![] }, 4.0);
+        under_test.simulate(6.0);
+
+        assert_eq!(1, under_test.client_count());
+        assert!(under_test.state_for(address(1)).is_none());
+    }
+}
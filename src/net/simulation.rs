@@ -0,0 +1,402 @@
+use serde::{Deserialize, Serialize};
+
+use bevy::prelude::Vec2;
+
+use crate::component::collider::{manfred_collider, Collider};
+use crate::component::elevation::{Elevation, JumpConfig};
+use crate::component::facing::{Facing, FacingConfig};
+use crate::component::obstacle::Obstacle;
+use crate::component::Position;
+use crate::data::level::ObstacleDefinition;
+use crate::net::message::PlayerState;
+use crate::plugin::movement::MovementConfig;
+use crate::system::position::moved_position;
+use crate::system::velocity::handle_acceleration;
+use crate::types::easing::MovementProfile;
+use crate::types::Direction;
+use crate::Velocity;
+
+/// How players move, which the server sends every client so that their predictions agree with it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MovementRules {
+    pub max_speed: u16,
+    pub profile: MovementProfile,
+    pub facing: FacingConfig,
+    pub jump: JumpConfig,
+    /// the obstacles of the level everyone plays in, ready for `moved_position`
+    pub obstacles: Vec<(Vec2, Collider, Obstacle)>,
+}
+
+impl MovementRules {
+    /// The rules of the config in a level without obstacles.
+    pub fn from_config(config: &MovementConfig) -> MovementRules {
+        MovementRules {
+            max_speed: config.max_speed,
+            profile: config.profile.clone(),
+            facing: config.facing.clone(),
+            jump: config.jump.clone(),
+            obstacles: vec![],
+        }
+    }
+
+    /// The rules with the obstacles of a level. They are built once here, as predictions replay
+    /// many steps every frame.
+    pub fn with_obstacles(mut self, obstacles: &[ObstacleDefinition]) -> MovementRules {
+        self.obstacles = obstacles
+            .iter()
+            .map(|obstacle| {
+                (
+                    obstacle.position,
+                    Collider::new(obstacle.size.x, obstacle.size.y),
+                    Obstacle {
+                        height: obstacle.height,
+                    },
+                )
+            })
+            .collect();
+        self
+    }
+
+    /// A standing velocity that moves by these rules.
+    pub fn velocity(&self) -> Velocity {
+        Velocity::new(self.max_speed).with_profile(self.profile.clone())
+    }
+}
+
+/// The directions a player pressed in one frame, and whether the jump started in it.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PlayerInput {
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool,
+    pub jump: bool,
+}
+
+impl PlayerInput {
+    pub fn from_pressed(is_pressed: impl Fn(Direction) -> bool, jump: bool) -> PlayerInput {
+        PlayerInput {
+            up: is_pressed(Direction::Up),
+            down: is_pressed(Direction::Down),
+            left: is_pressed(Direction::Left),
+            right: is_pressed(Direction::Right),
+            jump,
+        }
+    }
+
+    pub fn is_pressed(&self, direction: Direction) -> bool {
+        match direction {
+            Direction::Up => self.up,
+            Direction::Down => self.down,
+            Direction::Left => self.left,
+            Direction::Right => self.right,
+        }
+    }
+}
+
+/// The movement of one player outside of the ECS, as run by the server and replayed by the client.
+#[derive(Debug, Clone)]
+pub struct SimulatedPlayer {
+    pub position: Position,
+    pub velocity: Velocity,
+    pub view_direction: Direction,
+    pub facing: Facing,
+    pub elevation: Elevation,
+}
+
+impl SimulatedPlayer {
+    pub fn new(position: Position, rules: &MovementRules) -> SimulatedPlayer {
+        SimulatedPlayer {
+            position,
+            velocity: rules.velocity(),
+            view_direction: Direction::Down,
+            facing: Facing::default(),
+            elevation: Elevation::default(),
+        }
+    }
+
+    pub fn from_state(state: &PlayerState, rules: &MovementRules) -> SimulatedPlayer {
+        let mut velocity = rules.velocity();
+        velocity.set_steps(state.x_steps, state.y_steps);
+        velocity.set_slowing_down(state.slowing_down);
+
+        SimulatedPlayer {
+            position: state.position,
+            velocity,
+            view_direction: state.view_direction,
+            facing: state.facing.clone(),
+            elevation: state.elevation,
+        }
+    }
+
+    /// One frame of movement, the same as `velocity_control_system`, `elevation_system` and
+    /// `move_positions_system` with Manfred's collider.
+    pub fn step(&mut self, input: PlayerInput, rules: &MovementRules) {
+        if input.jump {
+            self.elevation.jump(rules.jump.speed);
+        }
+        handle_acceleration(
+            |direction| input.is_pressed(direction),
+            &mut self.velocity,
+            &mut self.view_direction,
+            &mut self.facing,
+            &rules.facing,
+        );
+        self.elevation.update(rules.jump.gravity);
+
+        self.position = moved_position(
+            self.position,
+            &self.velocity,
+            Some(&manfred_collider()),
+            self.elevation.height(),
+            &rules.obstacles,
+        );
+    }
+
+    pub fn state(&self, id: u32) -> PlayerState {
+        PlayerState {
+            id,
+            position: self.position,
+            x_steps: self.velocity.x_steps(),
+            y_steps: self.velocity.y_steps(),
+            slowing_down: self.velocity.is_slowing_down(),
+            view_direction: self.view_direction,
+            facing: self.facing.clone(),
+            elevation: self.elevation,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{
+        IntoSystem, KeyCode, ParallelSystemDescriptorCoercion, Transform, Vec2, Vec3,
+    };
+
+    use crate::component::collider::{manfred_collider, Collider};
+    use crate::component::elevation::Elevation;
+    use crate::component::facing::{Facing, FacingConfig};
+    use crate::component::manfred::Manfred;
+    use crate::component::obstacle::Obstacle;
+    use crate::component::Position;
+    use crate::data::level::ObstacleDefinition;
+    use crate::net::simulation::{MovementRules, PlayerInput, SimulatedPlayer};
+    use crate::plugin::movement::MovementConfig;
+    use crate::system::elevation::elevation_system;
+    use crate::system::position::{move_positions_system, FromXAndY};
+    use crate::system::velocity::velocity_control_system;
+    use crate::test_support::test_world::TestWorldBuilder;
+    use crate::types::easing::MovementProfile;
+    use crate::types::Direction;
+    use crate::Velocity;
+
+    fn rules() -> MovementRules {
+        MovementRules {
+            max_speed: 5,
+            profile: MovementProfile::smooth(),
+            facing: FacingConfig {
+                hysteresis: 3,
+                min_hold_frames: 2,
+                prefer_last_pressed: true,
+            },
+            ..Default::default()
+        }
+    }
+
+    fn fence() -> ObstacleDefinition {
+        ObstacleDefinition {
+            name: "fence".to_string(),
+            position: Vec2::new(60.0, -30.0),
+            size: Vec2::new(10.0, 40.0),
+            height: 10,
+        }
+    }
+
+    #[test]
+    fn simulation_moves_like_the_systems() {
+        let mut world = TestWorldBuilder::new()
+            .with_resource(MovementConfig {
+                profile: MovementProfile::smooth(),
+                facing: rules().facing,
+                ..Default::default()
+            })
+            .with_system(velocity_control_system.system().label("velocity"))
+            .with_system(move_positions_system.system().after("velocity"))
+            .with_entity(
+                "manfred",
+                (
+                    Manfred::default(),
+                    Facing::default(),
+                    Velocity::new(5).with_profile(MovementProfile::smooth()),
+                    Position::default(),
+                    Transform::default(),
+                ),
+            )
+            .build();
+        let mut under_test = SimulatedPlayer::new(Position::default(), &rules());
+
+        world.press(KeyCode::D);
+        world.press(KeyCode::W);
+        for _ in 0..12 {
+            world.run_step();
+            under_test.step(
+                PlayerInput {
+                    up: true,
+                    right: true,
+                    ..Default::default()
+                },
+                &rules(),
+            );
+        }
+        world.release(KeyCode::W);
+        for _ in 0..5 {
+            world.run_step();
+            under_test.step(
+                PlayerInput {
+                    right: true,
+                    ..Default::default()
+                },
+                &rules(),
+            );
+        }
+
+        assert_eq!(&under_test.position, world.get::<Position>("manfred"));
+        assert_eq!(
+            world.get::<Manfred>("manfred").view_direction,
+            under_test.view_direction
+        );
+        assert_eq!(Direction::Right, under_test.view_direction);
+    }
+
+    #[test]
+    fn simulation_jumps_over_obstacles_like_the_systems() {
+        let fence = fence();
+        let mut world = TestWorldBuilder::new()
+            .with_resource(MovementConfig {
+                profile: MovementProfile::smooth(),
+                facing: rules().facing,
+                ..Default::default()
+            })
+            .with_system(velocity_control_system.system().label("velocity"))
+            .with_system(
+                elevation_system
+                    .system()
+                    .label("elevation")
+                    .after("velocity"),
+            )
+            .with_system(move_positions_system.system().after("elevation"))
+            .with_entity(
+                "manfred",
+                (
+                    Manfred::default(),
+                    Facing::default(),
+                    Velocity::new(5).with_profile(MovementProfile::smooth()),
+                    Position::default(),
+                    Transform::default(),
+                    manfred_collider(),
+                    Elevation::default(),
+                ),
+            )
+            .with_entity(
+                "fence",
+                (
+                    Transform::from_translation(Vec3::compute_from_x_y(
+                        fence.position.x,
+                        fence.position.y,
+                    )),
+                    Collider::new(fence.size.x, fence.size.y),
+                    Obstacle {
+                        height: fence.height,
+                    },
+                ),
+            )
+            .build();
+        let rules = rules().with_obstacles(&[fence]);
+        let mut under_test = SimulatedPlayer::new(Position::default(), &rules);
+
+        world.press(KeyCode::D);
+        for frame in 0..40 {
+            let jump = frame == 12;
+            if jump {
+                world.press(KeyCode::Space);
+            }
+            world.run_step();
+            world.release(KeyCode::Space);
+            under_test.step(
+                PlayerInput {
+                    right: true,
+                    jump,
+                    ..Default::default()
+                },
+                &rules,
+            );
+
+            assert_eq!(&under_test.position, world.get::<Position>("manfred"));
+            assert_eq!(&under_test.elevation, world.get::<Elevation>("manfred"));
+        }
+        assert!(under_test.position.x > 80);
+    }
+
+    #[test]
+    fn obstacles_stop_simulated_players_on_the_ground() {
+        let rules = rules().with_obstacles(&[fence()]);
+        let mut under_test = SimulatedPlayer::new(Position::default(), &rules);
+
+        for _ in 0..40 {
+            under_test.step(
+                PlayerInput {
+                    right: true,
+                    ..Default::default()
+                },
+                &rules,
+            );
+        }
+
+        assert!(under_test.position.x <= 40);
+    }
+
+    #[test]
+    fn state_round_trip_keeps_the_movement() {
+        let mut player = SimulatedPlayer::new(Position::new(10, 20), &rules());
+        for _ in 0..3 {
+            player.step(
+                PlayerInput {
+                    up: true,
+                    left: true,
+                    ..Default::default()
+                },
+                &rules(),
+            );
+        }
+
+        let restored = SimulatedPlayer::from_state(&player.state(1), &rules());
+
+        assert_eq!(player.position, restored.position);
+        assert_eq!(player.velocity.x(), restored.velocity.x());
+        assert_eq!(player.view_direction, restored.view_direction);
+        assert_eq!(player.facing, restored.facing);
+    }
+
+    #[test]
+    fn restored_players_keep_slowing_down_along_their_curve() {
+        let rules = MovementRules {
+            max_speed: 100,
+            ..rules()
+        };
+        let mut player = SimulatedPlayer::new(Position::default(), &rules);
+        let right = PlayerInput {
+            right: true,
+            ..Default::default()
+        };
+        for _ in 0..10 {
+            player.step(right, &rules);
+        }
+        player.step(PlayerInput::default(), &rules);
+
+        let restored = SimulatedPlayer::from_state(&player.state(1), &rules);
+
+        assert!(restored.velocity.is_slowing_down());
+        assert_eq!(99, restored.velocity.x());
+        assert_eq!(player.velocity.x(), restored.velocity.x());
+    }
+}
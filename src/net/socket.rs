@@ -0,0 +1,101 @@
+use std::io::ErrorKind;
+use std::net::{SocketAddr, UdpSocket};
+use std::time::Instant;
+
+use bevy::log::warn;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::net::conditioner::{LinkConditioner, LinkConditions};
+use crate::net::message::{decode, encode};
+
+/// Large enough for the state of many players.
+const MAX_PACKET_SIZE: usize = 16 * 1024;
+
+/// A non-blocking UDP socket sending and receiving messages, optionally through a link
+/// conditioner.
+#[derive(Debug)]
+pub struct NetSocket {
+    socket: UdpSocket,
+    conditioner: Option<LinkConditioner>,
+    started: Instant,
+}
+
+impl NetSocket {
+    pub fn bind(address: &str, conditions: Option<LinkConditions>) -> Result<NetSocket, String> {
+        let socket = UdpSocket::bind(address)
+            .map_err(|error| format!("cannot bind to {}: {}", address, error))?;
+        socket
+            .set_nonblocking(true)
+            .map_err(|error| format!("cannot make the socket non-blocking: {}", error))?;
+
+        Ok(NetSocket {
+            socket,
+            conditioner: conditions.map(|conditions| LinkConditioner::new(conditions, 0)),
+            started: Instant::now(),
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, String> {
+        self.socket
+            .local_addr()
+            .map_err(|error| format!("the socket has no address: {}", error))
+    }
+
+    pub fn send<T: Serialize>(&mut self, target: SocketAddr, message: &T) {
+        let bytes = match encode(message) {
+            Ok(bytes) => bytes,
+            Err(message) => {
+                warn!("{}", message);
+                return;
+            }
+        };
+
+        let now = self.now();
+        match self.conditioner.as_mut() {
+            Some(conditioner) => conditioner.push(now, target, bytes),
+            None => Self::send_bytes(&self.socket, target, &bytes),
+        }
+    }
+
+    /// Sends the delayed packets that are due and returns the messages received since the last
+    /// call. Packets that cannot be decoded are skipped.
+    pub fn receive<T: DeserializeOwned>(&mut self) -> Vec<(SocketAddr, T)> {
+        let now = self.now();
+        if let Some(conditioner) = self.conditioner.as_mut() {
+            for (target, bytes) in conditioner.pop_due(now) {
+                Self::send_bytes(&self.socket, target, &bytes);
+            }
+        }
+
+        let mut messages = vec![];
+        let mut buffer = [0; MAX_PACKET_SIZE];
+        loop {
+            match self.socket.recv_from(&mut buffer) {
+                Ok((length, sender)) => match decode(&buffer[..length]) {
+                    Ok(message) => messages.push((sender, message)),
+                    Err(message) => warn!("{} from {}", message, sender),
+                },
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+                // the other side was not listening (yet), which some platforms report
+                Err(error) if error.kind() == ErrorKind::ConnectionReset => continue,
+                Err(error) => {
+                    warn!("cannot receive: {}", error);
+                    break;
+                }
+            }
+        }
+        messages
+    }
+
+    /// Seconds since the socket was bound.
+    pub fn now(&self) -> f64 {
+        self.started.elapsed().as_secs_f64()
+    }
+
+    fn send_bytes(socket: &UdpSocket, target: SocketAddr, bytes: &[u8]) {
+        if let Err(error) = socket.send_to(bytes, target) {
+            warn!("cannot send to {}: {}", target, error);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::core::FixedTimestep;
+use bevy::prelude::{
+    AppBuilder, IntoExclusiveSystem, IntoSystem, ParallelSystemDescriptorCoercion, Plugin,
+    StartupStage, SystemSet, Vec2,
+};
+
+use crate::component::fidget::FidgetConfig;
+use crate::event::animation::AnimationEvent;
+use crate::plugin::console::AddConsoleCommand;
+use crate::resource::console::ConsoleCommand;
+use crate::resource::palette::PaletteAtlases;
+use crate::system::animation::{
+    animate_command, animation_system, animation_trigger_system, fidget_system,
+    load_animation_definitions,
+};
+use crate::system::palette::{load_palette_definitions, palette_command};
+
+#[derive(Debug, Clone)]
+pub struct AnimationConfig {
+    pub sprite_atlas: String,
+    pub sprite_size: Vec2,
+    pub columns: u32,
+    pub rows: u32,
+    /// seconds between two animation ticks
+    pub frame_duration: f64,
+    /// the RON file the animation graphs are loaded from
+    pub definitions_path: String,
+    /// the RON file the colour palettes for `sprite_atlas` are loaded from
+    pub palettes_path: String,
+    /// when Manfred starts with the idle animations after he was left alone
+    pub fidget: FidgetConfig,
+}
+
+impl Default for AnimationConfig {
+    fn default() -> Self {
+        AnimationConfig {
+            sprite_atlas: "images\\manfred_sprite_atlas.png".to_string(),
+            sprite_size: Vec2::new(40.0, 80.0),
+            columns: 8,
+            rows: 4,
+            frame_duration: 0.1,
+            definitions_path: "assets/animations/animations.ron".to_string(),
+            palettes_path: "assets/palettes/palettes.ron".to_string(),
+            fidget: FidgetConfig::default(),
+        }
+    }
+}
+
+/// Plays the animation graphs of Manfred and everyone else with an `Animator`, following their
+/// velocity and view direction. Picking up items and taking damage trigger the animations of the
+/// same names, so the `InventoryPlugin` and the `HealthPlugin` are needed as well. Manfreds left
+/// alone start to fidget. NPCs can wear Manfred's sprites in other colours, see
+/// `assets/palettes/palettes.ron`.
+#[derive(Debug, Default)]
+pub struct AnimationPlugin {
+    pub config: AnimationConfig,
+}
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(self.config.clone())
+            .add_event::<AnimationEvent>()
+            .init_resource::<PaletteAtlases>()
+            .add_startup_system_to_stage(
+                StartupStage::PreStartup,
+                load_animation_definitions.exclusive_system(),
+            )
+            .add_startup_system_to_stage(
+                StartupStage::PreStartup,
+                load_palette_definitions.exclusive_system(),
+            )
+            .add_system(animation_trigger_system.system().before("animation"))
+            .add_system(fidget_system.system().after("velocity").before("animation"))
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(FixedTimestep::step(self.config.frame_duration))
+                    .with_system(animation_system.system().label("animation")),
+            )
+            .add_console_command(ConsoleCommand::new(
+                "animate",
+                "<event>",
+                "triggers an animation event on Manfred, e.g. attack",
+                animate_command,
+            ))
+            .add_console_command(ConsoleCommand::new(
+                "palette",
+                "<palette>",
+                "shows Manfred in the colours of a palette, e.g. farmer",
+                palette_command,
+            ));
+    }
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::{AppBuilder, IntoSystem, ParallelSystemDescriptorCoercion, Plugin};
+
+use crate::system::camera::{add_camera, camera_follow_system};
+
+#[derive(Debug, Clone)]
+pub struct CameraConfig {
+    /// values greater than 1 zoom out, values smaller than 1 zoom in
+    pub scale: f32,
+    /// how far the camera zooms out at most to keep all players in view
+    pub max_scale: f32,
+    /// the space kept around the players at the border of the screen
+    pub margin: f32,
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        CameraConfig {
+            scale: 1.0,
+            max_scale: 2.0,
+            margin: 150.0,
+        }
+    }
+}
+
+/// Keeps the players in the middle of the screen and zooms out as they walk away from each other.
+#[derive(Debug, Default)]
+pub struct CameraPlugin {
+    pub config: CameraConfig,
+}
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(self.config.clone())
+            .add_startup_system(add_camera.system())
+            .add_system(camera_follow_system.system().after("update_position"));
+    }
+}
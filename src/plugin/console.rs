@@ -0,0 +1,76 @@
+use bevy::input::InputSystem;
+use bevy::prelude::{
+    AppBuilder, CoreStage, ExclusiveSystemDescriptorCoercion, IntoExclusiveSystem, IntoSystem,
+    KeyCode, ParallelSystemDescriptorCoercion, Plugin,
+};
+
+use crate::resource::console::{ConsoleCommand, ConsoleCommands, ConsoleState};
+use crate::system::console::{
+    add_console, console_execution_system, console_input_system, console_text_system, help_command,
+    inspect_command,
+};
+
+/// Lets plugins register their own console commands, independent of whether the console itself
+/// is part of the app.
+pub trait AddConsoleCommand {
+    fn add_console_command(&mut self, command: ConsoleCommand) -> &mut Self;
+}
+
+impl AddConsoleCommand for AppBuilder {
+    fn add_console_command(&mut self, command: ConsoleCommand) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(ConsoleCommands::default)
+            .register(command);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConsoleConfig {
+    pub toggle_key: KeyCode,
+    pub visible_lines: usize,
+}
+
+impl Default for ConsoleConfig {
+    fn default() -> Self {
+        ConsoleConfig {
+            toggle_key: KeyCode::Grave,
+            visible_lines: 12,
+        }
+    }
+}
+
+/// A drop-down console for developer commands. While it is open, it consumes all keyboard input.
+#[derive(Debug, Default)]
+pub struct ConsolePlugin {
+    pub config: ConsoleConfig,
+}
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(self.config.clone())
+            .init_resource::<ConsoleState>()
+            .add_startup_system(add_console.system())
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                console_input_system.system().after(InputSystem),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                console_execution_system.exclusive_system().at_end(),
+            )
+            .add_system(console_text_system.system())
+            .add_console_command(ConsoleCommand::new(
+                "help",
+                "[command]",
+                "lists all commands or explains one",
+                help_command,
+            ))
+            .add_console_command(ConsoleCommand::new(
+                "inspect",
+                "[entity id]",
+                "prints the components of an entity, Manfred by default",
+                inspect_command,
+            ));
+    }
+}
@@ -0,0 +1,49 @@
+use bevy::prelude::{AppBuilder, IntoSystem, ParallelSystemDescriptorCoercion, Plugin};
+
+use crate::event::world_clock::TimeOfDayChangedEvent;
+use crate::plugin::console::AddConsoleCommand;
+use crate::resource::console::ConsoleCommand;
+use crate::resource::day_night::TintedMaterials;
+use crate::resource::world_clock::WorldClock;
+use crate::system::day_night::{time_command, tint_system, world_clock_system};
+
+#[derive(Debug, Clone)]
+pub struct DayNightConfig {
+    /// how many seconds pass in the game world per real second, 144 makes a day last 10 minutes
+    pub world_seconds_per_second: f32,
+    /// the hour the clock starts at in a new game
+    pub start_hour: f32,
+}
+
+impl Default for DayNightConfig {
+    fn default() -> Self {
+        DayNightConfig {
+            world_seconds_per_second: 144.0,
+            start_hour: 8.0,
+        }
+    }
+}
+
+/// Runs the world clock and tints all sprites according to the time of day. Other systems can
+/// read the `WorldClock` resource or listen to `TimeOfDayChangedEvent`s.
+#[derive(Debug, Default)]
+pub struct DayNightPlugin {
+    pub config: DayNightConfig,
+}
+
+impl Plugin for DayNightPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(self.config.clone())
+            .insert_resource(WorldClock::new(0, self.config.start_hour))
+            .init_resource::<TintedMaterials>()
+            .add_event::<TimeOfDayChangedEvent>()
+            .add_system(world_clock_system.system().label("world_clock"))
+            .add_system(tint_system.system().after("world_clock"))
+            .add_console_command(ConsoleCommand::new(
+                "time",
+                "[hour]",
+                "shows the time of day or sets the hour",
+                time_command,
+            ));
+    }
+}
@@ -0,0 +1,69 @@
+use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
+use bevy::prelude::{AppBuilder, IntoSystem, KeyCode, ParallelSystemDescriptorCoercion, Plugin};
+
+use crate::plugin::console::AddConsoleCommand;
+use crate::resource::console::ConsoleCommand;
+use crate::resource::debug_overlay::DebugOverlay;
+use crate::system::debug_overlay::{
+    add_debug_overlay, debug_overlay_command, debug_shapes_system, debug_text_system,
+    toggle_debug_overlay_system,
+};
+
+#[derive(Debug, Clone)]
+pub struct DebugOverlayConfig {
+    pub toggle_key: KeyCode,
+    /// the drawn velocity vector is this many times longer than the distance moved per frame
+    pub velocity_scale: f32,
+    pub visible_on_start: bool,
+}
+
+impl Default for DebugOverlayConfig {
+    fn default() -> Self {
+        DebugOverlayConfig {
+            toggle_key: KeyCode::F3,
+            velocity_scale: 10.0,
+            visible_on_start: false,
+        }
+    }
+}
+
+/// Draws colliders, velocities and view directions and lists the internal movement values.
+/// Only available with the `debug_overlay` feature.
+#[derive(Debug, Default)]
+pub struct DebugOverlayPlugin {
+    pub config: DebugOverlayConfig,
+}
+
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_plugin(FrameTimeDiagnosticsPlugin::default())
+            .insert_resource(self.config.clone())
+            .insert_resource(DebugOverlay {
+                visible: self.config.visible_on_start,
+            })
+            .add_startup_system(add_debug_overlay.system())
+            .add_system(
+                toggle_debug_overlay_system
+                    .system()
+                    .label("toggle_debug_overlay"),
+            )
+            .add_system(
+                debug_shapes_system
+                    .system()
+                    .after("toggle_debug_overlay")
+                    .after("update_position"),
+            )
+            .add_system(
+                debug_text_system
+                    .system()
+                    .after("toggle_debug_overlay")
+                    .after("update_position"),
+            )
+            .add_console_command(ConsoleCommand::new(
+                "debug_overlay",
+                "",
+                "shows or hides the debug overlay",
+                debug_overlay_command,
+            ));
+    }
+}
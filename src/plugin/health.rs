@@ -0,0 +1,59 @@
+use bevy::prelude::{AppBuilder, IntoSystem, ParallelSystemDescriptorCoercion, Plugin, SystemSet};
+
+use crate::event::damage::DamageEvent;
+use crate::system::health::{
+    add_health_system, damage_system, game_over_system, hazard_contact_system,
+    invulnerability_system, show_game_over,
+};
+use crate::types::game_state::GameState;
+
+#[derive(Debug, Clone)]
+pub struct HealthConfig {
+    pub manfred_max_health: u32,
+    /// how long an entity cannot be damaged again after being hit
+    pub invulnerability_seconds: f32,
+    /// how long the sprite stays visible or hidden while flashing
+    pub flash_interval: f32,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        HealthConfig {
+            manfred_max_health: 5,
+            invulnerability_seconds: 1.5,
+            flash_interval: 0.1,
+        }
+    }
+}
+
+/// Gives Manfred health, lets hazards hurt him and ends the game when he has no health left.
+#[derive(Debug, Default)]
+pub struct HealthPlugin {
+    pub config: HealthConfig,
+}
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(self.config.clone())
+            .add_state(GameState::Playing)
+            .add_event::<DamageEvent>()
+            .add_system(add_health_system.system())
+            .add_system(
+                hazard_contact_system
+                    .system()
+                    .label("hazard_contact")
+                    .after("update_position"),
+            )
+            .add_system(
+                damage_system
+                    .system()
+                    .label("damage")
+                    .after("hazard_contact"),
+            )
+            .add_system(invulnerability_system.system().after("damage"))
+            .add_system(game_over_system.system().after("damage"))
+            .add_system_set(
+                SystemSet::on_enter(GameState::GameOver).with_system(show_game_over.system()),
+            );
+    }
+}
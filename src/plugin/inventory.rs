@@ -0,0 +1,97 @@
+use bevy::prelude::{
+    AppBuilder, IntoExclusiveSystem, IntoSystem, KeyCode, ParallelSystemDescriptorCoercion, Plugin,
+    StartupStage,
+};
+
+use crate::event::item::ItemPickedUpEvent;
+use crate::plugin::console::AddConsoleCommand;
+use crate::resource::console::ConsoleCommand;
+use crate::system::equipment::{
+    add_equipment_system, equip_command, equipment_layer_system, equipment_sync_system,
+    unequip_command,
+};
+use crate::system::game_state::is_playing;
+use crate::system::inventory::{
+    add_inventory_panel, add_inventory_system, give_command, inventory_text_system,
+    item_pickup_system, load_item_definitions, toggle_inventory_panel_system,
+};
+
+#[derive(Debug, Clone)]
+pub struct InventoryConfig {
+    /// the number of slots in Manfred's inventory
+    pub capacity: usize,
+    /// the RON file the item definitions are loaded from
+    pub items_path: String,
+    pub interact_key: KeyCode,
+    pub toggle_key: KeyCode,
+    /// how far away from Manfred's collider an item can be picked up with the interact key
+    pub interact_distance: f32,
+}
+
+impl Default for InventoryConfig {
+    fn default() -> Self {
+        InventoryConfig {
+            capacity: 12,
+            items_path: "assets/items/items.ron".to_string(),
+            interact_key: KeyCode::E,
+            toggle_key: KeyCode::I,
+            interact_distance: 50.0,
+        }
+    }
+}
+
+/// Gives Manfred an inventory, lets him pick up the items of the level and shows what he carries.
+/// Items worn from the inventory are drawn as layers of Manfred's sprite, which needs the
+/// `AnimationConfig` of the `AnimationPlugin`.
+#[derive(Debug, Default)]
+pub struct InventoryPlugin {
+    pub config: InventoryConfig,
+}
+
+impl Plugin for InventoryPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(self.config.clone())
+            .add_event::<ItemPickedUpEvent>()
+            .add_startup_system_to_stage(
+                StartupStage::PreStartup,
+                load_item_definitions.exclusive_system(),
+            )
+            .add_startup_system(add_inventory_panel.system())
+            .add_system(add_inventory_system.system())
+            .add_system(
+                item_pickup_system
+                    .system()
+                    .with_run_criteria(is_playing.system())
+                    .label("item_pickup")
+                    .after("update_position"),
+            )
+            .add_system(toggle_inventory_panel_system.system())
+            .add_system(inventory_text_system.system().after("item_pickup"))
+            .add_system(add_equipment_system.system())
+            .add_system(equipment_layer_system.system().label("equipment_layers"))
+            .add_system(
+                equipment_sync_system
+                    .system()
+                    .after("equipment_layers")
+                    .after("animation"),
+            )
+            .add_console_command(ConsoleCommand::new(
+                "give",
+                "<item> [count]",
+                "puts items into Manfred's inventory",
+                give_command,
+            ))
+            .add_console_command(ConsoleCommand::new(
+                "equip",
+                "<item>",
+                "puts on an item from Manfred's inventory",
+                equip_command,
+            ))
+            .add_console_command(ConsoleCommand::new(
+                "unequip",
+                "<Head|Body|Back|Hand>",
+                "puts the item worn in the slot back into Manfred's inventory",
+                unequip_command,
+            ));
+    }
+}
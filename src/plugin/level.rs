@@ -0,0 +1,79 @@
+use bevy::prelude::{
+    AppBuilder, IntoExclusiveSystem, IntoSystem, ParallelSystemDescriptorCoercion, Plugin,
+    StartupStage,
+};
+
+use crate::plugin::console::AddConsoleCommand;
+use crate::resource::console::ConsoleCommand;
+use crate::system::elevation::add_shadow_system;
+use crate::system::level::{
+    add_fade_overlay, add_level_objects, add_manf, door_system, fade_overlay_system,
+    generate_forest_command, level_loading_system, level_transition_system, load_level_command,
+    load_level_definition, spawn_command,
+};
+
+#[derive(Debug, Clone)]
+pub struct LevelConfig {
+    /// the RON file the first level is loaded from
+    pub path: String,
+    /// how long fading to black and back takes when switching levels
+    pub fade_seconds: f32,
+    /// the settings the `generate_forest` console command uses
+    pub forest_settings: String,
+    /// where the `generate_forest` console command saves the forest
+    pub generated_forest: String,
+}
+
+impl Default for LevelConfig {
+    fn default() -> Self {
+        LevelConfig {
+            path: "assets/levels/meadow.ron".to_string(),
+            fade_seconds: 0.4,
+            forest_settings: "assets/generation/forest.ron".to_string(),
+            generated_forest: "assets/levels/generated_forest.ron".to_string(),
+        }
+    }
+}
+
+/// Loads the level file and spawns Manfred and the objects of the level. Doors switch to other
+/// levels behind a fade to black, so the plugin relies on the `TriggerPlugin`.
+#[derive(Debug, Default)]
+pub struct LevelPlugin {
+    pub config: LevelConfig,
+}
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(self.config.clone())
+            .add_startup_system_to_stage(
+                StartupStage::PreStartup,
+                load_level_definition.exclusive_system(),
+            )
+            .add_startup_system(add_manf.system())
+            .add_startup_system(add_level_objects.exclusive_system())
+            .add_startup_system(add_fade_overlay.system())
+            .add_system(door_system.system().label("door").after("trigger_zones"))
+            .add_system(level_transition_system.system().after("door"))
+            .add_system(level_loading_system.exclusive_system())
+            .add_system(fade_overlay_system.system())
+            .add_system(add_shadow_system.system())
+            .add_console_command(ConsoleCommand::new(
+                "spawn",
+                "<prefab> <x> <y>",
+                "places a prefab, e.g. a tree, in the level",
+                spawn_command,
+            ))
+            .add_console_command(ConsoleCommand::new(
+                "load_level",
+                "[path] [spawn point]",
+                "loads a level file, by default reloads the current one",
+                load_level_command,
+            ))
+            .add_console_command(ConsoleCommand::new(
+                "generate_forest",
+                "[seed]",
+                "generates a forest level and loads it",
+                generate_forest_command,
+            ));
+    }
+}
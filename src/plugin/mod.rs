@@ -0,0 +1,108 @@
+use bevy::prelude::{AppBuilder, CoreStage, IntoSystem, Plugin};
+
+use crate::plugin::animation::{AnimationConfig, AnimationPlugin};
+use crate::plugin::camera::{CameraConfig, CameraPlugin};
+use crate::plugin::console::{ConsoleConfig, ConsolePlugin};
+use crate::plugin::day_night::{DayNightConfig, DayNightPlugin};
+use crate::plugin::health::{HealthConfig, HealthPlugin};
+use crate::plugin::inventory::{InventoryConfig, InventoryPlugin};
+use crate::plugin::level::{LevelConfig, LevelPlugin};
+use crate::plugin::movement::{MovementConfig, MovementPlugin};
+use crate::plugin::network::{NetworkConfig, NetworkPlugin};
+use crate::plugin::quest::{QuestConfig, QuestPlugin};
+use crate::plugin::save::{SaveConfig, SavePlugin};
+use crate::plugin::sound::{SoundConfig, SoundPlugin};
+use crate::plugin::streaming::{StreamingConfig, StreamingPlugin};
+use crate::plugin::trigger::TriggerPlugin;
+use crate::resource::game_time::GameTime;
+use crate::system::game_time::game_time_system;
+
+pub mod animation;
+pub mod camera;
+pub mod console;
+pub mod day_night;
+#[cfg(feature = "debug_overlay")]
+pub mod debug_overlay;
+pub mod health;
+pub mod inventory;
+pub mod level;
+pub mod movement;
+pub mod network;
+pub mod quest;
+pub mod save;
+pub mod sound;
+pub mod streaming;
+pub mod trigger;
+
+/// Bundles everything needed to play the game. The configuration of every sub-plugin can be
+/// overridden, e.g. to start with a different level.
+#[derive(Debug, Clone, Default)]
+pub struct ManfredGamePlugin {
+    pub movement: MovementConfig,
+    pub animation: AnimationConfig,
+    pub camera: CameraConfig,
+    pub console: ConsoleConfig,
+    pub day_night: DayNightConfig,
+    pub health: HealthConfig,
+    pub inventory: InventoryConfig,
+    pub level: LevelConfig,
+    pub network: NetworkConfig,
+    pub quest: QuestConfig,
+    pub save: SaveConfig,
+    pub sound: SoundConfig,
+    pub streaming: StreamingConfig,
+    #[cfg(feature = "debug_overlay")]
+    pub debug_overlay: debug_overlay::DebugOverlayConfig,
+}
+
+impl Plugin for ManfredGamePlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<GameTime>()
+            .add_system_to_stage(CoreStage::PreUpdate, game_time_system.system())
+            .add_plugin(MovementPlugin {
+                config: self.movement.clone(),
+            })
+            .add_plugin(AnimationPlugin {
+                config: self.animation.clone(),
+            })
+            .add_plugin(CameraPlugin {
+                config: self.camera.clone(),
+            })
+            .add_plugin(ConsolePlugin {
+                config: self.console.clone(),
+            })
+            .add_plugin(DayNightPlugin {
+                config: self.day_night.clone(),
+            })
+            .add_plugin(HealthPlugin {
+                config: self.health.clone(),
+            })
+            .add_plugin(InventoryPlugin {
+                config: self.inventory.clone(),
+            })
+            .add_plugin(LevelPlugin {
+                config: self.level.clone(),
+            })
+            .add_plugin(StreamingPlugin {
+                config: self.streaming.clone(),
+            })
+            .add_plugin(TriggerPlugin)
+            .add_plugin(NetworkPlugin {
+                config: self.network.clone(),
+            })
+            .add_plugin(QuestPlugin {
+                config: self.quest.clone(),
+            })
+            .add_plugin(SavePlugin {
+                config: self.save.clone(),
+            })
+            .add_plugin(SoundPlugin {
+                config: self.sound.clone(),
+            });
+
+        #[cfg(feature = "debug_overlay")]
+        app.add_plugin(debug_overlay::DebugOverlayPlugin {
+            config: self.debug_overlay.clone(),
+        });
+    }
+}
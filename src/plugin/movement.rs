@@ -0,0 +1,108 @@
+use bevy::prelude::{AppBuilder, IntoSystem, ParallelSystemDescriptorCoercion, Plugin};
+
+use crate::component::elevation::JumpConfig;
+use crate::component::facing::FacingConfig;
+use crate::plugin::console::AddConsoleCommand;
+use crate::resource::checksum::ChecksumLog;
+use crate::resource::console::ConsoleCommand;
+use crate::system::checksum::{checksum_command, world_checksum_system};
+use crate::system::elevation::{elevation_system, shadow_system};
+use crate::system::game_state::is_playing;
+use crate::system::position::{add_position_system, move_positions_system, teleport_command};
+use crate::system::velocity::{easing_command, max_speed_command, velocity_control_system};
+use crate::types::easing::MovementProfile;
+use crate::types::input::InputBindings;
+
+#[derive(Debug, Clone)]
+pub struct MovementConfig {
+    pub max_speed: u16,
+    /// the input bindings of every local player, one Manfred is spawned per player
+    pub players: Vec<InputBindings>,
+    /// how Manfred speeds up and slows down
+    pub profile: MovementProfile,
+    /// how eagerly Manfred turns while walking almost diagonally
+    pub facing: FacingConfig,
+    pub jump: JumpConfig,
+}
+
+impl MovementConfig {
+    /// Two players on one keyboard or two gamepads.
+    pub fn co_op() -> MovementConfig {
+        MovementConfig {
+            players: vec![InputBindings::player_one(), InputBindings::player_two()],
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        MovementConfig {
+            max_speed: 5,
+            players: vec![InputBindings::player_one()],
+            profile: MovementProfile::default(),
+            facing: FacingConfig::default(),
+            jump: JumpConfig::default(),
+        }
+    }
+}
+
+/// Controls every player's Manfred via keyboard or gamepad and moves everything that has a
+/// velocity. Jumps lift Manfred over low obstacles, his shadow stays on the ground. Records a
+/// checksum of the movement every tick to detect when runs go out of sync.
+#[derive(Debug, Default)]
+pub struct MovementPlugin {
+    pub config: MovementConfig,
+}
+
+impl Plugin for MovementPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(self.config.clone())
+            .insert_resource(ChecksumLog::default())
+            .add_system(
+                velocity_control_system
+                    .system()
+                    .with_run_criteria(is_playing.system())
+                    .label("velocity"),
+            )
+            .add_system(
+                elevation_system
+                    .system()
+                    .label("elevation")
+                    .after("velocity"),
+            )
+            .add_system(add_position_system.system().before("update_position"))
+            .add_system(
+                move_positions_system
+                    .system()
+                    .label("update_position")
+                    .after("elevation"),
+            )
+            .add_system(shadow_system.system().after("update_position"))
+            .add_system(world_checksum_system.system().after("update_position"))
+            .add_console_command(ConsoleCommand::new(
+                "teleport",
+                "<x> <y>",
+                "puts Manfred at the given position",
+                teleport_command,
+            ))
+            .add_console_command(ConsoleCommand::new(
+                "max_speed",
+                "<speed>",
+                "sets how fast Manfred can walk",
+                max_speed_command,
+            ))
+            .add_console_command(ConsoleCommand::new(
+                "easing",
+                "<acceleration> [deceleration]",
+                "sets how Manfred speeds up and slows down",
+                easing_command,
+            ))
+            .add_console_command(ConsoleCommand::new(
+                "checksum",
+                "",
+                "shows the checksum of the latest tick",
+                checksum_command,
+            ));
+    }
+}
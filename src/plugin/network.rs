@@ -0,0 +1,73 @@
+use std::net::{SocketAddr, ToSocketAddrs};
+
+use bevy::log::error;
+use bevy::prelude::{AppBuilder, IntoSystem, ParallelSystemDescriptorCoercion, Plugin};
+
+use crate::net::conditioner::LinkConditions;
+use crate::net::socket::NetSocket;
+use crate::resource::network::NetworkClient;
+use crate::system::game_state::is_playing;
+use crate::system::network::{
+    connect_system, disconnect_system, network_input_system, network_receive_system,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct NetworkConfig {
+    /// the address of the server, e.g. `127.0.0.1:7777`, the game is played offline without it
+    pub server: Option<String>,
+    /// simulated latency and packet loss of the messages sent to the server
+    pub conditions: Option<LinkConditions>,
+}
+
+/// Connects to a server started with the `server` binary. The first player's movement is predicted
+/// locally and corrected by the server, the other players appear as remote Manfreds.
+#[derive(Debug, Default)]
+pub struct NetworkPlugin {
+    pub config: NetworkConfig,
+}
+
+impl Plugin for NetworkPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        let server = match &self.config.server {
+            Some(server) => server,
+            None => return,
+        };
+
+        let client = resolve(server).and_then(|server| {
+            NetSocket::bind("0.0.0.0:0", self.config.conditions.clone())
+                .map(|socket| NetworkClient::new(socket, server))
+        });
+        let client = match client {
+            Ok(client) => client,
+            Err(message) => {
+                error!("{}, playing offline", message);
+                return;
+            }
+        };
+
+        app.insert_resource(self.config.clone())
+            .insert_resource(client)
+            .add_system(connect_system.system())
+            .add_system(
+                network_receive_system
+                    .system()
+                    .label("network_receive")
+                    .before("velocity"),
+            )
+            .add_system(
+                network_input_system
+                    .system()
+                    .with_run_criteria(is_playing.system())
+                    .after("network_receive"),
+            )
+            .add_system(disconnect_system.system());
+    }
+}
+
+fn resolve(server: &str) -> Result<SocketAddr, String> {
+    server
+        .to_socket_addrs()
+        .map_err(|error| format!("cannot resolve {}: {}", server, error))?
+        .next()
+        .ok_or_else(|| format!("{} has no address", server))
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::{
+    AppBuilder, IntoExclusiveSystem, IntoSystem, KeyCode, ParallelSystemDescriptorCoercion, Plugin,
+    StartupStage,
+};
+
+use crate::event::interaction::TalkedToEvent;
+use crate::plugin::console::AddConsoleCommand;
+use crate::resource::console::ConsoleCommand;
+use crate::resource::quest_log::QuestLog;
+use crate::resource::world_flags::WorldFlags;
+use crate::system::game_state::is_playing;
+use crate::system::quest::{
+    add_quest_log_panel, flag_command, load_quest_definitions, quest_log_text_system,
+    quest_progress_system, start_quests_system, talk_system, toggle_quest_log_system,
+};
+
+#[derive(Debug, Clone)]
+pub struct QuestConfig {
+    /// the RON file the quest definitions are loaded from
+    pub quests_path: String,
+    pub talk_key: KeyCode,
+    pub toggle_key: KeyCode,
+    /// how far away from Manfred's collider an NPC can be talked to
+    pub talk_distance: f32,
+}
+
+impl Default for QuestConfig {
+    fn default() -> Self {
+        QuestConfig {
+            quests_path: "assets/quests/quests.ron".to_string(),
+            talk_key: KeyCode::T,
+            toggle_key: KeyCode::L,
+            talk_distance: 60.0,
+        }
+    }
+}
+
+/// Tracks world flags and quests. Quest objectives are advanced by talking to NPCs, by picked up
+/// items and by entered trigger zones, so the plugin relies on the `InventoryPlugin` and the
+/// `TriggerPlugin` for their events.
+#[derive(Debug, Default)]
+pub struct QuestPlugin {
+    pub config: QuestConfig,
+}
+
+impl Plugin for QuestPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(self.config.clone())
+            .init_resource::<WorldFlags>()
+            .init_resource::<QuestLog>()
+            .add_event::<TalkedToEvent>()
+            .add_startup_system_to_stage(
+                StartupStage::PreStartup,
+                load_quest_definitions.exclusive_system(),
+            )
+            .add_startup_system(add_quest_log_panel.system())
+            .add_system(start_quests_system.system().label("quest_start"))
+            .add_system(
+                talk_system
+                    .system()
+                    .with_run_criteria(is_playing.system())
+                    .label("talk"),
+            )
+            .add_system(
+                quest_progress_system
+                    .system()
+                    .label("quest_progress")
+                    .after("quest_start")
+                    .after("talk")
+                    .after("item_pickup")
+                    .after("trigger_zones"),
+            )
+            .add_system(toggle_quest_log_system.system())
+            .add_system(quest_log_text_system.system().after("quest_progress"))
+            .add_console_command(ConsoleCommand::new(
+                "flag",
+                "[name] [value]",
+                "lists the world flags, shows one or sets it",
+                flag_command,
+            ));
+    }
+}
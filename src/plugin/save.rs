@@ -0,0 +1,44 @@
+use bevy::prelude::{AppBuilder, Plugin};
+
+use crate::plugin::console::AddConsoleCommand;
+use crate::resource::console::ConsoleCommand;
+use crate::system::save::{load_command, save_command};
+
+#[derive(Debug, Clone)]
+pub struct SaveConfig {
+    /// where the `save` and `load` console commands write and read the game by default
+    pub path: String,
+}
+
+impl Default for SaveConfig {
+    fn default() -> Self {
+        SaveConfig {
+            path: "saves/quicksave.ron".to_string(),
+        }
+    }
+}
+
+/// Saves and loads the game: the level, Manfred, his inventory, the world flags, the quest log and
+/// the world clock.
+#[derive(Debug, Default)]
+pub struct SavePlugin {
+    pub config: SaveConfig,
+}
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(self.config.clone())
+            .add_console_command(ConsoleCommand::new(
+                "save",
+                "[path]",
+                "saves the game",
+                save_command,
+            ))
+            .add_console_command(ConsoleCommand::new(
+                "load",
+                "[path]",
+                "loads a saved game",
+                load_command,
+            ));
+    }
+}
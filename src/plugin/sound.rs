@@ -0,0 +1,73 @@
+use bevy::prelude::{
+    AppBuilder, IntoExclusiveSystem, IntoSystem, ParallelSystemDescriptorCoercion, Plugin,
+    StartupStage,
+};
+
+use crate::event::sound::PlaySoundEvent;
+use crate::plugin::console::AddConsoleCommand;
+use crate::resource::console::ConsoleCommand;
+use crate::resource::sound::{AmbientTrack, SoundVolumes};
+use crate::system::sound::{
+    ambient_track_system, footstep_system, load_footstep_definitions, play_ambient_system,
+    play_sounds_system, volume_command, volume_system,
+};
+
+#[derive(Debug, Clone)]
+pub struct SoundConfig {
+    /// the RON file the footstep sounds of every surface are loaded from
+    pub footsteps_path: String,
+    /// the volume of footsteps and other effects from 0 to 1
+    pub effects_volume: f32,
+    /// the volume of the ambient tracks of the levels from 0 to 1
+    pub ambient_volume: f32,
+}
+
+impl Default for SoundConfig {
+    fn default() -> Self {
+        SoundConfig {
+            footsteps_path: "assets/sounds/footsteps.ron".to_string(),
+            effects_volume: 0.8,
+            ambient_volume: 0.5,
+        }
+    }
+}
+
+/// Plays footsteps in sync with the walk animation and loops the ambient track of the current
+/// level. The `AnimationPlugin` sends the footstep events. Nothing is heard unless the
+/// `bevy_kira_audio` plugin was added to the app as well.
+#[derive(Debug, Default)]
+pub struct SoundPlugin {
+    pub config: SoundConfig,
+}
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(self.config.clone())
+            .insert_resource(SoundVolumes {
+                effects: self.config.effects_volume,
+                ambient: self.config.ambient_volume,
+            })
+            .init_resource::<AmbientTrack>()
+            .add_event::<PlaySoundEvent>()
+            .add_startup_system_to_stage(
+                StartupStage::PreStartup,
+                load_footstep_definitions.exclusive_system(),
+            )
+            .add_system(
+                footstep_system
+                    .system()
+                    .label("footsteps")
+                    .after("animation"),
+            )
+            .add_system(play_sounds_system.system().after("footsteps"))
+            .add_system(ambient_track_system.system().label("ambient_track"))
+            .add_system(play_ambient_system.system().after("ambient_track"))
+            .add_system(volume_system.system())
+            .add_console_command(ConsoleCommand::new(
+                "volume",
+                "[effects|ambient] [volume]",
+                "shows or sets the volume of a sound channel",
+                volume_command,
+            ));
+    }
+}
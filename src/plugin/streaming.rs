@@ -0,0 +1,46 @@
+use bevy::prelude::{
+    AppBuilder, IntoExclusiveSystem, IntoSystem, ParallelSystemDescriptorCoercion, Plugin,
+};
+
+use crate::resource::streaming::LoadedChunks;
+use crate::system::streaming::{request_chunks_system, spawn_chunks_system, unload_chunks_system};
+
+#[derive(Debug, Clone)]
+pub struct StreamingConfig {
+    /// chunks up to this many chunks away from Manfred are loaded
+    pub load_radius: i32,
+    /// chunks more than this many chunks away from Manfred are despawned, keep it at least as
+    /// large as the load radius so chunks do not flicker at the border
+    pub unload_radius: i32,
+}
+
+impl Default for StreamingConfig {
+    fn default() -> Self {
+        StreamingConfig {
+            load_radius: 1,
+            unload_radius: 2,
+        }
+    }
+}
+
+/// Spawns and despawns the chunks of streamed levels as Manfred walks around. The chunk files are
+/// read on the IO task pool.
+#[derive(Debug, Default)]
+pub struct StreamingPlugin {
+    pub config: StreamingConfig,
+}
+
+impl Plugin for StreamingPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(self.config.clone())
+            .init_resource::<LoadedChunks>()
+            .add_system(
+                request_chunks_system
+                    .system()
+                    .label("request_chunks")
+                    .after("update_position"),
+            )
+            .add_system(unload_chunks_system.system().after("request_chunks"))
+            .add_system(spawn_chunks_system.exclusive_system().at_end());
+    }
+}
@@ -0,0 +1,22 @@
+use bevy::prelude::{AppBuilder, IntoSystem, ParallelSystemDescriptorCoercion, Plugin};
+
+use crate::event::trigger::{TriggerEnteredEvent, TriggerExitedEvent, TriggerStayEvent};
+use crate::system::trigger::trigger_zone_system;
+
+/// Sends events when Manfred enters, stays in or leaves the trigger zones of the level.
+#[derive(Debug, Default)]
+pub struct TriggerPlugin;
+
+impl Plugin for TriggerPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_event::<TriggerEnteredEvent>()
+            .add_event::<TriggerStayEvent>()
+            .add_event::<TriggerExitedEvent>()
+            .add_system(
+                trigger_zone_system
+                    .system()
+                    .label("trigger_zones")
+                    .after("update_position"),
+            );
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::VecDeque;
+
+/// The world checksums of the most recent ticks. Two runs fed the same inputs have to record the
+/// same checksums, the first tick where they differ is where they went out of sync.
+#[derive(Debug, Clone)]
+pub struct ChecksumLog {
+    capacity: usize,
+    /// the tick of the oldest checksum still kept
+    first_tick: u64,
+    checksums: VecDeque<u64>,
+}
+
+impl ChecksumLog {
+    pub fn new(capacity: usize) -> ChecksumLog {
+        ChecksumLog {
+            capacity: capacity.max(1),
+            first_tick: 0,
+            checksums: VecDeque::new(),
+        }
+    }
+
+    /// Records the checksum of the next tick and returns that tick.
+    pub fn record(&mut self, checksum: u64) -> u64 {
+        if self.checksums.len() == self.capacity {
+            self.checksums.pop_front();
+            self.first_tick += 1;
+        }
+        self.checksums.push_back(checksum);
+        self.first_tick + self.checksums.len() as u64 - 1
+    }
+
+    /// The newest tick and its checksum.
+    pub fn latest(&self) -> Option<(u64, u64)> {
+        self.checksums
+            .back()
+            .map(|checksum| (self.first_tick + self.checksums.len() as u64 - 1, *checksum))
+    }
+
+    pub fn at(&self, tick: u64) -> Option<u64> {
+        let index = tick.checked_sub(self.first_tick)?;
+        self.checksums.get(index as usize).copied()
+    }
+
+    /// The first tick both logs still know about whose checksums differ.
+    pub fn first_difference(&self, other: &ChecksumLog) -> Option<u64> {
+        let mut tick = self.first_tick.max(other.first_tick);
+        while let (Some(own), Some(others)) = (self.at(tick), other.at(tick)) {
+            if own != others {
+                return Some(tick);
+            }
+            tick += 1;
+        }
+        None
+    }
+}
+
+impl Default for ChecksumLog {
+    fn default() -> Self {
+        ChecksumLog::new(600)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resource::checksum::ChecksumLog;
+
+    #[test]
+    fn keeps_only_the_newest_checksums() {
+        let mut under_test = ChecksumLog::new(3);
+
+        for checksum in 10..15 {
+            under_test.record(checksum);
+        }
+
+        assert_eq!(None, under_test.at(1));
+        assert_eq!(Some(12), under_test.at(2));
+        assert_eq!(Some(14), under_test.at(4));
+        assert_eq!(None, under_test.at(5));
+        assert_eq!(Some((4, 14)), under_test.latest());
+    }
+
+    #[test]
+    fn first_difference_finds_where_runs_diverge() {
+        let mut first = ChecksumLog::new(10);
+        let mut second = ChecksumLog::new(4);
+        for tick in 0..8 {
+            first.record(tick);
+            second.record(if tick < 6 { tick } else { 100 });
+        }
+
+        assert_eq!(Some(6), first.first_difference(&second));
+        assert_eq!(Some(6), second.first_difference(&first));
+    }
+
+    #[test]
+    fn identical_runs_have_no_difference() {
+        let mut first = ChecksumLog::default();
+        let mut second = ChecksumLog::default();
+        for tick in 0..5 {
+            first.record(tick * 7);
+            second.record(tick * 7);
+        }
+
+        assert_eq!(None, first.first_difference(&second));
+    }
+}
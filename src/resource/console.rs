@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use bevy::prelude::World;
+
+pub type ConsoleCommandHandler = fn(&mut World, &ConsoleArgs) -> Result<String, String>;
+
+/// A command that can be typed into the developer console, e.g. `teleport 100 -20`.
+#[derive(Clone)]
+pub struct ConsoleCommand {
+    pub name: &'static str,
+    /// the arguments, e.g. `<x> <y>`
+    pub usage: &'static str,
+    pub help: &'static str,
+    pub handler: ConsoleCommandHandler,
+}
+
+impl ConsoleCommand {
+    pub fn new(
+        name: &'static str,
+        usage: &'static str,
+        help: &'static str,
+        handler: ConsoleCommandHandler,
+    ) -> ConsoleCommand {
+        ConsoleCommand {
+            name,
+            usage,
+            help,
+            handler,
+        }
+    }
+
+    pub fn help_line(&self) -> String {
+        match self.usage {
+            "" => format!("{} - {}", self.name, self.help),
+            usage => format!("{} {} - {}", self.name, usage, self.help),
+        }
+    }
+}
+
+/// All commands known to the console. Plugins register their own commands via
+/// [crate::plugin::console::AddConsoleCommand].
+#[derive(Default)]
+pub struct ConsoleCommands {
+    commands: BTreeMap<&'static str, ConsoleCommand>,
+}
+
+impl ConsoleCommands {
+    pub fn register(&mut self, command: ConsoleCommand) {
+        self.commands.insert(command.name, command);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ConsoleCommand> {
+        self.commands.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ConsoleCommand> {
+        self.commands.values()
+    }
+}
+
+pub struct ConsoleArgs {
+    args: Vec<String>,
+}
+
+impl ConsoleArgs {
+    pub fn new(args: Vec<String>) -> ConsoleArgs {
+        ConsoleArgs { args }
+    }
+
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.args.get(index).map(String::as_str)
+    }
+
+    pub fn parse<T>(&self, index: usize, name: &str) -> Result<T, String>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let arg = self
+            .get(index)
+            .ok_or_else(|| format!("missing argument <{}>", name))?;
+
+        arg.parse()
+            .map_err(|error| format!("invalid <{}> '{}': {}", name, arg, error))
+    }
+
+    pub fn parse_optional<T>(&self, index: usize, name: &str) -> Result<Option<T>, String>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match self.get(index) {
+            None => Ok(None),
+            Some(_) => self.parse(index, name).map(Some),
+        }
+    }
+}
+
+/// State of the drop-down console.
+#[derive(Debug, Default)]
+pub struct ConsoleState {
+    pub open: bool,
+    pub input: String,
+    pub history: Vec<String>,
+    /// lines that were submitted but not yet executed
+    pub submitted: Vec<String>,
+}
+
+/// Parses the line and runs the matching command. This is what the console does when Enter is
+/// pressed.
+pub fn execute_console_command(world: &mut World, line: &str) -> Result<String, String> {
+    let mut words = line.split_whitespace().map(str::to_string);
+    let name = match words.next() {
+        Some(name) => name,
+        None => return Ok(String::new()),
+    };
+
+    let command = world
+        .get_resource::<ConsoleCommands>()
+        .and_then(|commands| commands.get(&name))
+        .cloned()
+        .ok_or_else(|| format!("unknown command '{}', try 'help'", name))?;
+
+    (command.handler)(world, &ConsoleArgs::new(words.collect()))
+        .map_err(|error| format!("{}\nusage: {} {}", error, command.name, command.usage))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::World;
+    use rstest::*;
+
+    use crate::resource::console::{
+        execute_console_command, ConsoleArgs, ConsoleCommand, ConsoleCommands,
+    };
+
+    fn add(_: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+        let a = args.parse::<i32>(0, "a")?;
+        let b = args.parse::<i32>(1, "b")?;
+        Ok((a + b).to_string())
+    }
+
+    fn world_with_add_command() -> World {
+        let mut world = World::default();
+        let mut commands = ConsoleCommands::default();
+        commands.register(ConsoleCommand::new(
+            "add",
+            "<a> <b>",
+            "adds two numbers",
+            add,
+        ));
+        world.insert_resource(commands);
+        world
+    }
+
+    #[test]
+    fn executes_registered_command_with_arguments() {
+        let mut world = world_with_add_command();
+
+        assert_eq!(
+            Ok("5".to_string()),
+            execute_console_command(&mut world, " add  2 3 ")
+        );
+    }
+
+    #[rstest]
+    #[case("add 2", "missing argument <b>\nusage: add <a> <b>")]
+    #[case(
+        "add 2 x",
+        "invalid <b> 'x': invalid digit found in string\nusage: add <a> <b>"
+    )]
+    #[case("subtract 2 3", "unknown command 'subtract', try 'help'")]
+    fn reports_errors(#[case] line: &str, #[case] expected_error: &str) {
+        let mut world = world_with_add_command();
+
+        assert_eq!(
+            Err(expected_error.to_string()),
+            execute_console_command(&mut world, line)
+        );
+    }
+
+    #[test]
+    fn empty_line_does_nothing() {
+        let mut world = world_with_add_command();
+
+        assert_eq!(
+            Ok(String::new()),
+            execute_console_command(&mut world, "   ")
+        );
+    }
+
+    #[test]
+    fn help_line_contains_usage() {
+        let command = ConsoleCommand::new("add", "<a> <b>", "adds two numbers", add);
+
+        assert_eq!("add <a> <b> - adds two numbers", command.help_line());
+    }
+}
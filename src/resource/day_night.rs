@@ -0,0 +1,13 @@
+use std::collections::HashMap;
+
+use bevy::asset::HandleId;
+use bevy::prelude::Color;
+
+/// Remembers the untinted colour of every sprite material, as the day and night tint is applied
+/// to the materials themselves.
+#[derive(Debug, Default)]
+pub struct TintedMaterials {
+    pub base_colors: HashMap<HandleId, Color>,
+    /// the tint that was last applied to all materials
+    pub tint: Option<Color>,
+}
@@ -0,0 +1,13 @@
+use bevy::prelude::{ColorMaterial, Handle};
+
+#[derive(Debug, Clone, Default)]
+pub struct DebugOverlay {
+    pub visible: bool,
+}
+
+pub struct DebugOverlayMaterials {
+    pub collider: Handle<ColorMaterial>,
+    pub velocity: Handle<ColorMaterial>,
+    pub facing: Handle<ColorMaterial>,
+    pub trigger: Handle<ColorMaterial>,
+}
@@ -0,0 +1,45 @@
+/// The time as seen by the game logic. It is advanced once per frame from bevy's `Time`, which
+/// allows tests to drive it manually.
+#[derive(Debug, Clone, Default)]
+pub struct GameTime {
+    delta_seconds: f32,
+    seconds_since_startup: f64,
+    frames: u64,
+}
+
+impl GameTime {
+    pub fn advance(&mut self, delta_seconds: f32) {
+        self.delta_seconds = delta_seconds;
+        self.seconds_since_startup += delta_seconds as f64;
+        self.frames += 1;
+    }
+
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta_seconds
+    }
+
+    pub fn seconds_since_startup(&self) -> f64 {
+        self.seconds_since_startup
+    }
+
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resource::game_time::GameTime;
+
+    #[test]
+    fn advancing_accumulates_time_and_frames() {
+        let mut under_test = GameTime::default();
+
+        under_test.advance(0.5);
+        under_test.advance(0.25);
+
+        assert_eq!(0.25, under_test.delta_seconds());
+        assert_eq!(0.75, under_test.seconds_since_startup());
+        assert_eq!(2, under_test.frames());
+    }
+}
@@ -0,0 +1,161 @@
+/// Knows which level is loaded and drives the fade to black while switching to another one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelManager {
+    current: String,
+    transition: Option<LevelTransition>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelTransition {
+    pub target_level: String,
+    pub target_spawn_point: String,
+    pub phase: TransitionPhase,
+    /// seconds spent in the current phase
+    pub elapsed: f32,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TransitionPhase {
+    FadingOut,
+    /// the screen is black, waiting for the level to be switched
+    Loading,
+    FadingIn,
+}
+
+impl LevelManager {
+    pub fn new(current: &str) -> LevelManager {
+        LevelManager {
+            current: current.to_string(),
+            transition: None,
+        }
+    }
+
+    pub fn current(&self) -> &str {
+        &self.current
+    }
+
+    pub fn set_current(&mut self, path: &str) {
+        self.current = path.to_string();
+    }
+
+    pub fn transition(&self) -> Option<&LevelTransition> {
+        self.transition.as_ref()
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Starts fading out. Returns false if another transition is still running.
+    pub fn request_transition(&mut self, target_level: &str, target_spawn_point: &str) -> bool {
+        if self.is_transitioning() {
+            return false;
+        }
+
+        self.transition = Some(LevelTransition {
+            target_level: target_level.to_string(),
+            target_spawn_point: target_spawn_point.to_string(),
+            phase: TransitionPhase::FadingOut,
+            elapsed: 0.0,
+        });
+        true
+    }
+
+    pub fn advance(&mut self, delta_seconds: f32, fade_seconds: f32) {
+        if let Some(transition) = self.transition.as_mut() {
+            if transition.phase == TransitionPhase::Loading {
+                return;
+            }
+
+            transition.elapsed += delta_seconds;
+            if transition.elapsed < fade_seconds {
+                return;
+            }
+
+            match transition.phase {
+                TransitionPhase::FadingOut => {
+                    transition.phase = TransitionPhase::Loading;
+                    transition.elapsed = 0.0;
+                }
+                _ => self.transition = None,
+            }
+        }
+    }
+
+    /// Returns the level and spawn point to switch to once the screen is black and starts fading
+    /// in again.
+    pub fn take_pending_load(&mut self) -> Option<(String, String)> {
+        match self.transition.as_mut() {
+            Some(transition) if transition.phase == TransitionPhase::Loading => {
+                transition.phase = TransitionPhase::FadingIn;
+                Some((
+                    transition.target_level.clone(),
+                    transition.target_spawn_point.clone(),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// How black the screen is, from 0 to 1.
+    pub fn fade_alpha(&self, fade_seconds: f32) -> f32 {
+        let progress = |elapsed: f32| (elapsed / fade_seconds).min(1.0);
+
+        match &self.transition {
+            None => 0.0,
+            Some(transition) => match transition.phase {
+                TransitionPhase::FadingOut => progress(transition.elapsed),
+                TransitionPhase::Loading => 1.0,
+                TransitionPhase::FadingIn => 1.0 - progress(transition.elapsed),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resource::level_manager::{LevelManager, TransitionPhase};
+
+    fn phase(manager: &LevelManager) -> Option<TransitionPhase> {
+        manager.transition().map(|transition| transition.phase)
+    }
+
+    #[test]
+    fn transition_fades_out_loads_and_fades_in() {
+        let mut under_test = LevelManager::new("meadow.ron");
+        under_test.request_transition("farmhouse.ron", "front_door");
+
+        under_test.advance(0.25, 0.5);
+        assert_eq!(Some(TransitionPhase::FadingOut), phase(&under_test));
+        assert_eq!(0.5, under_test.fade_alpha(0.5));
+        assert_eq!(None, under_test.take_pending_load());
+
+        under_test.advance(0.25, 0.5);
+        under_test.advance(0.25, 0.5);
+        assert_eq!(Some(TransitionPhase::Loading), phase(&under_test));
+        assert_eq!(1.0, under_test.fade_alpha(0.5));
+
+        assert_eq!(
+            Some(("farmhouse.ron".to_string(), "front_door".to_string())),
+            under_test.take_pending_load()
+        );
+        assert_eq!(Some(TransitionPhase::FadingIn), phase(&under_test));
+
+        under_test.advance(0.5, 0.5);
+        assert_eq!(None, phase(&under_test));
+        assert_eq!(0.0, under_test.fade_alpha(0.5));
+    }
+
+    #[test]
+    fn only_one_transition_at_a_time() {
+        let mut under_test = LevelManager::new("meadow.ron");
+
+        assert!(under_test.request_transition("farmhouse.ron", "front_door"));
+        assert!(!under_test.request_transition("cave.ron", "entrance"));
+
+        assert_eq!(
+            "farmhouse.ron",
+            under_test.transition().unwrap().target_level
+        );
+    }
+}
@@ -0,0 +1,14 @@
+pub mod checksum;
+pub mod console;
+pub mod day_night;
+#[cfg(feature = "debug_overlay")]
+pub mod debug_overlay;
+pub mod game_time;
+pub mod level_manager;
+pub mod network;
+pub mod palette;
+pub mod quest_log;
+pub mod sound;
+pub mod streaming;
+pub mod world_clock;
+pub mod world_flags;
@@ -0,0 +1,38 @@
+use std::net::SocketAddr;
+
+use crate::net::prediction::Prediction;
+use crate::net::simulation::MovementRules;
+use crate::net::socket::NetSocket;
+
+/// The connection of this game to the server.
+#[derive(Debug)]
+pub struct NetworkClient {
+    pub socket: NetSocket,
+    pub server: SocketAddr,
+    /// the id the server gave this player, none until the server answered
+    pub id: Option<u32>,
+    /// how the server moves the players, the default until it answered
+    pub rules: MovementRules,
+    pub prediction: Prediction,
+    /// the newest input sequence number the server has applied
+    pub acknowledged: u32,
+    pub last_connect_attempt: Option<f64>,
+}
+
+impl NetworkClient {
+    pub fn new(socket: NetSocket, server: SocketAddr) -> NetworkClient {
+        NetworkClient {
+            socket,
+            server,
+            id: None,
+            rules: MovementRules::default(),
+            prediction: Prediction::default(),
+            acknowledged: 0,
+            last_connect_attempt: None,
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.id.is_some()
+    }
+}
@@ -0,0 +1,27 @@
+use std::collections::HashMap;
+
+use bevy::prelude::{Handle, TextureAtlas};
+
+/// The recoloured sprite atlases by palette name, so every palette is only applied once.
+#[derive(Debug, Default)]
+pub struct PaletteAtlases {
+    atlases: HashMap<String, Handle<TextureAtlas>>,
+}
+
+impl PaletteAtlases {
+    pub fn get(&self, palette: &str) -> Option<Handle<TextureAtlas>> {
+        self.atlases.get(palette).cloned()
+    }
+
+    pub fn insert(&mut self, palette: &str, atlas: Handle<TextureAtlas>) {
+        self.atlases.insert(palette.to_string(), atlas);
+    }
+
+    pub fn len(&self) -> usize {
+        self.atlases.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.atlases.is_empty()
+    }
+}
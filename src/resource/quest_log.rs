@@ -0,0 +1,269 @@
+use serde::{Deserialize, Serialize};
+
+use crate::data::quest::{ObjectiveEvent, QuestDefinition, QuestDefinitions};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum QuestStatus {
+    Active,
+    Finished,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct QuestProgress {
+    pub id: String,
+    pub status: QuestStatus,
+    /// the index of the current stage
+    pub stage: usize,
+    /// the progress of each objective of the current stage
+    pub objectives: Vec<u32>,
+}
+
+/// All quests Manfred has started, in the order he started them.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct QuestLog {
+    quests: Vec<QuestProgress>,
+}
+
+impl QuestLog {
+    /// Returns false if the quest was started before.
+    pub fn start(&mut self, quest: &QuestDefinition) -> bool {
+        if self.get(&quest.id).is_some() {
+            return false;
+        }
+
+        self.quests.push(QuestProgress {
+            id: quest.id.clone(),
+            status: QuestStatus::Active,
+            stage: 0,
+            objectives: vec![
+                0;
+                quest
+                    .stages
+                    .first()
+                    .map_or(0, |stage| stage.objectives.len())
+            ],
+        });
+        true
+    }
+
+    pub fn get(&self, id: &str) -> Option<&QuestProgress> {
+        self.quests.iter().find(|quest| quest.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &QuestProgress> {
+        self.quests.iter()
+    }
+
+    /// Advances the objectives of all active quests and moves them to their next stage once all
+    /// objectives of the current one are fulfilled. Returns the ids of the quests that were
+    /// finished by the event.
+    pub fn record(
+        &mut self,
+        definitions: &QuestDefinitions,
+        event: &ObjectiveEvent,
+    ) -> Vec<String> {
+        let mut finished = vec![];
+
+        for progress in self.quests.iter_mut() {
+            if progress.status != QuestStatus::Active {
+                continue;
+            }
+            let definition = match definitions.get(&progress.id) {
+                Some(definition) => definition,
+                None => continue,
+            };
+            // e.g. a saved stage of a quest that got shorter since, there is nothing left to do
+            let stage = match definition.stages.get(progress.stage) {
+                Some(stage) => stage,
+                None => {
+                    progress.status = QuestStatus::Finished;
+                    progress.objectives.clear();
+                    finished.push(progress.id.clone());
+                    continue;
+                }
+            };
+
+            for (objective, value) in stage.objectives.iter().zip(progress.objectives.iter_mut()) {
+                *value = (*value + objective.progress_from(event)).min(objective.required());
+            }
+
+            let stage_done = stage
+                .objectives
+                .iter()
+                .zip(progress.objectives.iter())
+                .all(|(objective, value)| *value >= objective.required());
+            if !stage_done {
+                continue;
+            }
+
+            progress.stage += 1;
+            match definition.stages.get(progress.stage) {
+                Some(next_stage) => progress.objectives = vec![0; next_stage.objectives.len()],
+                None => {
+                    progress.status = QuestStatus::Finished;
+                    progress.objectives.clear();
+                    finished.push(progress.id.clone());
+                }
+            }
+        }
+
+        finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::quest::{
+        Objective, ObjectiveEvent, QuestDefinition, QuestDefinitions, QuestStage,
+    };
+    use crate::resource::quest_log::{QuestLog, QuestProgress, QuestStatus};
+
+    fn definitions() -> QuestDefinitions {
+        QuestDefinitions {
+            quests: vec![QuestDefinition {
+                id: "firewood".to_string(),
+                title: "Firewood".to_string(),
+                requires_flag: None,
+                stages: vec![
+                    QuestStage {
+                        description: "gather".to_string(),
+                        objectives: vec![
+                            Objective::Collect {
+                                item: "stick".to_string(),
+                                count: 3,
+                            },
+                            Objective::Reach("old well".to_string()),
+                        ],
+                    },
+                    QuestStage {
+                        description: "deliver".to_string(),
+                        objectives: vec![Objective::TalkTo("Farmer".to_string())],
+                    },
+                ],
+                rewards: vec![],
+            }],
+        }
+    }
+
+    fn sticks(count: u32) -> ObjectiveEvent {
+        ObjectiveEvent::Collected {
+            item: "stick".to_string(),
+            count,
+        }
+    }
+
+    fn started_log(definitions: &QuestDefinitions) -> QuestLog {
+        let mut log = QuestLog::default();
+        log.start(definitions.get("firewood").unwrap());
+        log
+    }
+
+    #[test]
+    fn quests_can_only_be_started_once() {
+        let definitions = definitions();
+        let mut under_test = started_log(&definitions);
+
+        assert!(!under_test.start(definitions.get("firewood").unwrap()));
+        assert_eq!(1, under_test.iter().count());
+    }
+
+    #[test]
+    fn progress_is_capped_at_the_required_amount() {
+        let definitions = definitions();
+        let mut under_test = started_log(&definitions);
+
+        under_test.record(&definitions, &sticks(2));
+        under_test.record(&definitions, &sticks(2));
+
+        assert_eq!(vec![3, 0], under_test.get("firewood").unwrap().objectives);
+    }
+
+    #[test]
+    fn stage_advances_once_all_objectives_are_fulfilled() {
+        let definitions = definitions();
+        let mut under_test = started_log(&definitions);
+
+        under_test.record(&definitions, &sticks(3));
+        assert_eq!(0, under_test.get("firewood").unwrap().stage);
+
+        under_test.record(
+            &definitions,
+            &ObjectiveEvent::Reached("old well".to_string()),
+        );
+
+        let progress = under_test.get("firewood").unwrap();
+        assert_eq!(1, progress.stage);
+        assert_eq!(vec![0], progress.objectives);
+    }
+
+    #[test]
+    fn completing_the_last_stage_finishes_the_quest() {
+        let definitions = definitions();
+        let mut under_test = started_log(&definitions);
+        under_test.record(&definitions, &sticks(3));
+        under_test.record(
+            &definitions,
+            &ObjectiveEvent::Reached("old well".to_string()),
+        );
+
+        let finished = under_test.record(
+            &definitions,
+            &ObjectiveEvent::TalkedTo("Farmer".to_string()),
+        );
+
+        assert_eq!(vec!["firewood".to_string()], finished);
+        assert_eq!(
+            QuestStatus::Finished,
+            under_test.get("firewood").unwrap().status
+        );
+        assert!(under_test
+            .record(
+                &definitions,
+                &ObjectiveEvent::TalkedTo("Farmer".to_string())
+            )
+            .is_empty());
+    }
+
+    #[test]
+    fn saved_stages_past_the_last_one_finish_the_quest() {
+        let definitions = definitions();
+        let mut under_test = QuestLog {
+            quests: vec![QuestProgress {
+                id: "firewood".to_string(),
+                status: QuestStatus::Active,
+                stage: 5,
+                objectives: vec![1],
+            }],
+        };
+
+        let finished = under_test.record(&definitions, &sticks(1));
+
+        assert_eq!(vec!["firewood".to_string()], finished);
+        assert_eq!(
+            QuestStatus::Finished,
+            under_test.get("firewood").unwrap().status
+        );
+    }
+
+    #[test]
+    fn quests_without_stages_finish_instead_of_panicking() {
+        let mut definitions = definitions();
+        definitions.quests[0].stages.clear();
+        let mut under_test = started_log(&definitions);
+
+        let finished = under_test.record(&definitions, &sticks(1));
+
+        assert_eq!(vec!["firewood".to_string()], finished);
+    }
+
+    #[test]
+    fn quest_log_survives_a_round_trip_through_ron() {
+        let definitions = definitions();
+        let mut under_test = started_log(&definitions);
+        under_test.record(&definitions, &sticks(1));
+
+        let serialized = ron::to_string(&under_test).unwrap();
+
+        assert_eq!(under_test, ron::from_str(&serialized).unwrap());
+    }
+}
@@ -0,0 +1,12 @@
+/// The volumes of the sound channels from 0 to 1, which can be changed while the game runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoundVolumes {
+    pub effects: f32,
+    pub ambient: f32,
+}
+
+/// The ambient track that should be looping, which follows the current level.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AmbientTrack {
+    pub sound: Option<String>,
+}
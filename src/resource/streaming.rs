@@ -0,0 +1,79 @@
+use std::collections::{BTreeSet, HashMap};
+
+use bevy::tasks::Task;
+use futures_lite::future;
+
+use crate::data::level::LevelDefinition;
+use crate::types::chunk::ChunkCoordinate;
+
+/// The chunks of the current level that are spawned, and those whose files are still being read.
+#[derive(Default)]
+pub struct LoadedChunks {
+    loaded: BTreeSet<ChunkCoordinate>,
+    loading: HashMap<ChunkCoordinate, Task<Result<LevelDefinition, String>>>,
+}
+
+impl LoadedChunks {
+    pub fn is_loaded(&self, chunk: ChunkCoordinate) -> bool {
+        self.loaded.contains(&chunk)
+    }
+
+    pub fn is_requested(&self, chunk: ChunkCoordinate) -> bool {
+        self.is_loaded(chunk) || self.loading.contains_key(&chunk)
+    }
+
+    pub fn loaded(&self) -> impl Iterator<Item = ChunkCoordinate> + '_ {
+        self.loaded.iter().copied()
+    }
+
+    pub fn mark_loaded(&mut self, chunk: ChunkCoordinate) {
+        self.loading.remove(&chunk);
+        self.loaded.insert(chunk);
+    }
+
+    pub fn start_loading(
+        &mut self,
+        chunk: ChunkCoordinate,
+        task: Task<Result<LevelDefinition, String>>,
+    ) {
+        self.loading.insert(chunk, task);
+    }
+
+    /// Removes the chunks whose files were read from the loading ones and returns their contents.
+    pub fn take_finished(&mut self) -> Vec<(ChunkCoordinate, Result<LevelDefinition, String>)> {
+        let finished: Vec<(ChunkCoordinate, Result<LevelDefinition, String>)> = self
+            .loading
+            .iter_mut()
+            .filter_map(|(chunk, task)| {
+                future::block_on(future::poll_once(task)).map(|result| (*chunk, result))
+            })
+            .collect();
+
+        for (chunk, _) in finished.iter() {
+            self.loading.remove(chunk);
+        }
+
+        finished
+    }
+
+    /// Forgets the chunk. Dropping a task that is still loading cancels it.
+    pub fn unload(&mut self, chunk: ChunkCoordinate) {
+        self.loading.remove(&chunk);
+        self.loaded.remove(&chunk);
+    }
+
+    /// Forgets all chunks, e.g. after switching to another level.
+    pub fn clear(&mut self) {
+        self.loading.clear();
+        self.loaded.clear();
+    }
+
+    /// Every chunk that is loaded or loading.
+    pub fn requested(&self) -> Vec<ChunkCoordinate> {
+        self.loaded
+            .iter()
+            .chain(self.loading.keys())
+            .copied()
+            .collect()
+    }
+}
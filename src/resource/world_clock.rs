@@ -0,0 +1,160 @@
+use bevy::prelude::Color;
+use serde::{Deserialize, Serialize};
+
+const SECONDS_PER_HOUR: f32 = 3600.0;
+const SECONDS_PER_DAY: f32 = 24.0 * SECONDS_PER_HOUR;
+
+/// The tint of the world at certain hours, in between the colours are blended.
+const TINTS: [(f32, [f32; 3]); 8] = [
+    (0.0, NIGHT),
+    (5.0, NIGHT),
+    (6.5, DAWN),
+    (8.0, DAY),
+    (18.0, DAY),
+    (19.5, DUSK),
+    (21.0, NIGHT),
+    (24.0, NIGHT),
+];
+const NIGHT: [f32; 3] = [0.25, 0.3, 0.55];
+const DAWN: [f32; 3] = [0.95, 0.7, 0.6];
+const DAY: [f32; 3] = [1.0, 1.0, 1.0];
+const DUSK: [f32; 3] = [0.95, 0.6, 0.45];
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TimeOfDay {
+    /// from 5 to 8 o'clock
+    Dawn,
+    /// from 8 to 18 o'clock
+    Day,
+    /// from 18 to 21 o'clock
+    Dusk,
+    /// from 21 to 5 o'clock
+    Night,
+}
+
+/// The time in the game world, which passes much faster than real time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldClock {
+    day: u32,
+    seconds_of_day: f32,
+}
+
+impl WorldClock {
+    pub fn new(day: u32, hour: f32) -> WorldClock {
+        let mut clock = WorldClock {
+            day,
+            seconds_of_day: 0.0,
+        };
+        clock.advance(hour * SECONDS_PER_HOUR);
+        clock
+    }
+
+    /// Advances the clock by the given number of world seconds.
+    pub fn advance(&mut self, seconds: f32) {
+        let seconds = self.seconds_of_day + seconds;
+        self.day += (seconds / SECONDS_PER_DAY).floor() as u32;
+        self.seconds_of_day = seconds.rem_euclid(SECONDS_PER_DAY);
+    }
+
+    /// The number of days passed since the game started.
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// The hour of the day including the fraction of the hour, from 0 to 24.
+    pub fn hour(&self) -> f32 {
+        self.seconds_of_day / SECONDS_PER_HOUR
+    }
+
+    pub fn time_of_day(&self) -> TimeOfDay {
+        match self.hour() {
+            hour if hour < 5.0 => TimeOfDay::Night,
+            hour if hour < 8.0 => TimeOfDay::Dawn,
+            hour if hour < 18.0 => TimeOfDay::Day,
+            hour if hour < 21.0 => TimeOfDay::Dusk,
+            _ => TimeOfDay::Night,
+        }
+    }
+
+    /// The colour sprites are multiplied with at the current time.
+    pub fn tint(&self) -> Color {
+        let hour = self.hour();
+        let next = TINTS
+            .iter()
+            .position(|(tint_hour, _)| *tint_hour > hour)
+            .unwrap_or(TINTS.len() - 1);
+        let (start_hour, start) = TINTS[next - 1];
+        let (end_hour, end) = TINTS[next];
+
+        let t = (hour - start_hour) / (end_hour - start_hour);
+        let blend = |channel: usize| start[channel] + (end[channel] - start[channel]) * t;
+        Color::rgb(blend(0), blend(1), blend(2))
+    }
+}
+
+impl Default for WorldClock {
+    fn default() -> Self {
+        WorldClock::new(0, 8.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Color;
+    use quickcheck_macros::quickcheck;
+    use rstest::*;
+
+    use crate::resource::world_clock::{TimeOfDay, WorldClock};
+
+    #[rstest]
+    #[case(0.0, TimeOfDay::Night)]
+    #[case(4.99, TimeOfDay::Night)]
+    #[case(5.0, TimeOfDay::Dawn)]
+    #[case(8.0, TimeOfDay::Day)]
+    #[case(17.5, TimeOfDay::Day)]
+    #[case(18.0, TimeOfDay::Dusk)]
+    #[case(21.0, TimeOfDay::Night)]
+    #[case(23.9, TimeOfDay::Night)]
+    fn time_of_day_by_hour(#[case] hour: f32, #[case] expected: TimeOfDay) {
+        assert_eq!(expected, WorldClock::new(0, hour).time_of_day());
+    }
+
+    #[test]
+    fn advancing_past_midnight_starts_the_next_day() {
+        let mut under_test = WorldClock::new(0, 23.0);
+
+        under_test.advance(2.0 * 3600.0);
+
+        assert_eq!(1, under_test.day());
+        assert!((under_test.hour() - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn days_are_bright_and_nights_are_dark() {
+        assert_eq!(Color::rgb(1.0, 1.0, 1.0), WorldClock::new(0, 12.0).tint());
+
+        let night = WorldClock::new(0, 0.0).tint();
+        assert!(night.r() < 0.5 && night.g() < 0.5);
+    }
+
+    #[quickcheck]
+    fn tint_changes_smoothly(minutes: u16) {
+        let clock = WorldClock::new(0, (minutes % 1440) as f32 / 60.0);
+        let mut a_minute_later = clock.clone();
+        a_minute_later.advance(60.0);
+
+        let (tint, later_tint) = (clock.tint(), a_minute_later.tint());
+        assert!((tint.r() - later_tint.r()).abs() < 0.02);
+        assert!((tint.g() - later_tint.g()).abs() < 0.02);
+        assert!((tint.b() - later_tint.b()).abs() < 0.02);
+    }
+
+    #[test]
+    fn clock_survives_a_round_trip_through_ron() {
+        let under_test = WorldClock::new(3, 19.25);
+
+        let serialized = ron::to_string(&under_test).unwrap();
+
+        assert_eq!(under_test, ron::from_str(&serialized).unwrap());
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Named counters describing the state of the world, e.g. whether a door was opened or how many
+/// wolves were chased away. Flags that were never set are 0, any other value counts as set.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WorldFlags {
+    values: BTreeMap<String, i32>,
+}
+
+impl WorldFlags {
+    pub fn get(&self, name: &str) -> i32 {
+        self.values.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn is_set(&self, name: &str) -> bool {
+        self.get(name) != 0
+    }
+
+    pub fn set(&mut self, name: &str, value: i32) {
+        if value == 0 {
+            self.values.remove(name);
+        } else {
+            self.values.insert(name.to_string(), value);
+        }
+    }
+
+    pub fn add(&mut self, name: &str, amount: i32) {
+        self.set(name, self.get(name).saturating_add(amount));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, i32)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resource::world_flags::WorldFlags;
+
+    #[test]
+    fn unknown_flags_are_not_set() {
+        let under_test = WorldFlags::default();
+
+        assert_eq!(0, under_test.get("gate_opened"));
+        assert!(!under_test.is_set("gate_opened"));
+    }
+
+    #[test]
+    fn counters_can_be_increased_and_reset() {
+        let mut under_test = WorldFlags::default();
+
+        under_test.add("wolves_chased_away", 2);
+        under_test.add("wolves_chased_away", 1);
+        assert_eq!(3, under_test.get("wolves_chased_away"));
+
+        under_test.set("wolves_chased_away", 0);
+        assert_eq!(0, under_test.iter().count());
+    }
+
+    #[test]
+    fn flags_survive_a_round_trip_through_ron() {
+        let mut under_test = WorldFlags::default();
+        under_test.set("gate_opened", 1);
+        under_test.set("apples_eaten", 12);
+
+        let serialized = ron::to_string(&under_test).unwrap();
+
+        assert_eq!(under_test, ron::from_str(&serialized).unwrap());
+    }
+}
@@ -0,0 +1,448 @@
+use bevy::log::error;
+use bevy::prelude::{
+    Entity, EventReader, EventWriter, Query, Res, TextureAtlasSprite, With, World,
+};
+
+use crate::component::animation::Animator;
+use crate::component::fidget::Fidgeting;
+use crate::component::manfred::Manfred;
+use crate::component::network::RemotePlayer;
+use crate::data::animation::AnimationDefinitions;
+use crate::data::load_ron_file;
+use crate::event::animation::AnimationEvent;
+use crate::event::damage::DamageEvent;
+use crate::event::item::ItemPickedUpEvent;
+use crate::plugin::animation::AnimationConfig;
+use crate::resource::console::ConsoleArgs;
+use crate::resource::game_time::GameTime;
+use crate::types::Direction;
+use crate::Velocity;
+
+/// The name of Manfred's animation graph.
+pub const MANFRED_ANIMATIONS: &str = "manfred";
+/// The names of the animation events triggered by the game.
+pub const PICK_UP: &str = "pick_up";
+pub const HURT: &str = "hurt";
+/// Triggered when input cancels a fidget.
+pub const INPUT: &str = "input";
+
+/// The view direction of every row of the sprite atlas, from the top.
+const ROWS: [Direction; 4] = [
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+    Direction::Up,
+];
+
+pub fn atlas_row(direction: Direction) -> u32 {
+    ROWS.iter().position(|row| *row == direction).unwrap_or(0) as u32
+}
+
+/// The view direction a sprite atlas index shows.
+pub fn direction_of_index(index: u32, columns: u32) -> Direction {
+    ROWS.get((index / columns.max(1)) as usize)
+        .copied()
+        .unwrap_or(Direction::Down)
+}
+
+pub fn load_animation_definitions(world: &mut World) {
+    let path = world
+        .get_resource::<AnimationConfig>()
+        .unwrap()
+        .definitions_path
+        .clone();
+
+    let definitions = load_ron_file::<AnimationDefinitions>(&path).unwrap_or_else(|message| {
+        error!("{}", message);
+        AnimationDefinitions::default()
+    });
+    for graph in definitions.graphs.iter() {
+        if let Err(message) = graph.validate() {
+            error!("{}", message);
+        }
+    }
+
+    world.insert_resource(definitions);
+}
+
+/// Advances the animation graphs and shows their frames in the row of the view direction. Frame
+/// events of the reached frames are sent as animation events.
+pub fn animation_system(
+    config: Res<AnimationConfig>,
+    definitions: Res<AnimationDefinitions>,
+    mut animation_events: EventWriter<AnimationEvent>,
+    mut query: Query<(
+        Entity,
+        &mut Animator,
+        &mut TextureAtlasSprite,
+        Option<&Velocity>,
+        Option<&Manfred>,
+        Option<&RemotePlayer>,
+    )>,
+) {
+    for (entity, mut animator, mut atlas_sprite, velocity, manfred, remote) in query.iter_mut() {
+        let graph = match definitions.get(&animator.graph) {
+            Some(graph) => graph,
+            None => continue,
+        };
+
+        let reached = animator.update(graph, velocity.map_or(0, |velocity| velocity.speed()));
+
+        let state = animator.state().and_then(|name| graph.state(name));
+        for frame in reached {
+            for frame_event in state
+                .iter()
+                .flat_map(|state| state.frame_events.iter())
+                .filter(|frame_event| frame_event.frame == frame)
+            {
+                animation_events.send(AnimationEvent {
+                    entity,
+                    name: frame_event.name.clone(),
+                    frame,
+                });
+            }
+        }
+
+        let direction = manfred
+            .map(|manfred| manfred.view_direction)
+            .or_else(|| remote.map(|remote| remote.view_direction))
+            .unwrap_or(Direction::Down);
+        atlas_sprite.index = animator.column(graph) + atlas_row(direction) * config.columns;
+    }
+}
+
+/// Starts the pick up and hurt animations. The events are kept by the animators until the next
+/// animation tick, which does not happen every frame.
+pub fn animation_trigger_system(
+    mut picked_up_events: EventReader<ItemPickedUpEvent>,
+    mut damage_events: EventReader<DamageEvent>,
+    mut animators: Query<&mut Animator>,
+) {
+    let picked_up = picked_up_events.iter().map(|event| (event.picker, PICK_UP));
+    let damaged = damage_events.iter().map(|event| (event.target, HURT));
+
+    for (entity, event) in picked_up.chain(damaged) {
+        if let Ok(mut animator) = animators.get_mut(entity) {
+            animator.trigger(event);
+        }
+    }
+}
+
+/// Starts an idle animation when a character was left alone for a while and the animator is back
+/// in its initial state. Input cancels the idle animation again.
+pub fn fidget_system(
+    config: Res<AnimationConfig>,
+    definitions: Res<AnimationDefinitions>,
+    time: Res<GameTime>,
+    mut query: Query<(&mut Fidgeting, &mut Animator)>,
+) {
+    for (mut fidgeting, mut animator) in query.iter_mut() {
+        let graph = match definitions.get(&animator.graph) {
+            Some(graph) => graph,
+            None => continue,
+        };
+
+        if fidgeting.take_interruption() {
+            animator.trigger(INPUT);
+        }
+
+        let idle = animator.state() == Some(graph.initial.as_str());
+        if let Some(event) =
+            fidgeting.update(time.delta_seconds(), &config.fidget, &graph.fidgets, idle)
+        {
+            animator.trigger(event);
+        }
+    }
+}
+
+/// Triggers an animation event on every Manfred, e.g. `animate attack`.
+pub fn animate_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let event = args.parse::<String>(0, "event")?;
+
+    let mut query = world.query_filtered::<&mut Animator, With<Manfred>>();
+    let mut manfred_found = false;
+    for mut animator in query.iter_mut(world) {
+        animator.trigger(&event);
+        manfred_found = true;
+    }
+
+    match manfred_found {
+        true => Ok(format!("triggered {}", event)),
+        false => Err("there is no Manfred".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{
+        IntoSystem, KeyCode, ParallelSystemDescriptorCoercion, TextureAtlasSprite, Vec2,
+    };
+    use rstest::*;
+
+    use crate::component::animation::Animator;
+    use crate::component::fidget::{FidgetConfig, Fidgeting};
+    use crate::component::manfred::Manfred;
+    use crate::data::animation::{
+        AnimationDefinitions, AnimationGraph, AnimationState, Condition, Fidget, FrameEvent,
+        Transition,
+    };
+    use crate::event::animation::AnimationEvent;
+    use crate::event::damage::{DamageEvent, DamageSource};
+    use crate::event::item::ItemPickedUpEvent;
+    use crate::plugin::animation::AnimationConfig;
+    use crate::plugin::movement::MovementConfig;
+    use crate::resource::console::{execute_console_command, ConsoleCommand, ConsoleCommands};
+    use crate::system::animation::{
+        animate_command, animation_system, animation_trigger_system, atlas_row, direction_of_index,
+        fidget_system, INPUT,
+    };
+    use crate::system::velocity::velocity_control_system;
+    use crate::test_support::test_world::{TestWorld, TestWorldBuilder};
+    use crate::types::Direction;
+    use crate::Velocity;
+
+    fn state(name: &str, frames: Vec<u32>, transitions: Vec<Transition>) -> AnimationState {
+        AnimationState {
+            name: name.to_string(),
+            frames,
+            playback: 1000,
+            reference_speed: None,
+            looping: true,
+            interruptible: true,
+            frame_events: vec![],
+            transitions,
+        }
+    }
+
+    fn definitions() -> AnimationDefinitions {
+        let walk = AnimationState {
+            frame_events: vec![FrameEvent::new(1, "footstep")],
+            ..state(
+                "walk",
+                vec![0, 1, 2, 3],
+                vec![Transition {
+                    to: "idle".to_string(),
+                    when: Condition::SpeedBelow(1),
+                }],
+            )
+        };
+        let pick_up = AnimationState {
+            looping: false,
+            ..state("pick_up", vec![2, 3], vec![])
+        };
+        let yawn = AnimationState {
+            looping: false,
+            ..state(
+                "yawn",
+                vec![3, 3, 3],
+                vec![Transition {
+                    to: "idle".to_string(),
+                    when: Condition::Event(INPUT.to_string()),
+                }],
+            )
+        };
+
+        AnimationDefinitions {
+            graphs: vec![AnimationGraph {
+                name: "manfred".to_string(),
+                initial: "idle".to_string(),
+                states: vec![
+                    state(
+                        "idle",
+                        vec![0],
+                        vec![Transition {
+                            to: "walk".to_string(),
+                            when: Condition::SpeedAtLeast(1),
+                        }],
+                    ),
+                    walk,
+                    pick_up,
+                    yawn,
+                ],
+                any_state: vec![
+                    Transition {
+                        to: "pick_up".to_string(),
+                        when: Condition::Event("pick_up".to_string()),
+                    },
+                    Transition {
+                        to: "yawn".to_string(),
+                        when: Condition::Event("yawn".to_string()),
+                    },
+                ],
+                fidgets: vec![Fidget::new("yawn", 1)],
+            }],
+        }
+    }
+
+    fn world_with_manfred(walking: bool) -> TestWorld {
+        let mut velocity = Velocity::new(10);
+        if walking {
+            velocity.accelerate(Direction::Right);
+        }
+
+        TestWorldBuilder::new()
+            .with_resource(AnimationConfig {
+                columns: 4,
+                ..Default::default()
+            })
+            .with_resource(definitions())
+            .with_event::<AnimationEvent>()
+            .with_event::<ItemPickedUpEvent>()
+            .with_event::<DamageEvent>()
+            .with_system(animation_trigger_system.system().before("animation"))
+            .with_system(animation_system.system().label("animation"))
+            .with_entity(
+                "manfred",
+                (
+                    Manfred {
+                        view_direction: Direction::Left,
+                    },
+                    Animator::new("manfred"),
+                    TextureAtlasSprite::new(0),
+                    velocity,
+                ),
+            )
+            .build()
+    }
+
+    #[test]
+    fn walking_sends_the_event_of_the_reached_frame() {
+        let mut world = world_with_manfred(true);
+        world.run_steps(2);
+        assert!(world.events::<AnimationEvent>().is_empty());
+
+        world.run_step();
+        let events = world.events::<AnimationEvent>();
+        assert_eq!(1, events.len());
+        assert_eq!("footstep", events[0].name);
+        assert_eq!(1, events[0].frame);
+
+        world.run_step();
+        assert!(world.events::<AnimationEvent>().is_empty());
+    }
+
+    #[test]
+    fn standing_still_sends_no_events() {
+        let mut world = world_with_manfred(false);
+
+        world.run_steps(4);
+
+        assert!(world.events::<AnimationEvent>().is_empty());
+    }
+
+    #[rstest]
+    #[case(Direction::Down)]
+    #[case(Direction::Left)]
+    #[case(Direction::Right)]
+    #[case(Direction::Up)]
+    fn every_frame_of_a_row_faces_its_direction(#[case] direction: Direction) {
+        for column in 0..8 {
+            let index = atlas_row(direction) * 8 + column;
+
+            assert_eq!(direction, direction_of_index(index, 8));
+        }
+    }
+
+    #[test]
+    fn the_frame_is_shown_in_the_row_of_the_view_direction() {
+        let mut world = world_with_manfred(true);
+
+        world.run_steps(4);
+
+        assert_eq!(Some("walk"), world.get::<Animator>("manfred").state());
+        assert_eq!(4 + 2, world.get::<TextureAtlasSprite>("manfred").index);
+    }
+
+    #[test]
+    fn picking_something_up_plays_the_pick_up_animation() {
+        let mut world = world_with_manfred(false);
+        world.run_step();
+
+        let picker = world.entity("manfred");
+        world.send_event(ItemPickedUpEvent {
+            picker,
+            item: "apple".to_string(),
+            count: 1,
+        });
+        world.run_step();
+
+        assert_eq!(Some("pick_up"), world.get::<Animator>("manfred").state());
+        assert_eq!(4 + 2, world.get::<TextureAtlasSprite>("manfred").index);
+    }
+
+    #[test]
+    fn damage_without_a_hurt_animation_changes_nothing() {
+        let mut world = world_with_manfred(false);
+        world.run_step();
+
+        let target = world.entity("manfred");
+        world.send_event(DamageEvent {
+            target,
+            amount: 1,
+            source: DamageSource {
+                name: "thorn bush".to_string(),
+                position: Vec2::ZERO,
+            },
+        });
+        world.run_step();
+
+        assert_eq!(Some("idle"), world.get::<Animator>("manfred").state());
+    }
+
+    #[test]
+    fn animate_command_triggers_the_event() {
+        let mut commands = ConsoleCommands::default();
+        commands.register(ConsoleCommand::new("animate", "", "", animate_command));
+        let mut world = world_with_manfred(false);
+        world.world_mut().insert_resource(commands);
+        world.run_step();
+
+        assert_eq!(
+            Ok("triggered pick_up".to_string()),
+            execute_console_command(world.world_mut(), "animate pick_up")
+        );
+        world.run_step();
+
+        assert_eq!(Some("pick_up"), world.get::<Animator>("manfred").state());
+    }
+
+    #[test]
+    fn fidgets_start_when_left_alone_and_stop_on_input() {
+        let mut world = TestWorldBuilder::new()
+            .with_resource(MovementConfig::default())
+            .with_resource(AnimationConfig {
+                fidget: FidgetConfig {
+                    delay: 1.0,
+                    interval: 100.0,
+                    seed: 0,
+                },
+                ..Default::default()
+            })
+            .with_resource(definitions())
+            .with_event::<AnimationEvent>()
+            .with_system(velocity_control_system.system().label("velocity"))
+            .with_system(fidget_system.system().after("velocity").before("animation"))
+            .with_system(animation_system.system().label("animation"))
+            .with_entity(
+                "manfred",
+                (
+                    Manfred::default(),
+                    Animator::new("manfred"),
+                    Fidgeting::new(0),
+                    TextureAtlasSprite::new(0),
+                    Velocity::new(10),
+                ),
+            )
+            .build();
+
+        world.run_for_seconds(0.9);
+        assert_eq!(Some("idle"), world.get::<Animator>("manfred").state());
+
+        world.run_for_seconds(0.2);
+        assert_eq!(Some("yawn"), world.get::<Animator>("manfred").state());
+
+        world.press(KeyCode::D);
+        world.run_step();
+        assert_eq!(Some("idle"), world.get::<Animator>("manfred").state());
+    }
+}
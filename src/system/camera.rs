@@ -0,0 +1,124 @@
+use bevy::prelude::{
+    Commands, OrthographicCameraBundle, OrthographicProjection, Query, Res, Transform,
+    UiCameraBundle, Vec2, Windows, With, Without,
+};
+
+use crate::component::camera::MainCamera;
+use crate::component::manfred::Manfred;
+use crate::plugin::camera::CameraConfig;
+
+/// Used when there is no window, e.g. in tests.
+const DEFAULT_VIEW_SIZE: (f32, f32) = (1280.0, 720.0);
+
+pub fn add_camera(mut commands: Commands, config: Res<CameraConfig>) {
+    let mut camera = OrthographicCameraBundle::new_2d();
+    camera.orthographic_projection.scale = config.scale;
+
+    commands.spawn_bundle(camera).insert(MainCamera);
+    commands.spawn_bundle(UiCameraBundle::default());
+}
+
+pub fn camera_follow_system(
+    config: Res<CameraConfig>,
+    windows: Option<Res<Windows>>,
+    players: Query<&Transform, (With<Manfred>, Without<MainCamera>)>,
+    mut cameras: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    let view_size = windows
+        .as_ref()
+        .and_then(|windows| windows.get_primary())
+        .map_or(Vec2::from(DEFAULT_VIEW_SIZE), |window| {
+            Vec2::new(window.width(), window.height())
+        });
+    let positions: Vec<Vec2> = players
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .collect();
+
+    if let Some((center, scale)) = frame_players(&positions, view_size, &config) {
+        for (mut transform, mut projection) in cameras.iter_mut() {
+            transform.translation.x = center.x;
+            transform.translation.y = center.y;
+            if projection.scale != scale {
+                projection.scale = scale;
+            }
+        }
+    }
+}
+
+/// Returns the center and the scale of the camera that shows all players with the margin around
+/// them, or nothing without players. The scale stays within the limits of the config, so players
+/// far apart may leave the screen.
+pub fn frame_players(
+    positions: &[Vec2],
+    view_size: Vec2,
+    config: &CameraConfig,
+) -> Option<(Vec2, f32)> {
+    let first = *positions.first()?;
+    let (min, max) = positions
+        .iter()
+        .fold((first, first), |(min, max), position| {
+            (min.min(*position), max.max(*position))
+        });
+
+    let needed = max - min + Vec2::splat(2.0 * config.margin);
+    let scale = (needed.x / view_size.x)
+        .max(needed.y / view_size.y)
+        .clamp(config.scale, config.max_scale);
+
+    Some(((min + max) / 2.0, scale))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Vec2;
+    use rstest::*;
+
+    use crate::plugin::camera::CameraConfig;
+    use crate::system::camera::frame_players;
+
+    fn view_size() -> Vec2 {
+        Vec2::new(800.0, 600.0)
+    }
+
+    #[test]
+    fn single_player_is_centered_without_zooming() {
+        let config = CameraConfig::default();
+
+        let (center, scale) =
+            frame_players(&[Vec2::new(50.0, -20.0)], view_size(), &config).unwrap();
+
+        assert_eq!(Vec2::new(50.0, -20.0), center);
+        assert_eq!(config.scale, scale);
+    }
+
+    #[rstest]
+    #[case(400.0, 1.0)]
+    #[case(1100.0, 1.75)]
+    #[case(3000.0, 2.0)]
+    fn camera_zooms_out_as_players_separate(#[case] distance: f32, #[case] expected_scale: f32) {
+        let config = CameraConfig {
+            scale: 1.0,
+            max_scale: 2.0,
+            margin: 150.0,
+        };
+
+        let (center, scale) = frame_players(
+            &[Vec2::new(0.0, 0.0), Vec2::new(distance, 0.0)],
+            view_size(),
+            &config,
+        )
+        .unwrap();
+
+        assert_eq!(Vec2::new(distance / 2.0, 0.0), center);
+        assert_eq!(expected_scale, scale);
+    }
+
+    #[test]
+    fn nothing_to_frame_without_players() {
+        assert_eq!(
+            None,
+            frame_players(&[], view_size(), &CameraConfig::default())
+        );
+    }
+}
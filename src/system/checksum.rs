@@ -0,0 +1,244 @@
+use bevy::prelude::{Query, ResMut, World};
+
+use crate::component::elevation::Elevation;
+use crate::component::manfred::Manfred;
+use crate::component::Position;
+use crate::resource::checksum::ChecksumLog;
+use crate::resource::console::ConsoleArgs;
+use crate::types::checksum::Checksum;
+use crate::types::Direction;
+use crate::Velocity;
+
+type MovementState = (
+    i32,
+    i32,
+    Option<(i32, i32, i32)>,
+    Option<Direction>,
+    Option<(i32, i32)>,
+);
+
+/// Records a checksum over everything movement depends on, once per tick after the positions
+/// were updated.
+pub fn world_checksum_system(
+    mut log: ResMut<ChecksumLog>,
+    query: Query<(
+        &Position,
+        Option<&Velocity>,
+        Option<&Manfred>,
+        Option<&Elevation>,
+    )>,
+) {
+    let states = query
+        .iter()
+        .map(|(position, velocity, manfred, elevation)| {
+            movement_state(position, velocity, manfred, elevation)
+        })
+        .collect();
+
+    log.record(world_checksum(states));
+}
+
+fn movement_state(
+    position: &Position,
+    velocity: Option<&Velocity>,
+    manfred: Option<&Manfred>,
+    elevation: Option<&Elevation>,
+) -> MovementState {
+    (
+        position.x,
+        position.y,
+        velocity.map(|velocity| (velocity.x_steps(), velocity.y_steps(), velocity.max_speed())),
+        manfred.map(|manfred| manfred.view_direction),
+        elevation.map(|elevation| (elevation.height(), elevation.vertical_speed())),
+    )
+}
+
+/// Entity ids depend on what was spawned and despawned before, so the states are sorted instead
+/// of hashed in query order.
+fn world_checksum(mut states: Vec<MovementState>) -> u64 {
+    states.sort_by_key(|(x, y, velocity, direction, elevation)| {
+        (
+            *x,
+            *y,
+            *velocity,
+            direction.map(|direction| direction as u8),
+            *elevation,
+        )
+    });
+
+    let mut checksum = Checksum::new();
+    for (x, y, velocity, direction, elevation) in states {
+        checksum.add_i32(x);
+        checksum.add_i32(y);
+        match velocity {
+            Some((x_steps, y_steps, max_speed)) => {
+                checksum.add_bytes(&[1]);
+                checksum.add_i32(x_steps);
+                checksum.add_i32(y_steps);
+                checksum.add_i32(max_speed);
+            }
+            None => checksum.add_bytes(&[0]),
+        }
+        match direction {
+            Some(direction) => {
+                checksum.add_bytes(&[1]);
+                checksum.add_direction(direction);
+            }
+            None => checksum.add_bytes(&[0]),
+        }
+        match elevation {
+            Some((height, vertical_speed)) => {
+                checksum.add_bytes(&[1]);
+                checksum.add_i32(height);
+                checksum.add_i32(vertical_speed);
+            }
+            None => checksum.add_bytes(&[0]),
+        }
+    }
+    checksum.value()
+}
+
+pub fn checksum_command(world: &mut World, _args: &ConsoleArgs) -> Result<String, String> {
+    world
+        .get_resource::<ChecksumLog>()
+        .and_then(ChecksumLog::latest)
+        .map(|(tick, checksum)| format!("tick {}: {:016x}", tick, checksum))
+        .ok_or_else(|| "no checksum was recorded yet".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{IntoSystem, KeyCode, ParallelSystemDescriptorCoercion, Transform};
+
+    use crate::component::elevation::Elevation;
+    use crate::component::manfred::Manfred;
+    use crate::component::Position;
+    use crate::plugin::movement::MovementConfig;
+    use crate::resource::checksum::ChecksumLog;
+    use crate::resource::console::{execute_console_command, ConsoleCommand, ConsoleCommands};
+    use crate::system::checksum::{checksum_command, world_checksum_system};
+    use crate::system::position::move_positions_system;
+    use crate::system::velocity::velocity_control_system;
+    use crate::test_support::test_world::{TestWorld, TestWorldBuilder};
+    use crate::Velocity;
+
+    /// Manfreds at the given positions, named "first" and "second".
+    fn world_with_manfreds(positions: &[(i32, i32)]) -> TestWorld {
+        let mut builder = TestWorldBuilder::new()
+            .with_resource(MovementConfig::default())
+            .with_resource(ChecksumLog::default())
+            .with_system(velocity_control_system.system().label("velocity"))
+            .with_system(
+                move_positions_system
+                    .system()
+                    .label("update_position")
+                    .after("velocity"),
+            )
+            .with_system(world_checksum_system.system().after("update_position"));
+        for (name, (x, y)) in ["first", "second"].iter().zip(positions) {
+            builder = builder.with_entity(
+                *name,
+                (
+                    Manfred::default(),
+                    Velocity::new(5),
+                    Position::new(*x, *y),
+                    Transform::default(),
+                    Elevation::default(),
+                ),
+            );
+        }
+        builder.build()
+    }
+
+    fn walk(world: &mut TestWorld) {
+        world.press(KeyCode::D);
+        world.press(KeyCode::W);
+        world.run_steps(20);
+        world.release(KeyCode::W);
+        world.run_steps(20);
+    }
+
+    #[test]
+    fn identical_runs_record_identical_checksums() {
+        let mut first = world_with_manfreds(&[(0, 0)]);
+        let mut second = world_with_manfreds(&[(0, 0)]);
+
+        walk(&mut first);
+        walk(&mut second);
+
+        let first = first.resource::<ChecksumLog>();
+        assert_eq!(Some(39), first.latest().map(|(tick, _)| tick));
+        assert_eq!(
+            None,
+            first.first_difference(second.resource::<ChecksumLog>())
+        );
+    }
+
+    #[test]
+    fn diverging_runs_are_detected_in_the_tick_they_diverge() {
+        let mut first = world_with_manfreds(&[(0, 0)]);
+        let mut second = world_with_manfreds(&[(0, 0)]);
+
+        first.run_steps(5);
+        second.run_steps(4);
+        second.get_mut::<Position>("first").x += 1;
+        second.run_step();
+
+        assert_eq!(
+            Some(4),
+            first
+                .resource::<ChecksumLog>()
+                .first_difference(second.resource::<ChecksumLog>())
+        );
+    }
+
+    #[test]
+    fn diverging_jumps_are_detected() {
+        let mut first = world_with_manfreds(&[(0, 0)]);
+        let mut second = world_with_manfreds(&[(0, 0)]);
+
+        first.run_steps(5);
+        second.run_steps(4);
+        second.get_mut::<Elevation>("first").launch(3);
+        second.run_step();
+
+        assert_eq!(
+            Some(4),
+            first
+                .resource::<ChecksumLog>()
+                .first_difference(second.resource::<ChecksumLog>())
+        );
+    }
+
+    #[test]
+    fn checksum_does_not_depend_on_spawn_order() {
+        let mut first = world_with_manfreds(&[(0, 0), (100, 50)]);
+        let mut second = world_with_manfreds(&[(100, 50), (0, 0)]);
+
+        first.run_step();
+        second.run_step();
+
+        assert_eq!(
+            first.resource::<ChecksumLog>().latest(),
+            second.resource::<ChecksumLog>().latest()
+        );
+    }
+
+    #[test]
+    fn checksum_command_shows_the_latest_checksum() {
+        let mut commands = ConsoleCommands::default();
+        commands.register(ConsoleCommand::new("checksum", "", "", checksum_command));
+        let mut world = world_with_manfreds(&[(0, 0)]);
+        world.world_mut().insert_resource(commands);
+
+        assert!(execute_console_command(world.world_mut(), "checksum").is_err());
+
+        world.run_steps(3);
+        let (_, checksum) = world.resource::<ChecksumLog>().latest().unwrap();
+
+        assert_eq!(
+            Ok(format!("tick 2: {:016x}", checksum)),
+            execute_console_command(world.world_mut(), "checksum")
+        );
+    }
+}
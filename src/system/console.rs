@@ -0,0 +1,282 @@
+use std::fmt::Debug;
+
+use bevy::ecs::component::Component;
+use bevy::prelude::{
+    AlignItems, AssetServer, Assets, BuildChildren, Color, ColorMaterial, Commands, Entity,
+    EventReader, FlexDirection, Input, KeyCode, NodeBundle, PositionType, Query, Rect, Res, ResMut,
+    Size, Style, Text, TextBundle, TextStyle, Transform, Val, Visible, With, World,
+};
+use bevy::window::ReceivedCharacter;
+
+use crate::component::collider::Collider;
+use crate::component::console::{ConsolePanel, ConsoleText};
+use crate::component::elevation::Elevation;
+use crate::component::manfred::Manfred;
+use crate::component::obstacle::Obstacle;
+use crate::component::Position;
+use crate::plugin::console::ConsoleConfig;
+use crate::resource::console::{
+    execute_console_command, ConsoleArgs, ConsoleCommand, ConsoleCommands, ConsoleState,
+};
+use crate::Velocity;
+
+pub fn add_console(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(40.0)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(0.0),
+                    left: Val::Px(0.0),
+                    ..Default::default()
+                },
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::FlexStart,
+                ..Default::default()
+            },
+            material: materials.add(Color::rgba(0.0, 0.0, 0.0, 0.8).into()),
+            visible: Visible {
+                is_visible: false,
+                is_transparent: true,
+            },
+            ..Default::default()
+        })
+        .insert(ConsolePanel)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        "",
+                        TextStyle {
+                            font: asset_server.load("fonts/DejaVuSansMono.ttf"),
+                            font_size: 16.0,
+                            color: Color::WHITE,
+                        },
+                        Default::default(),
+                    ),
+                    ..Default::default()
+                })
+                .insert(ConsoleText);
+        });
+}
+
+pub fn console_input_system(
+    config: Res<ConsoleConfig>,
+    mut state: ResMut<ConsoleState>,
+    mut keyboard_input: ResMut<Input<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+) {
+    let typed: Vec<char> = characters
+        .iter()
+        .map(|character| character.char)
+        .filter(|char| !char.is_control() && *char != '`')
+        .collect();
+
+    if keyboard_input.just_pressed(config.toggle_key) {
+        state.open = !state.open;
+    } else if state.open {
+        state.input.extend(typed);
+
+        if keyboard_input.just_pressed(KeyCode::Back) {
+            state.input.pop();
+        }
+
+        if keyboard_input.just_pressed(KeyCode::Return) {
+            let line = std::mem::take(&mut state.input);
+            state.history.push(format!("> {}", line));
+            state.submitted.push(line);
+        }
+
+        if keyboard_input.just_pressed(KeyCode::Escape) {
+            state.open = false;
+        }
+    }
+
+    if state.open {
+        // nothing else should react to what is typed into the console
+        let pressed: Vec<KeyCode> = keyboard_input.get_pressed().cloned().collect();
+        for key in pressed {
+            keyboard_input.reset(key);
+        }
+    }
+}
+
+pub fn console_execution_system(world: &mut World) {
+    let submitted = match world.get_resource_mut::<ConsoleState>() {
+        Some(mut state) => std::mem::take(&mut state.submitted),
+        None => return,
+    };
+
+    for line in submitted {
+        let output = match execute_console_command(world, &line) {
+            Ok(output) => output,
+            Err(error) => error,
+        };
+
+        let mut state = world.get_resource_mut::<ConsoleState>().unwrap();
+        state.history.extend(
+            output
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(String::from),
+        );
+    }
+}
+
+pub fn console_text_system(
+    config: Res<ConsoleConfig>,
+    state: Res<ConsoleState>,
+    mut panels: Query<&mut Visible, With<ConsolePanel>>,
+    mut texts: Query<&mut Text, With<ConsoleText>>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+
+    for mut visible in panels.iter_mut() {
+        visible.is_visible = state.open;
+    }
+
+    let value = if state.open {
+        let first_line = state.history.len().saturating_sub(config.visible_lines);
+        let mut lines = state.history[first_line..].to_vec();
+        lines.push(format!("> {}_", state.input));
+        lines.join("\n")
+    } else {
+        String::new()
+    };
+
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+}
+
+pub fn help_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let commands = world
+        .get_resource::<ConsoleCommands>()
+        .ok_or("there are no commands")?;
+
+    match args.get(0) {
+        Some(name) => commands
+            .get(name)
+            .map(ConsoleCommand::help_line)
+            .ok_or_else(|| format!("unknown command '{}'", name)),
+        None => Ok(commands
+            .iter()
+            .map(ConsoleCommand::help_line)
+            .collect::<Vec<_>>()
+            .join("\n")),
+    }
+}
+
+pub fn inspect_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let entity = match args.parse_optional::<u32>(0, "entity id")? {
+        Some(id) => Entity::new(id),
+        None => world
+            .query_filtered::<Entity, With<Manfred>>()
+            .iter(world)
+            .next()
+            .ok_or("there is no Manfred")?,
+    };
+
+    if world.get_entity(entity).is_none() {
+        return Err(format!("there is no entity {}", entity.id()));
+    }
+
+    let mut lines = vec![format!("{:?}", entity)];
+
+    let entity_ref = world.entity(entity);
+    for component_id in entity_ref.archetype().components() {
+        if let Some(info) = world.components().get_info(component_id) {
+            lines.push(format!("  {}", info.name()));
+        }
+    }
+
+    describe::<Transform>(world, entity, &mut lines);
+    describe::<Position>(world, entity, &mut lines);
+    describe::<Velocity>(world, entity, &mut lines);
+    describe::<Manfred>(world, entity, &mut lines);
+    describe::<Collider>(world, entity, &mut lines);
+    describe::<Elevation>(world, entity, &mut lines);
+    describe::<Obstacle>(world, entity, &mut lines);
+
+    Ok(lines.join("\n"))
+}
+
+fn describe<T: Component + Debug>(world: &World, entity: Entity, lines: &mut Vec<String>) {
+    if let Some(component) = world.get::<T>(entity) {
+        lines.push(format!("  {:?}", component));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::manfred::Manfred;
+    use crate::resource::console::{execute_console_command, ConsoleCommand, ConsoleCommands};
+    use crate::system::console::{help_command, inspect_command};
+    use crate::test_support::test_world::TestWorldBuilder;
+    use crate::Velocity;
+
+    fn console_commands() -> ConsoleCommands {
+        let mut commands = ConsoleCommands::default();
+        commands.register(ConsoleCommand::new(
+            "help",
+            "[command]",
+            "helps",
+            help_command,
+        ));
+        commands.register(ConsoleCommand::new(
+            "inspect",
+            "[entity id]",
+            "inspects",
+            inspect_command,
+        ));
+        commands
+    }
+
+    #[test]
+    fn help_lists_all_commands() {
+        let mut world = TestWorldBuilder::new()
+            .with_resource(console_commands())
+            .build();
+
+        let output = execute_console_command(world.world_mut(), "help").unwrap();
+
+        assert_eq!(
+            "help [command] - helps\ninspect [entity id] - inspects",
+            output
+        );
+    }
+
+    #[test]
+    fn inspect_prints_manfreds_components() {
+        let mut world = TestWorldBuilder::new()
+            .with_resource(console_commands())
+            .with_entity("manfred", (Manfred::default(), Velocity::new(5)))
+            .build();
+
+        let output = execute_console_command(world.world_mut(), "inspect").unwrap();
+
+        assert!(output.contains("view_direction: Down"), "{}", output);
+        assert!(output.contains("max_speed: 5"), "{}", output);
+    }
+
+    #[test]
+    fn inspect_of_unknown_entity_fails() {
+        let mut world = TestWorldBuilder::new()
+            .with_resource(console_commands())
+            .build();
+
+        let result = execute_console_command(world.world_mut(), "inspect 42");
+
+        assert_eq!(
+            Err("there is no entity 42\nusage: inspect [entity id]".to_string()),
+            result
+        );
+    }
+}
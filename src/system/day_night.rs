@@ -0,0 +1,173 @@
+use bevy::prelude::{
+    Assets, Color, ColorMaterial, EventWriter, Handle, Query, Res, ResMut, Sprite,
+    TextureAtlasSprite, With, Without, World,
+};
+
+use crate::component::day_night::{BaseColor, Untinted};
+use crate::event::world_clock::TimeOfDayChangedEvent;
+use crate::plugin::day_night::DayNightConfig;
+use crate::resource::console::ConsoleArgs;
+use crate::resource::day_night::TintedMaterials;
+use crate::resource::game_time::GameTime;
+use crate::resource::world_clock::WorldClock;
+
+pub fn world_clock_system(
+    config: Res<DayNightConfig>,
+    game_time: Res<GameTime>,
+    mut clock: ResMut<WorldClock>,
+    mut time_of_day_events: EventWriter<TimeOfDayChangedEvent>,
+) {
+    let time_of_day = clock.time_of_day();
+
+    clock.advance(game_time.delta_seconds() * config.world_seconds_per_second);
+
+    if clock.time_of_day() != time_of_day {
+        time_of_day_events.send(TimeOfDayChangedEvent {
+            time_of_day: clock.time_of_day(),
+            day: clock.day(),
+        });
+    }
+}
+
+/// Multiplies the colours of all sprite materials and texture atlas sprites with the tint of the
+/// current time of day.
+pub fn tint_system(
+    clock: Res<WorldClock>,
+    mut tinted: ResMut<TintedMaterials>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    sprites: Query<&Handle<ColorMaterial>, (With<Sprite>, Without<Untinted>)>,
+    mut atlas_sprites: Query<(&mut TextureAtlasSprite, Option<&BaseColor>), Without<Untinted>>,
+) {
+    let tint = clock.tint();
+    let tint_changed = tinted.tint != Some(tint);
+
+    let mut new_materials = vec![];
+    for handle in sprites.iter() {
+        if !tinted.base_colors.contains_key(&handle.id) {
+            if let Some(material) = materials.get(handle) {
+                tinted.base_colors.insert(handle.id, material.color);
+                new_materials.push(handle.id);
+            }
+        }
+    }
+
+    let to_tint = match tint_changed {
+        true => tinted.base_colors.keys().copied().collect(),
+        false => new_materials,
+    };
+    for id in to_tint {
+        if let Some(material) = materials.get_mut(id) {
+            material.color = multiply(tinted.base_colors[&id], tint);
+        }
+    }
+
+    for (mut sprite, base_color) in atlas_sprites.iter_mut() {
+        let color = multiply(base_color.map_or(Color::WHITE, |base| base.0), tint);
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+
+    // forget materials that were removed, e.g. with the objects of the last level
+    if tint_changed {
+        tinted
+            .base_colors
+            .retain(|id, _| materials.get(*id).is_some());
+        tinted.tint = Some(tint);
+    }
+}
+
+fn multiply(color: Color, tint: Color) -> Color {
+    Color::rgba(
+        color.r() * tint.r(),
+        color.g() * tint.g(),
+        color.b() * tint.b(),
+        color.a(),
+    )
+}
+
+pub fn time_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let mut clock = world
+        .get_resource_mut::<WorldClock>()
+        .ok_or("there is no world clock")?;
+
+    if let Some(hour) = args.parse_optional::<f32>(0, "hour")? {
+        if !(0.0..24.0).contains(&hour) {
+            return Err(format!("the hour must be from 0 to 24, not {}", hour));
+        }
+        *clock = WorldClock::new(clock.day(), hour);
+    }
+
+    Ok(describe_clock(&clock))
+}
+
+fn describe_clock(clock: &WorldClock) -> String {
+    let minutes = (clock.hour() * 60.0) as u32;
+    format!(
+        "day {}, {:02}:{:02} ({:?})",
+        clock.day(),
+        minutes / 60,
+        minutes % 60,
+        clock.time_of_day()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::IntoSystem;
+
+    use crate::event::world_clock::TimeOfDayChangedEvent;
+    use crate::plugin::day_night::DayNightConfig;
+    use crate::resource::console::ConsoleArgs;
+    use crate::resource::world_clock::{TimeOfDay, WorldClock};
+    use crate::system::day_night::{describe_clock, world_clock_system};
+    use crate::test_support::test_world::{TestWorld, TestWorldBuilder};
+
+    /// One real second is one world hour.
+    fn world_at(hour: f32) -> TestWorld {
+        TestWorldBuilder::new()
+            .with_resource(DayNightConfig {
+                world_seconds_per_second: 3600.0,
+                ..Default::default()
+            })
+            .with_resource(WorldClock::new(0, hour))
+            .with_event::<TimeOfDayChangedEvent>()
+            .with_system(world_clock_system.system())
+            .with_frame_duration(0.5)
+            .build()
+    }
+
+    #[test]
+    fn clock_is_described_with_hours_and_minutes() {
+        assert_eq!(
+            "day 2, 19:30 (Dusk)",
+            describe_clock(&WorldClock::new(2, 19.5))
+        );
+    }
+
+    #[test]
+    fn clock_advances_with_game_time() {
+        let mut world = world_at(8.0);
+
+        world.run_steps(4);
+
+        assert_eq!(10.0, world.resource::<WorldClock>().hour());
+    }
+
+    #[test]
+    fn changing_the_time_of_day_is_announced_once() {
+        let mut world = world_at(17.0);
+
+        world.run_steps(2);
+        assert_eq!(
+            vec![TimeOfDayChangedEvent {
+                time_of_day: TimeOfDay::Dusk,
+                day: 0,
+            }],
+            world.events::<TimeOfDayChangedEvent>()
+        );
+
+        world.run_step();
+        assert!(world.events::<TimeOfDayChangedEvent>().is_empty());
+    }
+}
@@ -0,0 +1,314 @@
+use bevy::diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::{
+    AssetServer, Assets, Color, ColorMaterial, Commands, Entity, Handle, Input, KeyCode, Quat,
+    Query, Rect, Res, ResMut, Sprite, SpriteBundle, Style, Text, TextBundle, TextStyle, Transform,
+    Val, Vec2, Vec3, Visible, With, Without, World,
+};
+use bevy::ui::PositionType;
+
+use crate::component::collider::Collider;
+use crate::component::day_night::Untinted;
+use crate::component::debug_overlay::{DebugShape, DebugText};
+use crate::component::manfred::Manfred;
+use crate::component::trigger::TriggerZone;
+use crate::plugin::debug_overlay::DebugOverlayConfig;
+use crate::resource::console::ConsoleArgs;
+use crate::resource::debug_overlay::{DebugOverlay, DebugOverlayMaterials};
+use crate::resource::game_time::GameTime;
+use crate::Velocity;
+
+const LINE_WIDTH: f32 = 2.0;
+const FACING_ARROW_LENGTH: f32 = 30.0;
+// in front of everything computed by FromXAndY, but still behind the camera
+const DEBUG_Z: f32 = 900.0;
+
+pub fn add_debug_overlay(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.insert_resource(DebugOverlayMaterials {
+        collider: materials.add(Color::rgb(1.0, 0.2, 0.2).into()),
+        velocity: materials.add(Color::rgb(0.2, 1.0, 0.2).into()),
+        facing: materials.add(Color::rgb(0.2, 0.4, 1.0).into()),
+        trigger: materials.add(Color::rgb(1.0, 0.9, 0.2).into()),
+    });
+
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(5.0),
+                    left: Val::Px(5.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/DejaVuSansMono.ttf"),
+                    font_size: 14.0,
+                    color: Color::WHITE,
+                },
+                Default::default(),
+            ),
+            ..Default::default()
+        })
+        .insert(DebugText);
+}
+
+pub fn toggle_debug_overlay_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    config: Res<DebugOverlayConfig>,
+    mut overlay: ResMut<DebugOverlay>,
+) {
+    if keyboard_input.just_pressed(config.toggle_key) {
+        overlay.visible = !overlay.visible;
+    }
+}
+
+pub fn debug_overlay_command(world: &mut World, _: &ConsoleArgs) -> Result<String, String> {
+    let mut overlay = world
+        .get_resource_mut::<DebugOverlay>()
+        .ok_or("the debug overlay is not available")?;
+    overlay.visible = !overlay.visible;
+
+    match overlay.visible {
+        true => Ok("debug overlay shown".to_string()),
+        false => Ok("debug overlay hidden".to_string()),
+    }
+}
+
+/// Moves the line sprites of the previous frame onto the current shapes, spawns more when they are
+/// not enough and hides the ones left over.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn debug_shapes_system(
+    mut commands: Commands,
+    overlay: Res<DebugOverlay>,
+    config: Res<DebugOverlayConfig>,
+    materials: Res<DebugOverlayMaterials>,
+    mut shapes: Query<
+        (
+            &mut Sprite,
+            &mut Transform,
+            &mut Handle<ColorMaterial>,
+            &mut Visible,
+        ),
+        With<DebugShape>,
+    >,
+    colliders: Query<(&Transform, &Collider), Without<DebugShape>>,
+    trigger_zones: Query<(&Transform, &TriggerZone), Without<DebugShape>>,
+    velocities: Query<(&Transform, &Velocity), Without<DebugShape>>,
+    manfreds: Query<(&Transform, &Manfred), Without<DebugShape>>,
+) {
+    let mut lines = vec![];
+
+    if overlay.visible {
+        for (transform, collider) in colliders.iter() {
+            let (min, max) = collider.bounds(transform.translation.truncate());
+            add_box(&mut lines, &materials.collider, min, max);
+        }
+
+        for (transform, zone) in trigger_zones.iter() {
+            let (min, max) = zone.bounds(transform.translation.truncate());
+            add_box(&mut lines, &materials.trigger, min, max);
+        }
+
+        for (transform, velocity) in velocities.iter() {
+            if velocity.is_moving() {
+                let start = transform.translation.truncate();
+                let end = start + velocity.to_vec2() * config.velocity_scale;
+                lines.push((materials.velocity.clone(), start, end));
+            }
+        }
+
+        for (transform, manfred) in manfreds.iter() {
+            let start = transform.translation.truncate();
+            let end = start + manfred.view_direction.unit_vector() * FACING_ARROW_LENGTH;
+            lines.push((materials.facing.clone(), start, end));
+        }
+    }
+
+    let mut lines = lines.into_iter();
+    for (mut sprite, mut transform, mut material, mut visible) in shapes.iter_mut() {
+        match lines.next() {
+            Some((line_material, start, end)) => {
+                sprite.size = line_size(start, end);
+                *transform = line_transform(start, end);
+                *material = line_material;
+                visible.is_visible = true;
+            }
+            None => visible.is_visible = false,
+        }
+    }
+
+    for (material, start, end) in lines {
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite::new(line_size(start, end)),
+                material,
+                transform: line_transform(start, end),
+                ..Default::default()
+            })
+            .insert(DebugShape)
+            .insert(Untinted);
+    }
+}
+
+fn add_box(
+    lines: &mut Vec<(Handle<ColorMaterial>, Vec2, Vec2)>,
+    material: &Handle<ColorMaterial>,
+    min: Vec2,
+    max: Vec2,
+) {
+    let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+
+    for i in 0..corners.len() {
+        lines.push((
+            material.clone(),
+            corners[i],
+            corners[(i + 1) % corners.len()],
+        ));
+    }
+}
+
+fn line_size(start: Vec2, end: Vec2) -> Vec2 {
+    Vec2::new((end - start).length() + LINE_WIDTH, LINE_WIDTH)
+}
+
+fn line_transform(start: Vec2, end: Vec2) -> Transform {
+    let line = end - start;
+
+    Transform {
+        translation: ((start + end) / 2.0).extend(DEBUG_Z),
+        rotation: Quat::from_rotation_z(line.y.atan2(line.x)),
+        scale: Vec3::ONE,
+    }
+}
+
+pub fn debug_text_system(
+    overlay: Res<DebugOverlay>,
+    diagnostics: Res<Diagnostics>,
+    game_time: Res<GameTime>,
+    entities: Query<(Entity, &Transform, &Velocity, Option<&Manfred>)>,
+    mut texts: Query<&mut Text, With<DebugText>>,
+) {
+    let value = if overlay.visible {
+        let fps = diagnostics
+            .get(FrameTimeDiagnosticsPlugin::FPS)
+            .and_then(|fps| fps.average())
+            .unwrap_or(0.0);
+
+        let mut value = format!("fps: {:.0}  tick: {}\n", fps, game_time.frames());
+        for (entity, transform, velocity, manfred) in entities.iter() {
+            value += &format!(
+                "{:?}  position: ({:.0}, {:.0})  steps: ({}, {})  velocity: ({}, {})",
+                entity,
+                transform.translation.x,
+                transform.translation.y,
+                velocity.x_steps(),
+                velocity.y_steps(),
+                velocity.x(),
+                velocity.y(),
+            );
+            if let Some(manfred) = manfred {
+                value += &format!("  facing: {:?}", manfred.view_direction);
+            }
+            value.push('\n');
+        }
+        value
+    } else {
+        String::new()
+    };
+
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{
+        Handle, IntoSystem, ParallelSystemDescriptorCoercion, Transform, Vec3, Visible, With,
+    };
+
+    use crate::component::collider::Collider;
+    use crate::component::debug_overlay::DebugShape;
+    use crate::plugin::debug_overlay::DebugOverlayConfig;
+    use crate::resource::debug_overlay::{DebugOverlay, DebugOverlayMaterials};
+    use crate::system::debug_overlay::{debug_shapes_system, toggle_debug_overlay_system};
+    use crate::system::position::FromXAndY;
+    use crate::test_support::test_world::{TestWorld, TestWorldBuilder};
+    use crate::Velocity;
+
+    fn world_with_moving_box() -> TestWorld {
+        let mut velocity = Velocity::new(5);
+        velocity.set_movement(5, 0);
+
+        TestWorldBuilder::new()
+            .with_resource(DebugOverlay::default())
+            .with_resource(DebugOverlayConfig::default())
+            .with_resource(DebugOverlayMaterials {
+                collider: Handle::default(),
+                velocity: Handle::default(),
+                facing: Handle::default(),
+                trigger: Handle::default(),
+            })
+            .with_system(toggle_debug_overlay_system.system().label("toggle"))
+            .with_system(debug_shapes_system.system().after("toggle"))
+            .with_entity(
+                "box",
+                (
+                    Collider::new(30.0, 20.0),
+                    velocity,
+                    Transform::from_translation(Vec3::compute_from_x_y(0.0, 0.0)),
+                ),
+            )
+            .build()
+    }
+
+    /// Counts all debug shapes and the visible ones.
+    fn shapes(world: &mut TestWorld) -> (usize, usize) {
+        let world = world.world_mut();
+        let visible = world
+            .query_filtered::<&Visible, With<DebugShape>>()
+            .iter(world)
+            .map(|visible| visible.is_visible)
+            .collect::<Vec<_>>();
+        (
+            visible.len(),
+            visible.iter().filter(|shown| **shown).count(),
+        )
+    }
+
+    #[test]
+    fn toggling_shows_and_hides_the_collider_and_velocity_shapes() {
+        let toggle_key = DebugOverlayConfig::default().toggle_key;
+        let mut world = world_with_moving_box();
+        world.run_step();
+        assert_eq!((0, 0), shapes(&mut world));
+
+        world.press(toggle_key);
+        world.run_step();
+        assert_eq!((5, 5), shapes(&mut world));
+
+        world.release(toggle_key);
+        world.run_step();
+        world.press(toggle_key);
+        world.run_step();
+        assert_eq!((5, 0), shapes(&mut world));
+    }
+
+    #[test]
+    fn shapes_are_reused_instead_of_spawned_every_frame() {
+        let mut world = world_with_moving_box();
+        world.resource_mut::<DebugOverlay>().visible = true;
+        world.run_step();
+
+        world.run_steps(3);
+
+        assert_eq!((5, 5), shapes(&mut world));
+    }
+}
@@ -0,0 +1,156 @@
+use bevy::prelude::{
+    Added, AssetServer, Assets, BuildChildren, ColorMaterial, Commands, Entity, Parent, Query, Res,
+    ResMut, SpriteBundle, Transform, Vec2, Vec3, With,
+};
+
+use crate::component::collider::Collider;
+use crate::component::elevation::{Elevation, Shadow};
+use crate::plugin::movement::MovementConfig;
+
+const SHADOW_TEXTURE: &str = "images/shadow.png";
+/// How far the shadow is behind its parent, further than any equipment layer.
+const SHADOW_DEPTH: f32 = 0.01;
+/// Up to this height, the shadow shrinks to half of its size.
+const SHADOW_SHRINK_HEIGHT: f32 = 100.0;
+
+pub fn elevation_system(config: Res<MovementConfig>, mut query: Query<&mut Elevation>) {
+    for mut elevation in query.iter_mut() {
+        elevation.update(config.jump.gravity);
+    }
+}
+
+pub fn add_shadow_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    query: Query<Entity, Added<Elevation>>,
+) {
+    for entity in query.iter() {
+        let shadow = commands
+            .spawn_bundle(SpriteBundle {
+                material: materials.add(asset_server.load(SHADOW_TEXTURE).into()),
+                ..Default::default()
+            })
+            .insert(Shadow)
+            .id();
+        commands.entity(entity).push_children(&[shadow]);
+    }
+}
+
+/// Keeps every shadow on the ground below the collider of its parent, however high the parent
+/// is.
+pub fn shadow_system(
+    parents: Query<(&Elevation, Option<&Collider>)>,
+    mut shadows: Query<(&Parent, &mut Transform), With<Shadow>>,
+) {
+    for (parent, mut transform) in shadows.iter_mut() {
+        if let Ok((elevation, collider)) = parents.get(parent.0) {
+            let feet = collider.map_or(Vec2::ZERO, |collider| collider.offset);
+            let height = elevation.height() as f32;
+
+            transform.translation = Vec3::new(feet.x, feet.y - height, -SHADOW_DEPTH);
+            transform.scale = Vec3::splat(1.0 - (height / SHADOW_SHRINK_HEIGHT).min(1.0) / 2.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{
+        BuildWorldChildren, IntoSystem, KeyCode, ParallelSystemDescriptorCoercion, Transform, Vec3,
+    };
+
+    use crate::component::collider::Collider;
+    use crate::component::elevation::{Elevation, JumpConfig, Shadow};
+    use crate::component::manfred::Manfred;
+    use crate::component::Position;
+    use crate::plugin::movement::MovementConfig;
+    use crate::system::elevation::{elevation_system, shadow_system};
+    use crate::system::position::move_positions_system;
+    use crate::system::velocity::velocity_control_system;
+    use crate::test_support::test_world::{TestWorld, TestWorldBuilder};
+    use crate::Velocity;
+
+    fn world_with_jumper() -> TestWorld {
+        let mut world = TestWorldBuilder::new()
+            .with_resource(MovementConfig {
+                jump: JumpConfig {
+                    speed: 4,
+                    gravity: 1,
+                },
+                ..Default::default()
+            })
+            .with_system(velocity_control_system.system().label("velocity"))
+            .with_system(
+                elevation_system
+                    .system()
+                    .label("elevation")
+                    .after("velocity"),
+            )
+            .with_system(
+                move_positions_system
+                    .system()
+                    .label("update_position")
+                    .after("elevation"),
+            )
+            .with_system(shadow_system.system().after("update_position"))
+            .with_entity(
+                "manfred",
+                (
+                    Manfred::default(),
+                    Velocity::new(5),
+                    Position::new(0, 0),
+                    Transform::default(),
+                    Collider::new(30.0, 20.0).with_offset(0.0, -30.0),
+                    Elevation::default(),
+                ),
+            )
+            .with_entity("shadow", (Shadow, Transform::default()))
+            .build();
+        let shadow = world.entity("shadow");
+        let manfred = world.entity("manfred");
+        world
+            .world_mut()
+            .entity_mut(manfred)
+            .push_children(&[shadow]);
+        world
+    }
+
+    #[test]
+    fn space_lets_manfred_jump() {
+        let mut world = world_with_jumper();
+
+        world.press(KeyCode::Space);
+        world.run_step();
+        world.release(KeyCode::Space);
+
+        let mut heights = vec![world.get::<Elevation>("manfred").height()];
+        while !world.get::<Elevation>("manfred").is_grounded() {
+            world.run_step();
+            heights.push(world.get::<Elevation>("manfred").height());
+        }
+
+        assert_eq!(vec![4, 7, 9, 10, 10, 9, 7, 4, 0], heights);
+        assert_eq!(&Position::new(0, 0), world.get::<Position>("manfred"));
+    }
+
+    #[test]
+    fn the_shadow_stays_on_the_ground_and_shrinks() {
+        let mut world = world_with_jumper();
+
+        world.run_step();
+        let shadow = world.get::<Transform>("shadow");
+        assert_eq!(-30.0, shadow.translation.y);
+        assert_eq!(Vec3::ONE, shadow.scale);
+
+        world.press(KeyCode::Space);
+        world.run_steps(4);
+
+        let manfred = world.get::<Transform>("manfred").translation;
+        let shadow = world.get::<Transform>("shadow");
+        assert_eq!(10.0, manfred.y);
+        assert_eq!(-40.0, shadow.translation.y);
+        assert!(shadow.translation.z < 0.0);
+        assert!(shadow.scale.x < 1.0);
+    }
+}
@@ -0,0 +1,434 @@
+use bevy::prelude::{
+    AssetServer, Assets, BuildChildren, Changed, Children, Commands, DespawnRecursiveExt, Entity,
+    Parent, Query, Res, ResMut, SpriteSheetBundle, TextureAtlas, TextureAtlasSprite, Transform,
+    With, Without, World,
+};
+
+use crate::component::equipment::{Equipment, EquipmentLayer};
+use crate::component::inventory::Inventory;
+use crate::component::manfred::Manfred;
+use crate::data::item::{EquipmentSlot, ItemDefinitions};
+use crate::plugin::animation::AnimationConfig;
+use crate::resource::console::ConsoleArgs;
+use crate::system::animation::direction_of_index;
+
+/// The distance in z between two layers. It is far below the difference one pixel further up or
+/// down makes, so layers never end up behind or in front of other objects.
+const LAYER_DEPTH: f32 = 0.001;
+
+pub fn add_equipment_system(
+    mut commands: Commands,
+    query: Query<Entity, (With<Manfred>, Without<Equipment>)>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).insert(Equipment::default());
+    }
+}
+
+/// Adds a layer child for every item put on and removes the layers of the items taken off.
+pub fn equipment_layer_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    animation: Res<AnimationConfig>,
+    definitions: Res<ItemDefinitions>,
+    wearers: Query<(Entity, &Equipment, Option<&Children>), Changed<Equipment>>,
+    layers: Query<&EquipmentLayer>,
+) {
+    for (wearer, equipment, children) in wearers.iter() {
+        let mut shown = vec![];
+        for child in children.iter().flat_map(|children| children.iter()) {
+            if let Ok(layer) = layers.get(*child) {
+                match equipment.items().any(|item| item == layer.item) {
+                    true => shown.push(layer.item.as_str()),
+                    false => commands.entity(*child).despawn_recursive(),
+                }
+            }
+        }
+
+        for item in equipment.items().filter(|item| !shown.contains(item)) {
+            let equipment = match definitions
+                .get(item)
+                .and_then(|definition| definition.equipment.as_ref())
+            {
+                Some(equipment) => equipment,
+                None => continue,
+            };
+
+            let texture_atlas = TextureAtlas::from_grid(
+                asset_server.load(equipment.sprite_atlas.as_str()),
+                animation.sprite_size,
+                animation.columns as usize,
+                animation.rows as usize,
+            );
+            let layer = commands
+                .spawn_bundle(SpriteSheetBundle {
+                    texture_atlas: texture_atlases.add(texture_atlas),
+                    ..Default::default()
+                })
+                .insert(EquipmentLayer {
+                    item: item.to_string(),
+                    draw_order: equipment.draw_order,
+                })
+                .id();
+            commands.entity(wearer).push_children(&[layer]);
+        }
+    }
+}
+
+/// Shows the frame of the body on every layer and puts the layer behind or in front of the body
+/// according to the direction that frame faces.
+pub fn equipment_sync_system(
+    config: Res<AnimationConfig>,
+    bodies: Query<&TextureAtlasSprite, Without<EquipmentLayer>>,
+    mut layers: Query<(
+        &EquipmentLayer,
+        &Parent,
+        &mut TextureAtlasSprite,
+        &mut Transform,
+    )>,
+) {
+    for (layer, parent, mut atlas_sprite, mut transform) in layers.iter_mut() {
+        if let Ok(body) = bodies.get(parent.0) {
+            let direction = direction_of_index(body.index, config.columns);
+            atlas_sprite.index = body.index;
+            transform.translation.z = layer.draw_order.facing(direction) as f32 * LAYER_DEPTH;
+        }
+    }
+}
+
+/// Moves one of the item from the inventory into its slot. What was worn there before goes back
+/// into the inventory. Returns the slot.
+pub fn equip(
+    inventory: &mut Inventory,
+    equipment: &mut Equipment,
+    definitions: &ItemDefinitions,
+    item: &str,
+) -> Result<EquipmentSlot, String> {
+    let definition = definitions
+        .get(item)
+        .ok_or_else(|| format!("unknown item '{}'", item))?;
+    let slot = definition
+        .equipment
+        .as_ref()
+        .ok_or_else(|| format!("{} cannot be worn", definition.name))?
+        .slot;
+
+    if !inventory.remove(item, 1) {
+        return Err(format!("Manfred has no {}", definition.name));
+    }
+
+    if let Some(previous) = equipment.put_on(slot, item) {
+        if inventory.add(&previous, 1, max_stack(definitions, &previous)) > 0 {
+            equipment.put_on(slot, &previous);
+            inventory.add(item, 1, definition.max_stack);
+            return Err(format!(
+                "there is no room for the {} in the inventory",
+                definitions.name_of(&previous)
+            ));
+        }
+    }
+
+    Ok(slot)
+}
+
+/// Moves the item worn in the slot back into the inventory. Returns the item.
+pub fn unequip(
+    inventory: &mut Inventory,
+    equipment: &mut Equipment,
+    definitions: &ItemDefinitions,
+    slot: EquipmentSlot,
+) -> Result<String, String> {
+    let item = equipment
+        .get(slot)
+        .ok_or_else(|| format!("Manfred wears nothing on the {:?} slot", slot))?
+        .to_string();
+
+    if inventory.add(&item, 1, max_stack(definitions, &item)) > 0 {
+        return Err(format!(
+            "there is no room for the {} in the inventory",
+            definitions.name_of(&item)
+        ));
+    }
+    equipment.take_off(slot);
+
+    Ok(item)
+}
+
+fn max_stack(definitions: &ItemDefinitions, item: &str) -> u32 {
+    definitions
+        .get(item)
+        .map_or(1, |definition| definition.max_stack)
+}
+
+pub fn equip_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let item = args.get(0).ok_or("missing item")?.to_string();
+    let definitions = world
+        .get_resource::<ItemDefinitions>()
+        .cloned()
+        .unwrap_or_default();
+
+    let mut query = world.query_filtered::<(&mut Inventory, &mut Equipment), With<Manfred>>();
+    let (mut inventory, mut equipment) = query
+        .iter_mut(world)
+        .next()
+        .ok_or("Manfred has no inventory or equipment")?;
+
+    let slot = equip(&mut inventory, &mut equipment, &definitions, &item)?;
+
+    Ok(format!(
+        "Manfred wears the {} on the {:?} slot",
+        definitions.name_of(&item),
+        slot
+    ))
+}
+
+pub fn unequip_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let slot = args.parse::<EquipmentSlot>(0, "slot")?;
+    let definitions = world
+        .get_resource::<ItemDefinitions>()
+        .cloned()
+        .unwrap_or_default();
+
+    let mut query = world.query_filtered::<(&mut Inventory, &mut Equipment), With<Manfred>>();
+    let (mut inventory, mut equipment) = query
+        .iter_mut(world)
+        .next()
+        .ok_or("Manfred has no inventory or equipment")?;
+
+    let item = unequip(&mut inventory, &mut equipment, &definitions, slot)?;
+
+    Ok(format!(
+        "Manfred took off the {}",
+        definitions.name_of(&item)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{
+        BuildWorldChildren, IntoSystem, TextureAtlasSprite, Transform, Vec2, World,
+    };
+    use rstest::*;
+
+    use crate::component::equipment::{Equipment, EquipmentLayer};
+    use crate::component::inventory::Inventory;
+    use crate::component::manfred::Manfred;
+    use crate::data::item::{
+        DrawOrder, EquipmentDefinition, EquipmentSlot, ItemDefinition, ItemDefinitions, PickupMode,
+    };
+    use crate::plugin::animation::AnimationConfig;
+    use crate::resource::console::{execute_console_command, ConsoleCommand, ConsoleCommands};
+    use crate::system::equipment::{
+        equip, equip_command, equipment_sync_system, unequip, unequip_command,
+    };
+    use crate::test_support::test_world::TestWorldBuilder;
+
+    fn definitions() -> ItemDefinitions {
+        let definition = |id: &str, slot: Option<EquipmentSlot>| ItemDefinition {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            max_stack: 5,
+            pickup: PickupMode::Contact,
+            color: (1.0, 1.0, 1.0),
+            size: Vec2::new(10.0, 10.0),
+            equipment: slot.map(|slot| EquipmentDefinition {
+                slot,
+                sprite_atlas: format!("images/equipment/{}.png", id),
+                draw_order: DrawOrder::default(),
+            }),
+        };
+
+        ItemDefinitions {
+            items: vec![
+                definition("apple", None),
+                definition("straw_hat", Some(EquipmentSlot::Head)),
+                definition("helmet", Some(EquipmentSlot::Head)),
+            ],
+        }
+    }
+
+    fn inventory_with(items: &[(&str, u32)]) -> Inventory {
+        let mut inventory = Inventory::new(2);
+        for (item, count) in items {
+            inventory.add(item, *count, 5);
+        }
+        inventory
+    }
+
+    #[test]
+    fn equipping_moves_the_item_out_of_the_inventory() {
+        let mut inventory = inventory_with(&[("straw_hat", 2)]);
+        let mut equipment = Equipment::default();
+
+        assert_eq!(
+            Ok(EquipmentSlot::Head),
+            equip(&mut inventory, &mut equipment, &definitions(), "straw_hat")
+        );
+
+        assert_eq!(1, inventory.count("straw_hat"));
+        assert_eq!(Some("straw_hat"), equipment.get(EquipmentSlot::Head));
+    }
+
+    #[test]
+    fn equipping_swaps_the_worn_item_back_into_the_inventory() {
+        let mut inventory = inventory_with(&[("helmet", 1)]);
+        let mut equipment = Equipment::default();
+        equipment.put_on(EquipmentSlot::Head, "straw_hat");
+
+        equip(&mut inventory, &mut equipment, &definitions(), "helmet").unwrap();
+
+        assert_eq!(1, inventory.count("straw_hat"));
+        assert_eq!(0, inventory.count("helmet"));
+        assert_eq!(Some("helmet"), equipment.get(EquipmentSlot::Head));
+    }
+
+    #[rstest]
+    #[case("apple", "APPLE cannot be worn")]
+    #[case("helmet", "Manfred has no HELMET")]
+    #[case("crown", "unknown item 'crown'")]
+    fn equipping_what_cannot_be_worn_changes_nothing(#[case] item: &str, #[case] error: &str) {
+        let mut inventory = inventory_with(&[("apple", 1)]);
+        let mut equipment = Equipment::default();
+
+        assert_eq!(
+            Err(error.to_string()),
+            equip(&mut inventory, &mut equipment, &definitions(), item)
+        );
+
+        assert_eq!(inventory, inventory_with(&[("apple", 1)]));
+        assert_eq!(Equipment::default(), equipment);
+    }
+
+    #[test]
+    fn swapping_into_a_full_inventory_changes_nothing() {
+        let mut inventory = inventory_with(&[("helmet", 2), ("apple", 5)]);
+        let mut equipment = Equipment::default();
+        equipment.put_on(EquipmentSlot::Head, "straw_hat");
+
+        assert!(equip(&mut inventory, &mut equipment, &definitions(), "helmet").is_err());
+
+        assert_eq!(2, inventory.count("helmet"));
+        assert_eq!(0, inventory.count("straw_hat"));
+        assert_eq!(Some("straw_hat"), equipment.get(EquipmentSlot::Head));
+    }
+
+    #[test]
+    fn unequipping_moves_the_item_back_into_the_inventory() {
+        let mut inventory = inventory_with(&[]);
+        let mut equipment = Equipment::default();
+        equipment.put_on(EquipmentSlot::Head, "straw_hat");
+
+        assert_eq!(
+            Ok("straw_hat".to_string()),
+            unequip(
+                &mut inventory,
+                &mut equipment,
+                &definitions(),
+                EquipmentSlot::Head
+            )
+        );
+
+        assert_eq!(1, inventory.count("straw_hat"));
+        assert_eq!(None, equipment.get(EquipmentSlot::Head));
+        assert!(unequip(
+            &mut inventory,
+            &mut equipment,
+            &definitions(),
+            EquipmentSlot::Head
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn unequipping_into_a_full_inventory_keeps_the_item_on() {
+        let mut inventory = inventory_with(&[("apple", 10)]);
+        let mut equipment = Equipment::default();
+        equipment.put_on(EquipmentSlot::Head, "straw_hat");
+
+        assert!(unequip(
+            &mut inventory,
+            &mut equipment,
+            &definitions(),
+            EquipmentSlot::Head
+        )
+        .is_err());
+
+        assert_eq!(Some("straw_hat"), equipment.get(EquipmentSlot::Head));
+    }
+
+    #[test]
+    fn commands_equip_and_unequip_manfred() {
+        let mut commands = ConsoleCommands::default();
+        commands.register(ConsoleCommand::new("equip", "", "", equip_command));
+        commands.register(ConsoleCommand::new("unequip", "", "", unequip_command));
+        let mut world = World::default();
+        world.insert_resource(commands);
+        world.insert_resource(definitions());
+        world.spawn().insert_bundle((
+            Manfred::default(),
+            inventory_with(&[("straw_hat", 1)]),
+            Equipment::default(),
+        ));
+
+        assert_eq!(
+            Ok("Manfred wears the STRAW_HAT on the Head slot".to_string()),
+            execute_console_command(&mut world, "equip straw_hat")
+        );
+        assert_eq!(
+            Ok("Manfred took off the STRAW_HAT".to_string()),
+            execute_console_command(&mut world, "unequip Head")
+        );
+        assert!(execute_console_command(&mut world, "unequip Hat").is_err());
+    }
+
+    #[rstest]
+    #[case(3, 0)]
+    #[case(4 + 1, 1)]
+    #[case(8 + 2, 1)]
+    #[case(12 + 3, -1)]
+    fn layers_show_the_frame_of_the_body_in_the_draw_order_of_its_direction(
+        #[case] index: u32,
+        #[case] expected_order: i8,
+    ) {
+        let mut world = TestWorldBuilder::new()
+            .with_resource(AnimationConfig {
+                columns: 4,
+                ..Default::default()
+            })
+            .with_system(equipment_sync_system.system())
+            .with_entity(
+                "body",
+                (TextureAtlasSprite::new(index), Transform::default()),
+            )
+            .with_entity(
+                "backpack",
+                (
+                    EquipmentLayer {
+                        item: "backpack".to_string(),
+                        draw_order: DrawOrder {
+                            down: 0,
+                            left: 1,
+                            right: 1,
+                            up: -1,
+                        },
+                    },
+                    TextureAtlasSprite::new(0),
+                    Transform::default(),
+                ),
+            )
+            .build();
+        let backpack = world.entity("backpack");
+        let body = world.entity("body");
+        world
+            .world_mut()
+            .entity_mut(body)
+            .push_children(&[backpack]);
+
+        world.run_step();
+
+        assert_eq!(index, world.get::<TextureAtlasSprite>("backpack").index);
+        let z = world.get::<Transform>("backpack").translation.z;
+        assert_eq!(expected_order as f32, (z * 1000.0).round());
+    }
+}
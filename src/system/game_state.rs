@@ -0,0 +1,13 @@
+use bevy::ecs::schedule::ShouldRun;
+use bevy::prelude::{Res, State};
+
+use crate::types::game_state::GameState;
+
+/// Run criteria for systems that should stop once the game is over. Apps without a game state
+/// are always playing.
+pub fn is_playing(state: Option<Res<State<GameState>>>) -> ShouldRun {
+    match state {
+        Some(state) if *state.current() != GameState::Playing => ShouldRun::No,
+        _ => ShouldRun::Yes,
+    }
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::{Res, ResMut, Time};
+
+use crate::resource::game_time::GameTime;
+
+pub fn game_time_system(time: Res<Time>, mut game_time: ResMut<GameTime>) {
+    game_time.advance(time.delta_seconds());
+}
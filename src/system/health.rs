@@ -0,0 +1,303 @@
+use bevy::prelude::{
+    AssetServer, Color, Commands, Entity, EventReader, EventWriter, PositionType, Query, Rect, Res,
+    ResMut, State, Style, Text, TextBundle, TextStyle, Transform, Val, Visible, With, Without,
+};
+
+use crate::component::collider::Collider;
+use crate::component::elevation::Elevation;
+use crate::component::hazard::Hazard;
+use crate::component::health::{Health, Invulnerability};
+use crate::component::manfred::Manfred;
+use crate::event::damage::{DamageEvent, DamageSource};
+use crate::plugin::health::HealthConfig;
+use crate::resource::game_time::GameTime;
+use crate::system::position::ground_position;
+use crate::types::game_state::GameState;
+use crate::Velocity;
+
+pub fn add_health_system(
+    mut commands: Commands,
+    config: Res<HealthConfig>,
+    query: Query<Entity, (With<Manfred>, Without<Health>)>,
+) {
+    for entity in query.iter() {
+        commands
+            .entity(entity)
+            .insert(Health::new(config.manfred_max_health));
+    }
+}
+
+/// Hazards lie on the ground, so whoever is in the air, e.g. jumping over one, is not hurt.
+#[allow(clippy::type_complexity)]
+pub fn hazard_contact_system(
+    mut damage_events: EventWriter<DamageEvent>,
+    targets: Query<
+        (Entity, &Transform, &Collider, Option<&Elevation>),
+        (With<Health>, Without<Invulnerability>),
+    >,
+    hazards: Query<(&Transform, &Collider, &Hazard)>,
+) {
+    for (target, target_transform, target_collider, elevation) in targets.iter() {
+        if elevation.map_or(false, |elevation| elevation.height() > 0) {
+            continue;
+        }
+        let target_position = ground_position(target_transform, elevation);
+
+        for (hazard_transform, hazard_collider, hazard) in hazards.iter() {
+            let hazard_position = hazard_transform.translation.truncate();
+
+            if target_collider.overlaps(target_position, hazard_collider, hazard_position) {
+                damage_events.send(DamageEvent {
+                    target,
+                    amount: hazard.damage,
+                    source: DamageSource {
+                        name: hazard.name.clone(),
+                        position: hazard_collider.center(hazard_position),
+                    },
+                });
+            }
+        }
+    }
+}
+
+pub fn damage_system(
+    mut commands: Commands,
+    config: Res<HealthConfig>,
+    mut damage_events: EventReader<DamageEvent>,
+    mut targets: Query<
+        (
+            &mut Health,
+            &Transform,
+            Option<&Collider>,
+            Option<&Elevation>,
+            Option<&mut Velocity>,
+        ),
+        Without<Invulnerability>,
+    >,
+) {
+    // the invulnerability is only inserted at the end of the stage
+    let mut damaged = vec![];
+
+    for event in damage_events.iter() {
+        if damaged.contains(&event.target) {
+            continue;
+        }
+
+        if let Ok((mut health, transform, collider, elevation, velocity)) =
+            targets.get_mut(event.target)
+        {
+            if health.is_dead() {
+                continue;
+            }
+
+            health.take_damage(event.amount);
+            damaged.push(event.target);
+            commands.entity(event.target).insert(Invulnerability {
+                remaining_seconds: config.invulnerability_seconds,
+            });
+
+            if let Some(mut velocity) = velocity {
+                let position = ground_position(transform, elevation);
+                let center = collider.map_or(position, |collider| collider.center(position));
+                // whole pixels, the velocity itself is integer arithmetic only
+                let away = (center - event.source.position).round();
+                velocity.knock_back((away.x as i32, away.y as i32));
+            }
+        }
+    }
+}
+
+pub fn invulnerability_system(
+    mut commands: Commands,
+    config: Res<HealthConfig>,
+    game_time: Res<GameTime>,
+    mut query: Query<(Entity, &mut Invulnerability, Option<&mut Visible>)>,
+) {
+    for (entity, mut invulnerability, visible) in query.iter_mut() {
+        invulnerability.remaining_seconds -= game_time.delta_seconds();
+
+        let expired = invulnerability.remaining_seconds <= 0.0;
+        if expired {
+            commands.entity(entity).remove::<Invulnerability>();
+        }
+
+        if let Some(mut visible) = visible {
+            let flash_phase = (invulnerability.remaining_seconds / config.flash_interval) as i32;
+            visible.is_visible = expired || flash_phase % 2 == 0;
+        }
+    }
+}
+
+pub fn game_over_system(mut state: ResMut<State<GameState>>, query: Query<&Health, With<Manfred>>) {
+    if *state.current() == GameState::Playing && query.iter().any(Health::is_dead) {
+        // fails only if the game over is already queued
+        let _ = state.set(GameState::GameOver);
+    }
+}
+
+pub fn show_game_over(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut velocities: Query<&mut Velocity>,
+) {
+    for mut velocity in velocities.iter_mut() {
+        velocity.stop();
+    }
+
+    commands.spawn_bundle(TextBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            position: Rect {
+                top: Val::Percent(40.0),
+                left: Val::Percent(40.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        text: Text::with_section(
+            "Game Over",
+            TextStyle {
+                font: asset_server.load("fonts/DejaVuSansMono.ttf"),
+                font_size: 60.0,
+                color: Color::rgb(0.8, 0.1, 0.1),
+            },
+            Default::default(),
+        ),
+        ..Default::default()
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{IntoSystem, ParallelSystemDescriptorCoercion, Transform, Vec3, Visible};
+
+    use crate::component::collider::Collider;
+    use crate::component::elevation::Elevation;
+    use crate::component::hazard::Hazard;
+    use crate::component::health::{Health, Invulnerability};
+    use crate::component::manfred::Manfred;
+    use crate::event::damage::DamageEvent;
+    use crate::plugin::health::HealthConfig;
+    use crate::system::health::{damage_system, hazard_contact_system, invulnerability_system};
+    use crate::system::position::FromXAndY;
+    use crate::test_support::test_world::{TestWorld, TestWorldBuilder};
+    use crate::Velocity;
+
+    fn world_with_thorn_bush_right_of_manfred() -> TestWorld {
+        TestWorldBuilder::new()
+            .with_resource(HealthConfig::default())
+            .with_event::<DamageEvent>()
+            .with_system(hazard_contact_system.system().label("contact"))
+            .with_system(damage_system.system().after("contact"))
+            .with_entity(
+                "manfred",
+                (
+                    Manfred::default(),
+                    Health::new(5),
+                    Velocity::new(5),
+                    Collider::new(30.0, 20.0),
+                    Transform::from_translation(Vec3::compute_from_x_y(0.0, 0.0)),
+                ),
+            )
+            .with_entity(
+                "thorn bush",
+                (
+                    Hazard {
+                        name: "thorn bush".to_string(),
+                        damage: 2,
+                    },
+                    Collider::new(30.0, 20.0),
+                    Transform::from_translation(Vec3::compute_from_x_y(20.0, 5.0)),
+                ),
+            )
+            .build()
+    }
+
+    #[test]
+    fn touching_a_hazard_damages_once_and_grants_invulnerability() {
+        let mut world = world_with_thorn_bush_right_of_manfred();
+
+        world.run_step();
+
+        assert_eq!(3, world.get::<Health>("manfred").current());
+        assert!(world
+            .world()
+            .get::<Invulnerability>(world.entity("manfred"))
+            .is_some());
+
+        world.run_steps(5);
+
+        assert_eq!(3, world.get::<Health>("manfred").current());
+    }
+
+    #[test]
+    fn jumping_over_a_hazard_does_not_touch_it() {
+        let mut world = world_with_thorn_bush_right_of_manfred();
+        let mut elevation = Elevation::default();
+        elevation.launch(8);
+        elevation.update(1);
+        let manfred = world.entity("manfred");
+        world.world_mut().entity_mut(manfred).insert(elevation);
+        world.get_mut::<Transform>("manfred").translation =
+            Vec3::compute_from_x_y(0.0, 0.0) + Vec3::new(0.0, 8.0, 0.0);
+
+        world.run_step();
+
+        assert!(world.events::<DamageEvent>().is_empty());
+        assert_eq!(5, world.get::<Health>("manfred").current());
+    }
+
+    #[test]
+    fn damage_knocks_back_away_from_the_source() {
+        let mut world = world_with_thorn_bush_right_of_manfred();
+
+        world.run_step();
+
+        let velocity = world.get::<Velocity>("manfred");
+        assert!(velocity.x() < 0, "x: {}", velocity.x());
+        assert!(velocity.y() < 0, "y: {}", velocity.y());
+    }
+
+    #[test]
+    fn health_does_not_drop_below_zero() {
+        let mut world = world_with_thorn_bush_right_of_manfred();
+        world.get_mut::<Health>("manfred").take_damage(4);
+
+        world.run_step();
+
+        assert!(world.get::<Health>("manfred").is_dead());
+    }
+
+    #[test]
+    fn invulnerability_flashes_and_expires() {
+        let mut world = TestWorldBuilder::new()
+            .with_resource(HealthConfig::default())
+            .with_frame_duration(0.05)
+            .with_system(invulnerability_system.system())
+            .with_entity(
+                "manfred",
+                (
+                    Invulnerability {
+                        remaining_seconds: 0.5,
+                    },
+                    Visible::default(),
+                ),
+            )
+            .build();
+
+        let mut was_hidden = false;
+        for _ in 0..5 {
+            world.run_step();
+            was_hidden |= !world.get::<Visible>("manfred").is_visible;
+        }
+        assert!(was_hidden);
+
+        world.run_for_seconds(0.3);
+
+        assert!(world.get::<Visible>("manfred").is_visible);
+        assert!(world
+            .world()
+            .get::<Invulnerability>(world.entity("manfred"))
+            .is_none());
+    }
+}
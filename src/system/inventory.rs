@@ -0,0 +1,399 @@
+use bevy::log::error;
+use bevy::prelude::{
+    AssetServer, Assets, BuildChildren, Color, ColorMaterial, Commands, Entity, EventWriter, Input,
+    KeyCode, NodeBundle, Or, PositionType, Query, Rect, Res, ResMut, Size, Style, Text, TextBundle,
+    TextStyle, Transform, Val, Visible, With, Without, World,
+};
+
+use crate::component::collider::Collider;
+use crate::component::elevation::Elevation;
+use crate::component::inventory::{Inventory, InventoryPanel, InventoryText};
+use crate::component::item::Item;
+use crate::component::manfred::Manfred;
+use crate::data::item::{ItemDefinitions, PickupMode};
+use crate::data::load_ron_file;
+use crate::event::item::ItemPickedUpEvent;
+use crate::plugin::inventory::InventoryConfig;
+use crate::resource::console::ConsoleArgs;
+use crate::system::position::ground_position;
+
+pub fn load_item_definitions(world: &mut World) {
+    let path = world
+        .get_resource::<InventoryConfig>()
+        .unwrap()
+        .items_path
+        .clone();
+
+    let definitions = load_ron_file::<ItemDefinitions>(&path).unwrap_or_else(|message| {
+        error!("{}", message);
+        ItemDefinitions::default()
+    });
+
+    world.insert_resource(definitions);
+}
+
+pub fn add_inventory_system(
+    mut commands: Commands,
+    config: Res<InventoryConfig>,
+    query: Query<Entity, (With<Manfred>, Without<Inventory>)>,
+) {
+    for entity in query.iter() {
+        commands
+            .entity(entity)
+            .insert(Inventory::new(config.capacity));
+    }
+}
+
+/// Moves items into the inventory of whoever touches them or, for items that need an
+/// interaction, of whoever presses the interact key next to them. Only the nearest of those items
+/// is picked up per key press. Whoever jumps reaches the items on the ground below them.
+#[allow(clippy::type_complexity)]
+pub fn item_pickup_system(
+    mut commands: Commands,
+    config: Res<InventoryConfig>,
+    definitions: Res<ItemDefinitions>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut picked_up_events: EventWriter<ItemPickedUpEvent>,
+    mut pickers: Query<(
+        Entity,
+        &Transform,
+        &Collider,
+        Option<&Elevation>,
+        &mut Inventory,
+    )>,
+    mut items: Query<(Entity, &Transform, &Collider, &mut Item)>,
+) {
+    let interacting = keyboard_input.just_pressed(config.interact_key);
+
+    for (picker, picker_transform, picker_collider, elevation, mut inventory) in pickers.iter_mut()
+    {
+        let picker_position = ground_position(picker_transform, elevation);
+        let picker_center = picker_collider.center(picker_position);
+
+        let mut reached = vec![];
+        let mut nearest_interactable: Option<(Entity, f32)> = None;
+
+        for (entity, transform, collider, item) in items.iter_mut() {
+            // items that were taken completely are only despawned at the end of the stage
+            if item.stack.count == 0 {
+                continue;
+            }
+
+            let pickup = match definitions.get(&item.stack.item) {
+                Some(definition) => definition.pickup,
+                None => continue,
+            };
+            let position = transform.translation.truncate();
+
+            match pickup {
+                PickupMode::Contact => {
+                    if picker_collider.overlaps(picker_position, collider, position) {
+                        reached.push(entity);
+                    }
+                }
+                PickupMode::Interact => {
+                    let distance = picker_center.distance(collider.center(position));
+                    let is_nearest = nearest_interactable
+                        .map_or(true, |(_, nearest_distance)| distance < nearest_distance);
+
+                    if interacting && distance <= config.interact_distance && is_nearest {
+                        nearest_interactable = Some((entity, distance));
+                    }
+                }
+            }
+        }
+
+        reached.extend(nearest_interactable.map(|(entity, _)| entity));
+
+        for entity in reached {
+            let (_, _, _, mut item) = items.get_mut(entity).unwrap();
+            let max_stack = definitions.get(&item.stack.item).unwrap().max_stack;
+
+            let left_over = inventory.add(&item.stack.item, item.stack.count, max_stack);
+            let picked_up = item.stack.count - left_over;
+            if picked_up == 0 {
+                continue;
+            }
+
+            item.stack.count = left_over;
+            if left_over == 0 {
+                commands.entity(entity).despawn();
+            }
+
+            picked_up_events.send(ItemPickedUpEvent {
+                picker,
+                item: item.stack.item.clone(),
+                count: picked_up,
+            });
+        }
+    }
+}
+
+pub fn add_inventory_panel(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let hidden = Visible {
+        is_visible: false,
+        is_transparent: true,
+    };
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(240.0), Val::Auto),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(20.0),
+                    right: Val::Px(20.0),
+                    ..Default::default()
+                },
+                padding: Rect::all(Val::Px(8.0)),
+                ..Default::default()
+            },
+            material: materials.add(Color::rgba(0.1, 0.07, 0.03, 0.85).into()),
+            visible: hidden.clone(),
+            ..Default::default()
+        })
+        .insert(InventoryPanel)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        "",
+                        TextStyle {
+                            font: asset_server.load("fonts/DejaVuSansMono.ttf"),
+                            font_size: 16.0,
+                            color: Color::WHITE,
+                        },
+                        Default::default(),
+                    ),
+                    visible: hidden,
+                    ..Default::default()
+                })
+                .insert(InventoryText);
+        });
+}
+
+pub fn toggle_inventory_panel_system(
+    config: Res<InventoryConfig>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<&mut Visible, Or<(With<InventoryPanel>, With<InventoryText>)>>,
+) {
+    if keyboard_input.just_pressed(config.toggle_key) {
+        for mut visible in query.iter_mut() {
+            visible.is_visible = !visible.is_visible;
+        }
+    }
+}
+
+pub fn inventory_text_system(
+    definitions: Res<ItemDefinitions>,
+    inventories: Query<&Inventory, With<Manfred>>,
+    mut texts: Query<&mut Text, With<InventoryText>>,
+) {
+    if let Some(inventory) = inventories.iter().next() {
+        let value = describe_inventory(inventory, &definitions);
+
+        for mut text in texts.iter_mut() {
+            if text.sections[0].value != value {
+                text.sections[0].value = value.clone();
+            }
+        }
+    }
+}
+
+fn describe_inventory(inventory: &Inventory, definitions: &ItemDefinitions) -> String {
+    let mut lines = vec!["Inventory".to_string()];
+
+    for (index, slot) in inventory.slots().iter().enumerate() {
+        lines.push(match slot {
+            Some(stack) => format!(
+                "{:>2}. {} x{}",
+                index + 1,
+                definitions.name_of(&stack.item),
+                stack.count
+            ),
+            None => format!("{:>2}. -", index + 1),
+        });
+    }
+
+    lines.join("\n")
+}
+
+pub fn give_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let item = args.get(0).ok_or("missing item")?.to_string();
+    let count = args.parse_optional::<u32>(1, "count")?.unwrap_or(1);
+
+    let max_stack = world
+        .get_resource::<ItemDefinitions>()
+        .and_then(|definitions| definitions.get(&item))
+        .map(|definition| definition.max_stack)
+        .ok_or_else(|| format!("unknown item '{}'", item))?;
+
+    let mut query = world.query_filtered::<&mut Inventory, With<Manfred>>();
+    let mut inventory = query
+        .iter_mut(world)
+        .next()
+        .ok_or("Manfred has no inventory")?;
+
+    match inventory.add(&item, count, max_stack) {
+        0 => Ok(format!("gave Manfred {} {}", count, item)),
+        left_over => Ok(format!(
+            "gave Manfred {} {}, {} did not fit",
+            count - left_over,
+            item,
+            left_over
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{IntoSystem, KeyCode, Transform, Vec2, Vec3};
+
+    use crate::component::collider::Collider;
+    use crate::component::inventory::{Inventory, ItemStack};
+    use crate::component::item::Item;
+    use crate::component::manfred::Manfred;
+    use crate::data::item::{ItemDefinition, ItemDefinitions, PickupMode};
+    use crate::event::item::ItemPickedUpEvent;
+    use crate::plugin::inventory::InventoryConfig;
+    use crate::system::inventory::{describe_inventory, item_pickup_system};
+    use crate::system::position::FromXAndY;
+    use crate::test_support::test_world::{TestWorld, TestWorldBuilder};
+
+    fn definitions() -> ItemDefinitions {
+        let definition = |id: &str, max_stack, pickup| ItemDefinition {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            max_stack,
+            pickup,
+            color: (1.0, 1.0, 1.0),
+            size: Vec2::new(10.0, 10.0),
+            equipment: None,
+        };
+
+        ItemDefinitions {
+            items: vec![
+                definition("apple", 10, PickupMode::Contact),
+                definition("stick", 5, PickupMode::Interact),
+            ],
+        }
+    }
+
+    fn item(item: &str, count: u32, x: f32, y: f32) -> (Item, Collider, Transform) {
+        (
+            Item {
+                stack: ItemStack::new(item, count),
+            },
+            Collider::new(10.0, 10.0),
+            Transform::from_translation(Vec3::compute_from_x_y(x, y)),
+        )
+    }
+
+    fn world_with_manfred(capacity: usize) -> TestWorldBuilder {
+        TestWorldBuilder::new()
+            .with_resource(InventoryConfig::default())
+            .with_resource(definitions())
+            .with_event::<ItemPickedUpEvent>()
+            .with_system(item_pickup_system.system())
+            .with_entity(
+                "manfred",
+                (
+                    Manfred::default(),
+                    Inventory::new(capacity),
+                    Collider::new(30.0, 20.0),
+                    Transform::from_translation(Vec3::compute_from_x_y(0.0, 0.0)),
+                ),
+            )
+    }
+
+    fn item_exists(world: &TestWorld, name: &str) -> bool {
+        world.world().get_entity(world.entity(name)).is_some()
+    }
+
+    #[test]
+    fn touching_an_item_picks_it_up() {
+        let mut world = world_with_manfred(4)
+            .with_entity("apples", item("apple", 3, 10.0, 0.0))
+            .build();
+
+        world.run_step();
+
+        assert_eq!(3, world.get::<Inventory>("manfred").count("apple"));
+        assert!(!item_exists(&world, "apples"));
+        assert_eq!(
+            vec![ItemPickedUpEvent {
+                picker: world.entity("manfred"),
+                item: "apple".to_string(),
+                count: 3,
+            }],
+            world.events::<ItemPickedUpEvent>()
+        );
+    }
+
+    #[test]
+    fn items_out_of_reach_stay_in_the_world() {
+        let mut world = world_with_manfred(4)
+            .with_entity("apples", item("apple", 3, 100.0, 0.0))
+            .build();
+
+        world.run_step();
+
+        assert_eq!(0, world.get::<Inventory>("manfred").count("apple"));
+        assert!(item_exists(&world, "apples"));
+    }
+
+    #[test]
+    fn interactable_items_need_the_interact_key() {
+        let mut world = world_with_manfred(4)
+            .with_entity("near stick", item("stick", 1, 30.0, 0.0))
+            .with_entity("far stick", item("stick", 1, -40.0, 0.0))
+            .build();
+
+        world.run_step();
+        assert_eq!(0, world.get::<Inventory>("manfred").count("stick"));
+
+        world.press(InventoryConfig::default().interact_key);
+        world.run_step();
+
+        assert_eq!(1, world.get::<Inventory>("manfred").count("stick"));
+        assert!(!item_exists(&world, "near stick"));
+        assert!(item_exists(&world, "far stick"));
+    }
+
+    #[test]
+    fn what_does_not_fit_stays_in_the_world() {
+        let mut world = world_with_manfred(1)
+            .with_entity("apples", item("apple", 15, 0.0, 0.0))
+            .build();
+
+        world.run_step();
+
+        assert_eq!(10, world.get::<Inventory>("manfred").count("apple"));
+        assert_eq!(5, world.get::<Item>("apples").stack.count);
+    }
+
+    #[test]
+    fn inventory_lists_names_and_empty_slots() {
+        let mut inventory = Inventory::new(2);
+        inventory.add("apple", 3, 10);
+
+        assert_eq!(
+            "Inventory\n 1. APPLE x3\n 2. -",
+            describe_inventory(&inventory, &definitions())
+        );
+    }
+
+    #[test]
+    fn pressing_interact_with_nothing_near_does_nothing() {
+        let mut world = world_with_manfred(4).build();
+
+        world.press(KeyCode::E);
+        world.run_step();
+
+        assert!(world.events::<ItemPickedUpEvent>().is_empty());
+    }
+}
@@ -0,0 +1,693 @@
+use std::fs;
+use std::str::FromStr;
+
+use bevy::log::error;
+use bevy::prelude::{
+    AssetServer, Assets, Color, ColorMaterial, Commands, Entity, EventReader, Handle, NodeBundle,
+    PositionType, Query, Rect, Res, ResMut, Size, Sprite, SpriteBundle, SpriteSheetBundle, Style,
+    Texture, TextureAtlas, Transform, Val, Vec2, Vec3, With, World,
+};
+use ron::ser::PrettyConfig;
+
+use crate::component::animation::Animator;
+use crate::component::collider::{manfred_collider, tree_collider, Collider};
+use crate::component::day_night::BaseColor;
+use crate::component::door::Door;
+use crate::component::elevation::Elevation;
+use crate::component::facing::Facing;
+use crate::component::fidget::Fidgeting;
+use crate::component::hazard::Hazard;
+use crate::component::inventory::ItemStack;
+use crate::component::item::Item;
+use crate::component::level::{FadeOverlay, LevelObject};
+use crate::component::manfred::Manfred;
+use crate::component::npc::Npc;
+use crate::component::obstacle::Obstacle;
+use crate::component::player::{player_color, Player};
+use crate::component::surface::SurfaceArea;
+use crate::component::trigger::TriggerZone;
+use crate::component::Position;
+use crate::data::item::ItemDefinitions;
+use crate::data::level::{
+    DoorDefinition, HazardDefinition, ItemPlacement, LevelDefinition, NpcDefinition,
+    ObstacleDefinition, SurfaceDefinition, TriggerDefinition,
+};
+use crate::data::load_ron_file;
+use crate::event::trigger::TriggerEnteredEvent;
+use crate::generation::forest::{generate_forest, ForestSettings};
+use crate::plugin::animation::AnimationConfig;
+use crate::plugin::level::LevelConfig;
+use crate::plugin::movement::MovementConfig;
+use crate::resource::console::ConsoleArgs;
+use crate::resource::game_time::GameTime;
+use crate::resource::level_manager::LevelManager;
+use crate::resource::streaming::LoadedChunks;
+use crate::system::animation::MANFRED_ANIMATIONS;
+use crate::system::palette::palette_atlas;
+use crate::system::position::{teleport_manfred, FromXAndY};
+use crate::Velocity;
+
+const TREE_TEXTURE: &str = "images\\objects\\tree2.png";
+
+/// Things that can be placed in a level by name, e.g. from the console.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Prefab {
+    Tree,
+}
+
+impl FromStr for Prefab {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "tree" => Ok(Prefab::Tree),
+            _ => Err("known prefabs are: tree".to_string()),
+        }
+    }
+}
+
+pub fn add_manf(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    level: Res<LevelDefinition>,
+    animation: Res<AnimationConfig>,
+    movement: Res<MovementConfig>,
+) {
+    let texture_handle = asset_server.load(animation.sprite_atlas.as_str());
+
+    let texture_atlas = TextureAtlas::from_grid(
+        texture_handle,
+        animation.sprite_size,
+        animation.columns as usize,
+        animation.rows as usize,
+    );
+
+    let texture_atlas = texture_atlases.add(texture_atlas);
+
+    for index in 0..movement.players.len() {
+        let player = Player { index };
+        let start = level.manfred_start + player.start_offset();
+
+        commands
+            .spawn_bundle((
+                Manfred::default(),
+                Facing::default(),
+                Elevation::default(),
+                Animator::new(MANFRED_ANIMATIONS),
+                Fidgeting::new(animation.fidget.seed.wrapping_add(index as u64)),
+                player,
+                Position::new(start.x as i32, start.y as i32),
+                Velocity::new(movement.max_speed).with_profile(movement.profile.clone()),
+                manfred_collider(),
+                BaseColor(player_color(index)),
+            ))
+            .insert_bundle(SpriteSheetBundle {
+                texture_atlas: texture_atlas.clone(),
+                transform: Transform::from_translation(Vec3::compute_from_x_y(start.x, start.y)),
+                ..Default::default()
+            });
+    }
+}
+
+pub fn load_level_definition(world: &mut World) {
+    let path = world.get_resource::<LevelConfig>().unwrap().path.clone();
+
+    let level = load_ron_file::<LevelDefinition>(&path).unwrap_or_else(|message| {
+        error!("{}", message);
+        LevelDefinition::default()
+    });
+
+    world.insert_resource(level);
+    world.insert_resource(LevelManager::new(&path));
+}
+
+pub fn add_level_objects(world: &mut World) {
+    spawn_level_objects(world);
+}
+
+fn spawn_level_objects(world: &mut World) {
+    let level = world.get_resource::<LevelDefinition>().unwrap().clone();
+    spawn_objects(world, &level);
+}
+
+/// Spawns everything placed in the level definition, except for Manfred. Returns the spawned
+/// entities.
+pub fn spawn_objects(world: &mut World, level: &LevelDefinition) -> Vec<Entity> {
+    let mut entities = vec![];
+
+    for tree in level.trees.iter() {
+        entities.push(spawn_prefab(world, Prefab::Tree, *tree));
+    }
+
+    for hazard in level.hazards.iter() {
+        entities.push(spawn_hazard(world, hazard));
+    }
+
+    for obstacle in level.obstacles.iter() {
+        entities.push(spawn_obstacle(world, obstacle));
+    }
+
+    for item in level.items.iter() {
+        entities.extend(spawn_item(world, item));
+    }
+
+    for npc in level.npcs.iter() {
+        entities.push(spawn_npc(world, npc));
+    }
+
+    for trigger in level.triggers.iter() {
+        entities.push(spawn_trigger(world, trigger));
+    }
+
+    for door in level.doors.iter() {
+        entities.push(spawn_door(world, door));
+    }
+
+    for surface in level.surfaces.iter() {
+        entities.push(spawn_surface(world, surface));
+    }
+
+    entities
+}
+
+fn spawn_hazard(world: &mut World, hazard: &HazardDefinition) -> Entity {
+    let material = world
+        .get_resource_mut::<Assets<ColorMaterial>>()
+        .unwrap()
+        .add(Color::rgb(0.25, 0.35, 0.1).into());
+
+    world
+        .spawn()
+        .insert_bundle(SpriteBundle {
+            sprite: Sprite::new(hazard.size),
+            material,
+            transform: Transform::from_translation(Vec3::compute_from_x_y(
+                hazard.position.x,
+                hazard.position.y,
+            )),
+            ..Default::default()
+        })
+        .insert(Collider::new(hazard.size.x, hazard.size.y))
+        .insert(Hazard {
+            name: hazard.name.clone(),
+            damage: hazard.damage,
+        })
+        .insert(LevelObject)
+        .id()
+}
+
+fn spawn_obstacle(world: &mut World, obstacle: &ObstacleDefinition) -> Entity {
+    let material = world
+        .get_resource_mut::<Assets<ColorMaterial>>()
+        .unwrap()
+        .add(Color::rgb(0.45, 0.3, 0.15).into());
+
+    world
+        .spawn()
+        .insert_bundle(SpriteBundle {
+            sprite: Sprite::new(obstacle.size),
+            material,
+            transform: Transform::from_translation(Vec3::compute_from_x_y(
+                obstacle.position.x,
+                obstacle.position.y,
+            )),
+            ..Default::default()
+        })
+        .insert(Collider::new(obstacle.size.x, obstacle.size.y))
+        .insert(Obstacle {
+            height: obstacle.height,
+        })
+        .insert(LevelObject)
+        .id()
+}
+
+fn spawn_item(world: &mut World, placement: &ItemPlacement) -> Option<Entity> {
+    let definition = world
+        .get_resource::<ItemDefinitions>()
+        .and_then(|definitions| definitions.get(&placement.item))
+        .cloned();
+    let definition = match definition {
+        Some(definition) => definition,
+        None => {
+            error!("the level contains the unknown item '{}'", placement.item);
+            return None;
+        }
+    };
+
+    let (red, green, blue) = definition.color;
+    let material = world
+        .get_resource_mut::<Assets<ColorMaterial>>()
+        .unwrap()
+        .add(Color::rgb(red, green, blue).into());
+
+    let entity = world
+        .spawn()
+        .insert_bundle(SpriteBundle {
+            sprite: Sprite::new(definition.size),
+            material,
+            transform: Transform::from_translation(Vec3::compute_from_x_y(
+                placement.position.x,
+                placement.position.y,
+            )),
+            ..Default::default()
+        })
+        .insert(Collider::new(definition.size.x, definition.size.y))
+        .insert(Item {
+            stack: ItemStack::new(&placement.item, placement.count),
+        })
+        .insert(LevelObject)
+        .id();
+
+    Some(entity)
+}
+
+fn spawn_npc(world: &mut World, npc: &NpcDefinition) -> Entity {
+    let transform =
+        Transform::from_translation(Vec3::compute_from_x_y(npc.position.x, npc.position.y));
+    let atlas = npc.palette.as_ref().and_then(|palette| {
+        palette_atlas(world, palette)
+            .map_err(|message| error!("{}: {}", npc.name, message))
+            .ok()
+    });
+
+    let mut entity = match atlas {
+        Some(texture_atlas) => {
+            let mut entity = world.spawn();
+            entity.insert_bundle(SpriteSheetBundle {
+                texture_atlas,
+                transform,
+                ..Default::default()
+            });
+            entity
+        }
+        None => {
+            let material = world
+                .get_resource_mut::<Assets<ColorMaterial>>()
+                .unwrap()
+                .add(Color::rgb(0.3, 0.3, 0.7).into());
+            let mut entity = world.spawn();
+            entity.insert_bundle(SpriteBundle {
+                sprite: Sprite::new(Vec2::new(30.0, 70.0)),
+                material,
+                transform,
+                ..Default::default()
+            });
+            entity
+        }
+    };
+
+    entity
+        .insert(Collider::new(30.0, 20.0).with_offset(0.0, -25.0))
+        .insert(Npc {
+            name: npc.name.clone(),
+        })
+        .insert(LevelObject)
+        .id()
+}
+
+fn spawn_trigger(world: &mut World, trigger: &TriggerDefinition) -> Entity {
+    world
+        .spawn()
+        .insert(Transform::from_translation(Vec3::compute_from_x_y(
+            trigger.position.x,
+            trigger.position.y,
+        )))
+        .insert(TriggerZone::new(
+            &trigger.name,
+            trigger.size.x,
+            trigger.size.y,
+        ))
+        .insert(LevelObject)
+        .id()
+}
+
+fn spawn_door(world: &mut World, door: &DoorDefinition) -> Entity {
+    world
+        .spawn()
+        .insert(Transform::from_translation(Vec3::compute_from_x_y(
+            door.position.x,
+            door.position.y,
+        )))
+        .insert(TriggerZone::new(
+            &format!("door to {}", door.target_level),
+            door.size.x,
+            door.size.y,
+        ))
+        .insert(Door {
+            target_level: door.target_level.clone(),
+            target_spawn_point: door.target_spawn_point.clone(),
+        })
+        .insert(LevelObject)
+        .id()
+}
+
+fn spawn_surface(world: &mut World, surface: &SurfaceDefinition) -> Entity {
+    world
+        .spawn()
+        .insert(Transform::from_translation(Vec3::compute_from_x_y(
+            surface.position.x,
+            surface.position.y,
+        )))
+        .insert(SurfaceArea {
+            surface: surface.surface,
+            size: surface.size,
+        })
+        .insert(LevelObject)
+        .id()
+}
+
+pub fn spawn_prefab(world: &mut World, prefab: Prefab, position: Vec2) -> Entity {
+    match prefab {
+        Prefab::Tree => {
+            let texture: Handle<Texture> = world
+                .get_resource::<AssetServer>()
+                .unwrap()
+                .load(TREE_TEXTURE);
+            let material = world
+                .get_resource_mut::<Assets<ColorMaterial>>()
+                .unwrap()
+                .add(ColorMaterial::from(texture));
+
+            world
+                .spawn()
+                .insert_bundle(SpriteBundle {
+                    sprite: Sprite::new(Vec2::new(80.0, 200.0)),
+                    material,
+                    transform: Transform::from_translation(Vec3::compute_from_x_y(
+                        position.x, position.y,
+                    )),
+                    ..Default::default()
+                })
+                .insert(tree_collider())
+                .insert(LevelObject)
+                .id()
+        }
+    }
+}
+
+pub fn spawn_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let prefab = args.parse::<Prefab>(0, "prefab")?;
+    let x = args.parse::<f32>(1, "x")?;
+    let y = args.parse::<f32>(2, "y")?;
+
+    let entity = spawn_prefab(world, prefab, Vec2::new(x, y));
+
+    Ok(format!("spawned {:?} as {:?}", prefab, entity))
+}
+
+/// Replaces all objects of the level by the ones of the given level file and puts Manfred at the
+/// given spawn point, or at the start of the level if there is none. Manfred keeps facing the
+/// same direction but stops moving and lands, even in the middle of a jump.
+pub fn switch_level(
+    world: &mut World,
+    path: &str,
+    spawn_point: Option<&str>,
+) -> Result<(), String> {
+    let level = load_ron_file::<LevelDefinition>(path)?;
+    let start = match spawn_point {
+        Some(name) => level
+            .spawn_point(name)
+            .ok_or_else(|| format!("{} has no spawn point '{}'", path, name))?,
+        None => level.manfred_start,
+    };
+
+    let level_objects: Vec<Entity> = world
+        .query_filtered::<Entity, With<LevelObject>>()
+        .iter(world)
+        .collect();
+    for entity in level_objects {
+        world.despawn(entity);
+    }
+
+    world.insert_resource(level);
+    if let Some(mut chunks) = world.get_resource_mut::<LoadedChunks>() {
+        chunks.clear();
+    }
+    spawn_level_objects(world);
+    teleport_manfred(world, start);
+
+    let mut movements =
+        world.query_filtered::<(&mut Velocity, Option<&mut Elevation>), With<Manfred>>();
+    for (mut velocity, elevation) in movements.iter_mut(world) {
+        velocity.stop();
+        if let Some(mut elevation) = elevation {
+            *elevation = Elevation::default();
+        }
+    }
+
+    world
+        .get_resource_or_insert_with(|| LevelManager::new(path))
+        .set_current(path);
+
+    Ok(())
+}
+
+pub fn load_level_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let path = match (args.get(0), world.get_resource::<LevelManager>()) {
+        (Some(path), _) => path.to_string(),
+        (None, Some(manager)) => manager.current().to_string(),
+        (None, None) => world.get_resource::<LevelConfig>().unwrap().path.clone(),
+    };
+
+    switch_level(world, &path, args.get(1))?;
+
+    Ok(format!("loaded {}", path))
+}
+
+/// Generates a forest from the forest settings, saves it as a level file and loads it.
+pub fn generate_forest_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let config = world.get_resource::<LevelConfig>().unwrap().clone();
+    let mut settings = load_ron_file::<ForestSettings>(&config.forest_settings)?;
+    if let Some(seed) = args.parse_optional::<u64>(0, "seed")? {
+        settings.seed = seed;
+    }
+
+    let forest = generate_forest(&settings);
+    let content = ron::ser::to_string_pretty(&forest, PrettyConfig::default())
+        .map_err(|error| format!("cannot serialize the forest: {}", error))?;
+    fs::write(&config.generated_forest, content)
+        .map_err(|error| format!("cannot write {}: {}", config.generated_forest, error))?;
+
+    switch_level(world, &config.generated_forest, None)?;
+
+    Ok(format!(
+        "generated {} trees with seed {} into {}",
+        forest.trees.len(),
+        settings.seed,
+        config.generated_forest
+    ))
+}
+
+/// Starts the transition to another level when Manfred steps through a door.
+pub fn door_system(
+    mut manager: ResMut<LevelManager>,
+    mut trigger_events: EventReader<TriggerEnteredEvent>,
+    doors: Query<&Door>,
+    manfreds: Query<Entity, With<Manfred>>,
+) {
+    for event in trigger_events.iter() {
+        if manfreds.get(event.entity).is_err() {
+            continue;
+        }
+
+        if let Ok(door) = doors.get(event.trigger) {
+            manager.request_transition(&door.target_level, &door.target_spawn_point);
+        }
+    }
+}
+
+pub fn level_transition_system(
+    config: Res<LevelConfig>,
+    game_time: Res<GameTime>,
+    mut manager: ResMut<LevelManager>,
+) {
+    if manager.is_transitioning() {
+        manager.advance(game_time.delta_seconds(), config.fade_seconds);
+    }
+}
+
+/// Switches the level while the screen is black.
+pub fn level_loading_system(world: &mut World) {
+    let pending_load = world
+        .get_resource_mut::<LevelManager>()
+        .and_then(|mut manager| manager.take_pending_load());
+
+    if let Some((path, spawn_point)) = pending_load {
+        if let Err(message) = switch_level(world, &path, Some(&spawn_point)) {
+            error!("{}", message);
+        }
+    }
+}
+
+pub fn add_fade_overlay(mut commands: Commands, mut materials: ResMut<Assets<ColorMaterial>>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(0.0),
+                    left: Val::Px(0.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            material: materials.add(Color::rgba(0.0, 0.0, 0.0, 0.0).into()),
+            ..Default::default()
+        })
+        .insert(FadeOverlay);
+}
+
+pub fn fade_overlay_system(
+    config: Res<LevelConfig>,
+    manager: Res<LevelManager>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    overlays: Query<&Handle<ColorMaterial>, With<FadeOverlay>>,
+) {
+    if !manager.is_changed() {
+        return;
+    }
+
+    let alpha = manager.fade_alpha(config.fade_seconds);
+    for handle in overlays.iter() {
+        if let Some(material) = materials.get_mut(handle) {
+            material.color.set_a(alpha);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::asset::AssetPlugin;
+    use bevy::prelude::{AddAsset, App, ColorMaterial, IntoSystem, MinimalPlugins, Transform};
+
+    use crate::component::door::Door;
+    use crate::component::elevation::Elevation;
+    use crate::component::manfred::Manfred;
+    use crate::component::trigger::TriggerZone;
+    use crate::component::Position;
+    use crate::event::trigger::TriggerEnteredEvent;
+    use crate::plugin::level::LevelConfig;
+    use crate::resource::level_manager::{LevelManager, TransitionPhase};
+    use crate::system::level::{door_system, level_transition_system, switch_level};
+    use crate::test_support::test_world::{TestWorld, TestWorldBuilder};
+    use crate::types::Direction;
+    use crate::Velocity;
+
+    fn world_with_door() -> TestWorld {
+        TestWorldBuilder::new()
+            .with_resource(LevelConfig::default())
+            .with_resource(LevelManager::new("assets/levels/meadow.ron"))
+            .with_event::<TriggerEnteredEvent>()
+            .with_system(door_system.system())
+            .with_system(level_transition_system.system())
+            .with_frame_duration(0.1)
+            .with_entity("manfred", (Manfred::default(), Transform::default()))
+            .with_entity("npc", (Transform::default(),))
+            .with_entity(
+                "door",
+                (
+                    TriggerZone::new("door to farmhouse", 40.0, 40.0),
+                    Door {
+                        target_level: "assets/levels/farmhouse.ron".to_string(),
+                        target_spawn_point: "front_door".to_string(),
+                    },
+                ),
+            )
+            .build()
+    }
+
+    fn enter_door(world: &mut TestWorld, entity: &str) {
+        let entity = world.entity(entity);
+        let door = world.entity("door");
+        world.send_event(TriggerEnteredEvent {
+            entity,
+            trigger: door,
+            zone: "door to farmhouse".to_string(),
+        });
+    }
+
+    #[test]
+    fn manfred_entering_a_door_starts_the_transition() {
+        let mut world = world_with_door();
+
+        enter_door(&mut world, "manfred");
+        world.run_step();
+
+        let transition = world.resource::<LevelManager>().transition().unwrap();
+        assert_eq!("assets/levels/farmhouse.ron", transition.target_level);
+        assert_eq!("front_door", transition.target_spawn_point);
+    }
+
+    #[test]
+    fn others_entering_a_door_are_ignored() {
+        let mut world = world_with_door();
+
+        enter_door(&mut world, "npc");
+        world.run_step();
+
+        assert!(!world.resource::<LevelManager>().is_transitioning());
+    }
+
+    #[test]
+    fn screen_is_black_before_the_level_is_switched() {
+        let mut world = world_with_door();
+        enter_door(&mut world, "manfred");
+
+        world.run_for_seconds(LevelConfig::default().fade_seconds + 0.1);
+
+        let manager = world.resource::<LevelManager>();
+        assert_eq!(
+            TransitionPhase::Loading,
+            manager.transition().unwrap().phase
+        );
+        assert_eq!(1.0, manager.fade_alpha(LevelConfig::default().fade_seconds));
+    }
+
+    #[test]
+    fn switching_levels_keeps_the_facing_but_stops_and_lands_manfred() {
+        let mut app = App::build();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin)
+            .add_asset::<ColorMaterial>();
+        let mut velocity = Velocity::new(5);
+        velocity.set_movement(-5, 0);
+        let mut elevation = Elevation::default();
+        elevation.launch(8);
+        elevation.update(1);
+        let manfred = app
+            .world_mut()
+            .spawn()
+            .insert(Manfred {
+                view_direction: Direction::Left,
+            })
+            .insert(velocity)
+            .insert(elevation)
+            .insert(Position::new(300, 300))
+            .insert(Transform::default())
+            .id();
+
+        let switched = switch_level(
+            app.world_mut(),
+            "assets/levels/farmhouse.ron",
+            Some("front_door"),
+        );
+
+        assert_eq!(Ok(()), switched);
+        let world = app.world();
+        assert_eq!(
+            Direction::Left,
+            world.get::<Manfred>(manfred).unwrap().view_direction
+        );
+        assert!(!world.get::<Velocity>(manfred).unwrap().is_moving());
+        assert_eq!(
+            &Elevation::default(),
+            world.get::<Elevation>(manfred).unwrap()
+        );
+        assert_eq!(
+            &Position::new(0, -150),
+            world.get::<Position>(manfred).unwrap()
+        );
+    }
+}
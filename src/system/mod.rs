@@ -1,2 +1,23 @@
+pub mod animation;
+pub mod camera;
+pub mod checksum;
+pub mod console;
+pub mod day_night;
+#[cfg(feature = "debug_overlay")]
+pub mod debug_overlay;
+pub mod elevation;
+pub mod equipment;
+pub mod game_state;
+pub mod game_time;
+pub mod health;
+pub mod inventory;
+pub mod level;
+pub mod network;
+pub mod palette;
 pub mod position;
+pub mod quest;
+pub mod save;
+pub mod sound;
+pub mod streaming;
+pub mod trigger;
 pub mod velocity;
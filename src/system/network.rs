@@ -0,0 +1,348 @@
+use std::collections::HashSet;
+
+use bevy::app::AppExit;
+use bevy::prelude::{
+    Axis, Commands, Entity, EventReader, GamepadAxis, GamepadButton, Handle, Input, KeyCode, Query,
+    Res, ResMut, SpriteSheetBundle, TextureAtlas, Transform, Without,
+};
+
+use crate::component::animation::Animator;
+use crate::component::collider::manfred_collider;
+use crate::component::day_night::BaseColor;
+use crate::component::elevation::Elevation;
+use crate::component::facing::Facing;
+use crate::component::manfred::Manfred;
+use crate::component::network::RemotePlayer;
+use crate::component::player::{player_color, Player};
+use crate::component::Position;
+use crate::net::message::{ClientMessage, PlayerState, ServerMessage};
+use crate::net::simulation::{PlayerInput, SimulatedPlayer};
+use crate::plugin::movement::MovementConfig;
+use crate::resource::network::NetworkClient;
+use crate::system::animation::MANFRED_ANIMATIONS;
+use crate::Velocity;
+
+/// Seconds between two connection attempts while the server does not answer.
+const CONNECT_INTERVAL: f64 = 1.0;
+/// How many of the newest inputs every input message repeats.
+const REDUNDANT_INPUTS: usize = 4;
+
+pub fn connect_system(mut client: ResMut<NetworkClient>) {
+    if client.is_connected() {
+        return;
+    }
+
+    let now = client.socket.now();
+    if client
+        .last_connect_attempt
+        .map_or(true, |last| now - last >= CONNECT_INTERVAL)
+    {
+        let server = client.server;
+        client.socket.send(server, &ClientMessage::Connect);
+        client.last_connect_attempt = Some(now);
+    }
+}
+
+/// Applies what the server sent: the own Manfred is put where the server says plus the inputs it
+/// has not applied yet, the Manfreds of the other players are spawned, moved and despawned.
+#[allow(clippy::type_complexity)]
+pub fn network_receive_system(
+    mut commands: Commands,
+    mut client: ResMut<NetworkClient>,
+    mut config: ResMut<MovementConfig>,
+    mut local: Query<
+        (
+            &mut Position,
+            &mut Transform,
+            &mut Velocity,
+            &mut Manfred,
+            Option<&mut Facing>,
+            Option<&mut Elevation>,
+            Option<&Player>,
+            Option<&Handle<TextureAtlas>>,
+        ),
+        Without<RemotePlayer>,
+    >,
+    mut remotes: Query<
+        (
+            Entity,
+            &mut RemotePlayer,
+            &mut Position,
+            &mut Transform,
+            &mut Velocity,
+            &mut Elevation,
+        ),
+        Without<Manfred>,
+    >,
+) {
+    let messages = client.socket.receive::<ServerMessage>();
+    let server = client.server;
+
+    let mut local = local
+        .iter_mut()
+        .find(|(_, _, _, _, _, _, player, _)| player.map_or(true, |player| player.index == 0));
+
+    let mut newest_state = None;
+    for (sender, message) in messages {
+        if sender != server {
+            continue;
+        }
+        match message {
+            ServerMessage::Welcome { id, rules } => {
+                // the server's rules win, otherwise every prediction would be corrected
+                config.max_speed = rules.max_speed;
+                config.profile = rules.profile.clone();
+                config.facing = rules.facing.clone();
+                config.jump = rules.jump.clone();
+                if let Some((_, _, velocity, _, _, _, _, _)) = local.as_mut() {
+                    velocity.set_max_speed(rules.max_speed);
+                    velocity.set_profile(rules.profile.clone());
+                }
+                client.id = Some(id);
+                client.rules = rules;
+            }
+            ServerMessage::State {
+                acknowledged,
+                players,
+            } => {
+                // states can arrive out of order, older ones would undo the newer reconciliation
+                if acknowledged >= client.acknowledged {
+                    client.acknowledged = acknowledged;
+                    newest_state = Some((acknowledged, players));
+                }
+            }
+        }
+    }
+
+    let (acknowledged, players) = match (client.id, newest_state) {
+        (Some(_), Some(state)) => state,
+        _ => return,
+    };
+    let own_id = client.id.unwrap();
+    let rules = client.rules.clone();
+
+    let own_state = players.iter().find(|player| player.id == own_id);
+    if let (Some(state), Some(local)) = (own_state, local.as_mut()) {
+        let (position, transform, velocity, manfred, facing, elevation, _, _) = local;
+        let predicted = client.prediction.reconcile(
+            acknowledged,
+            SimulatedPlayer::from_state(state, &rules),
+            &rules,
+        );
+        **position = predicted.position;
+        transform.translation = predicted
+            .position
+            .elevated_translation(predicted.elevation.height());
+        velocity.set_steps(predicted.velocity.x_steps(), predicted.velocity.y_steps());
+        velocity.set_slowing_down(predicted.velocity.is_slowing_down());
+        manfred.view_direction = predicted.view_direction;
+        if let Some(facing) = facing {
+            **facing = predicted.facing;
+        }
+        if let Some(elevation) = elevation {
+            **elevation = predicted.elevation;
+        }
+    }
+
+    let mut updated = HashSet::new();
+    for (entity, mut remote, mut position, mut transform, mut velocity, mut elevation) in
+        remotes.iter_mut()
+    {
+        match players.iter().find(|player| player.id == remote.id) {
+            Some(state) => {
+                apply_remote_state(
+                    state,
+                    &mut remote,
+                    &mut position,
+                    &mut transform,
+                    &mut velocity,
+                    &mut elevation,
+                );
+                updated.insert(remote.id);
+            }
+            None => commands.entity(entity).despawn(),
+        }
+    }
+
+    let texture_atlas = local
+        .as_ref()
+        .and_then(|(_, _, _, _, _, _, _, texture_atlas)| texture_atlas.cloned());
+    for state in players
+        .iter()
+        .filter(|player| player.id != own_id && !updated.contains(&player.id))
+    {
+        let mut position = Position::default();
+        let mut transform = Transform::default();
+        let mut velocity = rules.velocity();
+        let mut remote = RemotePlayer {
+            id: state.id,
+            view_direction: state.view_direction,
+        };
+        let mut elevation = Elevation::default();
+        apply_remote_state(
+            state,
+            &mut remote,
+            &mut position,
+            &mut transform,
+            &mut velocity,
+            &mut elevation,
+        );
+
+        commands
+            .spawn_bundle((
+                remote,
+                Animator::new(MANFRED_ANIMATIONS),
+                position,
+                velocity,
+                elevation,
+                manfred_collider(),
+                BaseColor(player_color(state.id as usize)),
+            ))
+            .insert_bundle(SpriteSheetBundle {
+                texture_atlas: texture_atlas.clone().unwrap_or_default(),
+                transform,
+                ..Default::default()
+            });
+    }
+}
+
+fn apply_remote_state(
+    state: &PlayerState,
+    remote: &mut RemotePlayer,
+    position: &mut Position,
+    transform: &mut Transform,
+    velocity: &mut Velocity,
+    elevation: &mut Elevation,
+) {
+    *position = state.position;
+    *elevation = state.elevation;
+    transform.translation = state.position.elevated_translation(elevation.height());
+    velocity.set_steps(state.x_steps, state.y_steps);
+    velocity.set_slowing_down(state.slowing_down);
+    remote.view_direction = state.view_direction;
+}
+
+/// Sends the input of the first player, which `velocity_control_system` applies locally in the
+/// same frame.
+pub fn network_input_system(
+    config: Res<MovementConfig>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_buttons: Option<Res<Input<GamepadButton>>>,
+    gamepad_axes: Option<Res<Axis<GamepadAxis>>>,
+    mut client: ResMut<NetworkClient>,
+) {
+    let bindings = match (client.is_connected(), config.players.first()) {
+        (true, Some(bindings)) => bindings,
+        _ => return,
+    };
+
+    let input = PlayerInput::from_pressed(
+        |direction| {
+            bindings.is_pressed(
+                direction,
+                &keyboard_input,
+                gamepad_buttons.as_deref(),
+                gamepad_axes.as_deref(),
+            )
+        },
+        bindings.jump_pressed(&keyboard_input, gamepad_buttons.as_deref()),
+    );
+    client.prediction.record(input);
+
+    let message = ClientMessage::Input {
+        inputs: client.prediction.newest(REDUNDANT_INPUTS),
+    };
+    let server = client.server;
+    client.socket.send(server, &message);
+}
+
+/// Tells the server when the game is closed. Behind a link conditioner the message may never go
+/// out, then the server drops the player once it times out.
+pub fn disconnect_system(mut exits: EventReader<AppExit>, mut client: ResMut<NetworkClient>) {
+    if exits.iter().next().is_some() && client.is_connected() {
+        let server = client.server;
+        client.socket.send(server, &ClientMessage::Disconnect);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use bevy::prelude::{IntoSystem, ParallelSystemDescriptorCoercion, Transform};
+
+    use crate::component::door::Door;
+    use crate::component::network::RemotePlayer;
+    use crate::component::trigger::TriggerZone;
+    use crate::component::Position;
+    use crate::event::trigger::{TriggerEnteredEvent, TriggerExitedEvent, TriggerStayEvent};
+    use crate::net::message::ServerMessage;
+    use crate::net::simulation::{MovementRules, SimulatedPlayer};
+    use crate::net::socket::NetSocket;
+    use crate::plugin::movement::MovementConfig;
+    use crate::resource::level_manager::LevelManager;
+    use crate::resource::network::NetworkClient;
+    use crate::system::level::door_system;
+    use crate::system::network::network_receive_system;
+    use crate::system::trigger::trigger_zone_system;
+    use crate::test_support::test_world::TestWorldBuilder;
+
+    #[test]
+    fn remote_players_entering_a_door_do_not_start_a_transition() {
+        let mut server = NetSocket::bind("127.0.0.1:0", None).unwrap();
+        let socket = NetSocket::bind("127.0.0.1:0", None).unwrap();
+        let client_address = socket.local_addr().unwrap();
+        let mut world = TestWorldBuilder::new()
+            .with_resource(NetworkClient::new(socket, server.local_addr().unwrap()))
+            .with_resource(MovementConfig::default())
+            .with_resource(LevelManager::new("assets/levels/meadow.ron"))
+            .with_event::<TriggerEnteredEvent>()
+            .with_event::<TriggerStayEvent>()
+            .with_event::<TriggerExitedEvent>()
+            .with_system(network_receive_system.system().label("network_receive"))
+            .with_system(
+                trigger_zone_system
+                    .system()
+                    .label("trigger")
+                    .after("network_receive"),
+            )
+            .with_system(door_system.system().after("trigger"))
+            .with_entity(
+                "door",
+                (
+                    Transform::default(),
+                    TriggerZone::new("door to farmhouse", 40.0, 40.0),
+                    Door {
+                        target_level: "assets/levels/farmhouse.ron".to_string(),
+                        target_spawn_point: "front_door".to_string(),
+                    },
+                ),
+            )
+            .build();
+
+        // the feet of the other player are right in the door
+        let rules = MovementRules::default();
+        let other = SimulatedPlayer::new(Position::new(0, 30), &rules);
+        server.send(client_address, &ServerMessage::Welcome { id: 1, rules });
+        server.send(
+            client_address,
+            &ServerMessage::State {
+                acknowledged: 0,
+                players: vec![other.state(2)],
+            },
+        );
+        let mut remotes = world.world_mut().query::<&RemotePlayer>();
+        for _ in 0..200 {
+            world.run_step();
+            if remotes.iter(world.world()).next().is_some() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        world.run_steps(3);
+
+        assert_eq!(2, remotes.iter(world.world()).next().unwrap().id);
+        assert!(!world.resource::<LevelManager>().is_transitioning());
+    }
+}
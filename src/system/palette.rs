@@ -0,0 +1,168 @@
+use std::fs;
+use std::path::Path;
+
+use bevy::log::error;
+use bevy::prelude::{Assets, Handle, Texture, TextureAtlas, With, World};
+use bevy::render::texture::{ImageType, TextureFormat};
+
+use crate::component::manfred::Manfred;
+use crate::data::load_ron_file;
+use crate::data::palette::PaletteDefinitions;
+use crate::plugin::animation::AnimationConfig;
+use crate::resource::console::ConsoleArgs;
+use crate::resource::palette::PaletteAtlases;
+
+pub fn load_palette_definitions(world: &mut World) {
+    let path = world
+        .get_resource::<AnimationConfig>()
+        .unwrap()
+        .palettes_path
+        .clone();
+
+    let definitions = load_ron_file::<PaletteDefinitions>(&path).unwrap_or_else(|message| {
+        error!("{}", message);
+        PaletteDefinitions::default()
+    });
+    if let Err(message) = definitions.validate() {
+        error!("{}", message);
+    }
+
+    world.insert_resource(definitions);
+}
+
+/// Reads the image of the palettes and replaces its colours with those of the palette.
+pub fn recolored_texture(
+    definitions: &PaletteDefinitions,
+    palette: &str,
+) -> Result<Texture, String> {
+    let palette = definitions
+        .get(palette)
+        .ok_or_else(|| format!("there is no palette {}", palette))?;
+    let path = Path::new("assets").join(&definitions.sprite_atlas);
+
+    let bytes =
+        fs::read(&path).map_err(|error| format!("cannot read {}: {}", path.display(), error))?;
+    let mut texture = Texture::from_buffer(&bytes, ImageType::Extension("png"))
+        .map_err(|error| format!("cannot decode {}: {:?}", path.display(), error))?;
+    if texture.format != TextureFormat::Rgba8UnormSrgb {
+        return Err(format!("{} is not an RGBA image", path.display()));
+    }
+
+    definitions.recolor(palette, &mut texture.data);
+    Ok(texture)
+}
+
+/// The sprite atlas in the colours of the palette. It is recoloured the first time the palette is
+/// asked for and shared by everyone using the palette afterwards.
+pub fn palette_atlas(world: &mut World, palette: &str) -> Result<Handle<TextureAtlas>, String> {
+    let cached = world
+        .get_resource::<PaletteAtlases>()
+        .and_then(|atlases| atlases.get(palette));
+    if let Some(atlas) = cached {
+        return Ok(atlas);
+    }
+
+    let texture = {
+        let definitions = world
+            .get_resource::<PaletteDefinitions>()
+            .ok_or("no palettes are loaded")?;
+        recolored_texture(definitions, palette)?
+    };
+    let config = world
+        .get_resource::<AnimationConfig>()
+        .cloned()
+        .unwrap_or_default();
+
+    let texture = world
+        .get_resource_mut::<Assets<Texture>>()
+        .unwrap()
+        .add(texture);
+    let atlas = TextureAtlas::from_grid(
+        texture,
+        config.sprite_size,
+        config.columns as usize,
+        config.rows as usize,
+    );
+    let atlas = world
+        .get_resource_mut::<Assets<TextureAtlas>>()
+        .unwrap()
+        .add(atlas);
+
+    world
+        .get_resource_or_insert_with(PaletteAtlases::default)
+        .insert(palette, atlas.clone());
+    Ok(atlas)
+}
+
+pub fn palette_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let palette = args.parse::<String>(0, "palette")?;
+    let atlas = palette_atlas(world, &palette)?;
+
+    let mut query = world.query_filtered::<&mut Handle<TextureAtlas>, With<Manfred>>();
+    let mut manfred_found = false;
+    for mut texture_atlas in query.iter_mut(world) {
+        *texture_atlas = atlas.clone();
+        manfred_found = true;
+    }
+
+    match manfred_found {
+        true => Ok(format!("Manfred wears the colours of {}", palette)),
+        false => Err("there is no Manfred".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Texture;
+    use rstest::*;
+
+    use crate::data::load_ron_file;
+    use crate::data::palette::PaletteDefinitions;
+    use crate::system::palette::recolored_texture;
+
+    fn pixel(texture: &Texture, x: usize, y: usize) -> [u8; 4] {
+        let start = (y * texture.size.width as usize + x) * 4;
+        let mut pixel = [0; 4];
+        pixel.copy_from_slice(&texture.data[start..start + 4]);
+        pixel
+    }
+
+    fn definitions() -> PaletteDefinitions {
+        load_ron_file::<PaletteDefinitions>("assets/palettes/palettes.ron").unwrap()
+    }
+
+    #[rstest]
+    #[case(28, 6, [46, 139, 87, 255])]
+    #[case(27, 3, [240, 230, 140, 255])]
+    #[case(26, 12, [96, 64, 32, 255])]
+    #[case(28, 0, [0, 0, 0, 255])]
+    #[case(0, 0, [255, 255, 255, 0])]
+    fn the_farmer_palette_recolours_the_sprite_atlas(
+        #[case] x: usize,
+        #[case] y: usize,
+        #[case] expected: [u8; 4],
+    ) {
+        let texture = recolored_texture(&definitions(), "farmer").unwrap();
+
+        assert_eq!(expected, pixel(&texture, x, y));
+    }
+
+    #[test]
+    fn every_palette_keeps_the_size_of_the_sprite_atlas() {
+        let definitions = definitions();
+
+        for palette in definitions.palettes.iter() {
+            let texture = recolored_texture(&definitions, &palette.name).unwrap();
+
+            assert_eq!((160, 160), (texture.size.width, texture.size.height));
+        }
+    }
+
+    #[test]
+    fn unknown_palettes_are_reported() {
+        assert_eq!(
+            Err("there is no palette clown".to_string()),
+            recolored_texture(&definitions(), "clown").map(|_| ())
+        );
+    }
+}
@@ -1,14 +1,140 @@
-use bevy::prelude::{Query, Transform, Vec3};
+use std::borrow::Borrow;
 
+use bevy::prelude::{Commands, Entity, Query, Transform, Vec2, Vec3, With, Without, World};
+
+use crate::component::collider::Collider;
+use crate::component::elevation::Elevation;
+use crate::component::manfred::Manfred;
+use crate::component::obstacle::Obstacle;
+use crate::component::player::Player;
+use crate::component::Position;
+use crate::resource::console::ConsoleArgs;
 use crate::Velocity;
 
-pub fn move_positions_system(query: Query<(&mut Transform, &Velocity)>) {
-    query.for_each_mut(|(mut transform, velocity)| {
-        transform.translation = Vec3::compute_from_x_y(
-            transform.translation.x + velocity.x() as f32,
-            transform.translation.y + velocity.y() as f32,
+/// Gives everything that can move an integer position where its transform is, as the movement is
+/// computed on that.
+pub fn add_position_system(
+    mut commands: Commands,
+    query: Query<(Entity, &Transform), (With<Velocity>, Without<Position>)>,
+) {
+    for (entity, transform) in query.iter() {
+        let translation = transform.translation;
+        commands.entity(entity).insert(Position::new(
+            translation.x.round() as i32,
+            translation.y.round() as i32,
+        ));
+    }
+}
+
+/// Moves the integer positions, the transforms only follow them. Whoever has a collider slides
+/// along the obstacles that are too high to pass over, or stops at them.
+pub fn move_positions_system(
+    query: Query<
+        (
+            &mut Position,
+            &mut Transform,
+            &Velocity,
+            Option<&Collider>,
+            Option<&Elevation>,
+        ),
+        Without<Obstacle>,
+    >,
+    obstacles: Query<(&Transform, &Collider, &Obstacle)>,
+) {
+    let obstacles: Vec<_> = obstacles
+        .iter()
+        .map(|(transform, collider, obstacle)| {
+            (transform.translation.truncate(), collider, obstacle)
+        })
+        .collect();
+
+    query.for_each_mut(
+        |(mut position, mut transform, velocity, collider, elevation)| {
+            let height = elevation.map_or(0, Elevation::height);
+
+            *position = moved_position(*position, velocity, collider, height, &obstacles);
+            transform.translation = position.elevated_translation(height);
+        },
+    );
+}
+
+/// Where the velocity takes whoever is at the position within one frame, sliding along the
+/// obstacles or stopping at them, if there is a collider. The obstacles may be borrowed from the
+/// ECS or owned by the simulation.
+pub fn moved_position<C: Borrow<Collider>, O: Borrow<Obstacle>>(
+    position: Position,
+    velocity: &Velocity,
+    collider: Option<&Collider>,
+    height: i32,
+    obstacles: &[(Vec2, C, O)],
+) -> Position {
+    let (x, y) = (velocity.x(), velocity.y());
+
+    match collider {
+        Some(collider) => [(x, y), (x, 0), (0, y)]
+            .iter()
+            .map(|(x, y)| Position::new(position.x + x, position.y + y))
+            .find(|target| !is_blocked(collider, position, *target, height, obstacles))
+            .unwrap_or(position),
+        None => Position::new(position.x + x, position.y + y),
+    }
+}
+
+/// Whether moving the collider runs into an obstacle that is too high. Obstacles the collider
+/// already overlaps do not block, so whoever lands in one can walk out again.
+fn is_blocked<C: Borrow<Collider>, O: Borrow<Obstacle>>(
+    collider: &Collider,
+    from: Position,
+    to: Position,
+    height: i32,
+    obstacles: &[(Vec2, C, O)],
+) -> bool {
+    let as_vec2 = |position: Position| Vec2::new(position.x as f32, position.y as f32);
+    let (from, to) = (as_vec2(from), as_vec2(to));
+
+    obstacles
+        .iter()
+        .filter(|(_, _, obstacle)| obstacle.borrow().blocks(height))
+        .any(|(position, other, _)| {
+            let other = other.borrow();
+            collider.overlaps(to, other, *position) && !collider.overlaps(from, other, *position)
+        })
+}
+
+/// Where the entity stands on the ground, below its sprite while it is in the air.
+pub fn ground_position(transform: &Transform, elevation: Option<&Elevation>) -> Vec2 {
+    let height = elevation.map_or(0, Elevation::height);
+    transform.translation.truncate() - Vec2::new(0.0, height as f32)
+}
+
+/// Puts Manfred at the given position, the Manfreds of further players a bit to the side.
+/// Returns false if there is no Manfred.
+pub fn teleport_manfred(world: &mut World, target: Vec2) -> bool {
+    let mut query = world
+        .query_filtered::<(&mut Transform, Option<&mut Position>, Option<&Player>), With<Manfred>>(
         );
-    });
+
+    let mut manfred_found = false;
+    for (mut transform, position, player) in query.iter_mut(world) {
+        let target = target + player.map_or(Vec2::ZERO, Player::start_offset);
+        let target = Position::new(target.x.round() as i32, target.y.round() as i32);
+        transform.translation = target.translation();
+        if let Some(mut position) = position {
+            *position = target;
+        }
+        manfred_found = true;
+    }
+    manfred_found
+}
+
+pub fn teleport_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let x = args.parse::<f32>(0, "x")?;
+    let y = args.parse::<f32>(1, "y")?;
+
+    match teleport_manfred(world, Vec2::new(x, y)) {
+        true => Ok(format!("teleported Manfred to ({}, {})", x, y)),
+        false => Err("there is no Manfred".to_string()),
+    }
 }
 
 pub trait FromXAndY {
@@ -27,15 +153,61 @@ impl FromXAndY for Vec3 {
 mod tests {
     use std::cmp::Ordering;
 
-    use bevy::prelude::{Entity, Transform, Vec3};
+    use bevy::prelude::{IntoSystem, ParallelSystemDescriptorCoercion, Transform, Vec3};
     use rstest::*;
 
     use quickcheck_macros::quickcheck;
 
-    use crate::system::position::FromXAndY;
-    use crate::{
-        move_positions_system, Direction, IntoSystem, Stage, SystemStage, Velocity, World,
+    use crate::component::collider::Collider;
+    use crate::component::elevation::Elevation;
+    use crate::component::manfred::Manfred;
+    use crate::component::obstacle::Obstacle;
+    use crate::component::Position;
+    use crate::resource::console::{execute_console_command, ConsoleCommand, ConsoleCommands};
+    use crate::system::position::{
+        add_position_system, move_positions_system, teleport_command, FromXAndY,
     };
+    use crate::test_support::test_world::{TestWorld, TestWorldBuilder};
+    use crate::types::Direction;
+    use crate::Velocity;
+
+    fn elevated(height: i32) -> Elevation {
+        let mut elevation = Elevation::default();
+        elevation.launch(height);
+        elevation.update(0);
+        elevation
+    }
+
+    /// A mover of 10 x 10 at (0, 0) walking in the directions, and a fence 20 high, whose left
+    /// side is at x = 10.
+    fn world_with_fence(directions: &[Direction], elevation: Elevation) -> TestWorld {
+        let mut velocity = Velocity::new(10);
+        for direction in directions {
+            velocity.accelerate(*direction);
+        }
+
+        TestWorldBuilder::new()
+            .with_system(move_positions_system.system())
+            .with_entity(
+                "mover",
+                (
+                    Position::new(0, 0),
+                    Transform::default(),
+                    velocity,
+                    Collider::new(10.0, 10.0),
+                    elevation,
+                ),
+            )
+            .with_entity(
+                "fence",
+                (
+                    Transform::from_translation(Vec3::compute_from_x_y(12.0, 0.0)),
+                    Collider::new(4.0, 40.0),
+                    Obstacle { height: 20 },
+                ),
+            )
+            .build()
+    }
 
     #[quickcheck]
     fn vec3_from_x_and_y_preserves_x_and_y(x: f32, y: f32) {
@@ -63,20 +235,28 @@ mod tests {
         #[case] expected_y: f32,
         #[case] expected_z_relation: Ordering,
     ) {
-        let mut world = WorldWrapper::init();
+        let mut world = TestWorldBuilder::new()
+            .with_system(move_positions_system.system())
+            .with_entity(
+                "entity",
+                (
+                    Position::new(0, 0),
+                    Transform::from_translation(Vec3::compute_from_x_y(0.0, 0.0)),
+                    Velocity::new(10),
+                ),
+            )
+            .build();
 
-        let (transform, _) = world.get_entity();
-        let initial_z = *&transform.translation.z;
+        let initial_z = world.get::<Transform>("entity").translation.z;
 
         for acceleration in acceleration_steps {
-            world.accelerate_entity(acceleration);
+            world.get_mut::<Velocity>("entity").accelerate(acceleration);
         }
 
         world.run_step();
 
-        let (transform, _) = world.get_entity();
-        assert_eq!(expected_x, transform.translation.x);
-        assert_eq!(expected_y, transform.translation.y);
+        world.assert_position("entity", expected_x, expected_y);
+        let transform = world.get::<Transform>("entity");
         assert_eq!(
             Some(expected_z_relation),
             transform.translation.z.partial_cmp(&initial_z),
@@ -87,47 +267,124 @@ mod tests {
         assert!(transform.translation.z >= 0.0)
     }
 
-    struct WorldWrapper {
-        entity_id: Entity,
-        world: World,
-        system_stage: SystemStage,
+    #[test]
+    fn teleport_command_moves_manfred() {
+        let mut commands = ConsoleCommands::default();
+        commands.register(ConsoleCommand::new(
+            "teleport",
+            "<x> <y>",
+            "",
+            teleport_command,
+        ));
+        let mut world = TestWorldBuilder::new()
+            .with_resource(commands)
+            .with_entity(
+                "manfred",
+                (
+                    Manfred::default(),
+                    Transform::from_translation(Vec3::compute_from_x_y(0.0, 0.0)),
+                ),
+            )
+            .build();
+
+        let result = execute_console_command(world.world_mut(), "teleport 120 -40");
+
+        assert!(result.is_ok());
+        world.assert_position("manfred", 120.0, -40.0);
+        assert_eq!(
+            Vec3::compute_from_x_y(120.0, -40.0).z,
+            world.get::<Transform>("manfred").translation.z
+        );
     }
 
-    impl WorldWrapper {
-        fn init() -> WorldWrapper {
-            let mut world = World::default();
-
-            let mut system_stage = SystemStage::parallel();
-            system_stage.add_system(move_positions_system.system());
-
-            let entity_id = world
-                .spawn()
-                .insert(Transform::from_translation(Vec3::compute_from_x_y(
-                    0.0, 0.0,
-                )))
-                .insert(Velocity::new(10))
-                .id();
-
-            WorldWrapper {
-                entity_id,
-                world,
-                system_stage,
-            }
-        }
+    #[test]
+    fn positions_accumulate_without_rounding_errors() {
+        let mut world = TestWorldBuilder::new()
+            .with_system(move_positions_system.system())
+            .with_entity(
+                "entity",
+                (
+                    Position::new(3, -7),
+                    Transform::default(),
+                    Velocity::new(10),
+                ),
+            )
+            .build();
+        let (x, y) = {
+            let mut velocity = world.get_mut::<Velocity>("entity");
+            velocity.accelerate(Direction::Up);
+            velocity.accelerate(Direction::Right);
+            velocity.accelerate(Direction::Right);
+            (velocity.x(), velocity.y())
+        };
 
-        fn run_step(&mut self) {
-            self.system_stage.run(&mut self.world);
-        }
+        world.run_steps(1000);
 
-        fn get_entity(&self) -> (&Transform, &Velocity) {
-            let transform = self.world.get::<Transform>(self.entity_id).unwrap();
-            let velocity = self.world.get::<Velocity>(self.entity_id).unwrap();
-            (transform, velocity)
-        }
+        assert_eq!(
+            &Position::new(3 + 1000 * x, -7 + 1000 * y),
+            world.get::<Position>("entity")
+        );
+        assert_eq!(
+            world.get::<Position>("entity").translation(),
+            world.get::<Transform>("entity").translation
+        );
+    }
 
-        fn accelerate_entity(&mut self, direction: Direction) {
-            let mut velocity = self.world.get_mut::<Velocity>(self.entity_id).unwrap();
-            velocity.accelerate(direction);
-        }
+    #[rstest]
+    #[case(0, 5)]
+    #[case(19, 5)]
+    #[case(20, 20)]
+    fn obstacles_stop_whoever_is_not_high_enough(#[case] height: i32, #[case] expected_x: i32) {
+        let mut world = world_with_fence(&[Direction::Right], elevated(height));
+
+        world.run_steps(20);
+
+        assert_eq!(
+            &Position::new(expected_x, 0),
+            world.get::<Position>("mover")
+        );
+    }
+
+    #[test]
+    fn movers_slide_along_obstacles() {
+        let mut world = world_with_fence(&[Direction::Right, Direction::Up], Elevation::default());
+
+        world.run_steps(20);
+
+        assert_eq!(&Position::new(5, 20), world.get::<Position>("mover"));
+    }
+
+    #[test]
+    fn elevated_sprites_are_drawn_higher_but_sorted_by_their_ground_position() {
+        let mut world = world_with_fence(&[], elevated(30));
+
+        world.run_step();
+
+        assert_eq!(&Position::new(0, 0), world.get::<Position>("mover"));
+        let translation = world.get::<Transform>("mover").translation;
+        assert_eq!(30.0, translation.y);
+        assert_eq!(Vec3::compute_from_x_y(0.0, 0.0).z, translation.z);
+    }
+
+    #[test]
+    fn movers_spawned_without_a_position_get_one_and_move() {
+        let mut velocity = Velocity::new(10);
+        velocity.accelerate(Direction::Right);
+        let mut world = TestWorldBuilder::new()
+            .with_system(add_position_system.system().before("update_position"))
+            .with_system(move_positions_system.system().label("update_position"))
+            .with_entity(
+                "npc",
+                (
+                    Transform::from_translation(Vec3::compute_from_x_y(30.4, -12.0)),
+                    velocity,
+                ),
+            )
+            .build();
+
+        world.run_steps(3);
+
+        assert_eq!(&Position::new(32, -12), world.get::<Position>("npc"));
+        world.assert_position("npc", 32.0, -12.0);
     }
 }
@@ -0,0 +1,486 @@
+use bevy::log::{error, info, warn};
+use bevy::prelude::{
+    AssetServer, Assets, BuildChildren, Color, ColorMaterial, Commands, Entity, EventReader,
+    EventWriter, Input, KeyCode, NodeBundle, Or, PositionType, Query, Rect, Res, ResMut, Size,
+    Style, Text, TextBundle, TextStyle, Transform, Val, Visible, With, World,
+};
+
+use crate::component::collider::Collider;
+use crate::component::inventory::Inventory;
+use crate::component::manfred::Manfred;
+use crate::component::npc::Npc;
+use crate::component::quest::{QuestLogPanel, QuestLogText};
+use crate::data::item::ItemDefinitions;
+use crate::data::load_ron_file;
+use crate::data::quest::{ObjectiveEvent, QuestDefinitions, Reward};
+use crate::event::interaction::TalkedToEvent;
+use crate::event::item::ItemPickedUpEvent;
+use crate::event::trigger::TriggerEnteredEvent;
+use crate::plugin::quest::QuestConfig;
+use crate::resource::console::ConsoleArgs;
+use crate::resource::quest_log::{QuestLog, QuestStatus};
+use crate::resource::world_flags::WorldFlags;
+
+pub fn load_quest_definitions(world: &mut World) {
+    let path = world
+        .get_resource::<QuestConfig>()
+        .unwrap()
+        .quests_path
+        .clone();
+
+    let mut definitions = load_ron_file::<QuestDefinitions>(&path).unwrap_or_else(|message| {
+        error!("{}", message);
+        QuestDefinitions::default()
+    });
+    for message in definitions.remove_invalid() {
+        error!("{}", message);
+    }
+
+    world.insert_resource(definitions);
+}
+
+/// Starts every quest whose required world flag is set.
+pub fn start_quests_system(
+    definitions: Res<QuestDefinitions>,
+    flags: Res<WorldFlags>,
+    mut log: ResMut<QuestLog>,
+) {
+    for quest in definitions.quests.iter() {
+        let startable = quest
+            .requires_flag
+            .as_ref()
+            .map_or(true, |flag| flags.is_set(flag));
+
+        if startable && log.get(&quest.id).is_none() {
+            log.start(quest);
+            info!("started quest '{}'", quest.title);
+        }
+    }
+}
+
+/// Lets Manfred talk to the nearest NPC in reach when the talk key is pressed.
+pub fn talk_system(
+    config: Res<QuestConfig>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut talked_to_events: EventWriter<TalkedToEvent>,
+    talkers: Query<(Entity, &Transform, &Collider), With<Manfred>>,
+    npcs: Query<(&Transform, &Collider, &Npc)>,
+) {
+    if !keyboard_input.just_pressed(config.talk_key) {
+        return;
+    }
+
+    for (talker, talker_transform, talker_collider) in talkers.iter() {
+        let talker_center = talker_collider.center(talker_transform.translation.truncate());
+
+        let nearest = npcs
+            .iter()
+            .map(|(transform, collider, npc)| {
+                let center = collider.center(transform.translation.truncate());
+                (talker_center.distance(center), npc)
+            })
+            .filter(|(distance, _)| *distance <= config.talk_distance)
+            .min_by(|(distance, _), (other_distance, _)| {
+                distance.partial_cmp(other_distance).unwrap()
+            });
+
+        if let Some((_, npc)) = nearest {
+            talked_to_events.send(TalkedToEvent {
+                talker,
+                npc: npc.name.clone(),
+            });
+        }
+    }
+}
+
+/// Feeds conversations, pickups and entered trigger zones into the quest log and hands out the
+/// rewards of finished quests.
+#[allow(clippy::too_many_arguments)]
+pub fn quest_progress_system(
+    definitions: Res<QuestDefinitions>,
+    item_definitions: Option<Res<ItemDefinitions>>,
+    mut log: ResMut<QuestLog>,
+    mut flags: ResMut<WorldFlags>,
+    mut talked_to_events: EventReader<TalkedToEvent>,
+    mut picked_up_events: EventReader<ItemPickedUpEvent>,
+    mut trigger_events: EventReader<TriggerEnteredEvent>,
+    manfreds: Query<Entity, With<Manfred>>,
+    mut inventories: Query<&mut Inventory, With<Manfred>>,
+) {
+    let is_manfred = |entity: Entity| manfreds.get(entity).is_ok();
+
+    let events: Vec<ObjectiveEvent> = talked_to_events
+        .iter()
+        .filter(|event| is_manfred(event.talker))
+        .map(|event| ObjectiveEvent::TalkedTo(event.npc.clone()))
+        .chain(
+            picked_up_events
+                .iter()
+                .filter(|event| is_manfred(event.picker))
+                .map(|event| ObjectiveEvent::Collected {
+                    item: event.item.clone(),
+                    count: event.count,
+                }),
+        )
+        .chain(
+            trigger_events
+                .iter()
+                .filter(|event| is_manfred(event.entity))
+                .map(|event| ObjectiveEvent::Reached(event.zone.clone())),
+        )
+        .collect();
+
+    for event in events.iter() {
+        for id in log.record(&definitions, event) {
+            let quest = definitions.get(&id).unwrap();
+            info!("finished quest '{}'", quest.title);
+
+            for reward in quest.rewards.iter() {
+                match reward {
+                    Reward::Flag { name, value } => flags.set(name, *value),
+                    Reward::Item { item, count } => {
+                        let max_stack = item_definitions
+                            .as_ref()
+                            .and_then(|definitions| definitions.get(item))
+                            .map_or(1, |definition| definition.max_stack);
+
+                        for mut inventory in inventories.iter_mut() {
+                            let left_over = inventory.add(item, *count, max_stack);
+                            if left_over > 0 {
+                                warn!("{} {} did not fit into the inventory", left_over, item);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn add_quest_log_panel(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let hidden = Visible {
+        is_visible: false,
+        is_transparent: true,
+    };
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(420.0), Val::Auto),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(20.0),
+                    left: Val::Px(20.0),
+                    ..Default::default()
+                },
+                padding: Rect::all(Val::Px(8.0)),
+                ..Default::default()
+            },
+            material: materials.add(Color::rgba(0.05, 0.05, 0.1, 0.85).into()),
+            visible: hidden.clone(),
+            ..Default::default()
+        })
+        .insert(QuestLogPanel)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        "",
+                        TextStyle {
+                            font: asset_server.load("fonts/DejaVuSansMono.ttf"),
+                            font_size: 16.0,
+                            color: Color::WHITE,
+                        },
+                        Default::default(),
+                    ),
+                    visible: hidden,
+                    ..Default::default()
+                })
+                .insert(QuestLogText);
+        });
+}
+
+pub fn toggle_quest_log_system(
+    config: Res<QuestConfig>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<&mut Visible, Or<(With<QuestLogPanel>, With<QuestLogText>)>>,
+) {
+    if keyboard_input.just_pressed(config.toggle_key) {
+        for mut visible in query.iter_mut() {
+            visible.is_visible = !visible.is_visible;
+        }
+    }
+}
+
+pub fn quest_log_text_system(
+    definitions: Res<QuestDefinitions>,
+    log: Res<QuestLog>,
+    mut texts: Query<&mut Text, With<QuestLogText>>,
+) {
+    if !log.is_changed() {
+        return;
+    }
+
+    let value = describe_quest_log(&log, &definitions);
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+}
+
+fn describe_quest_log(log: &QuestLog, definitions: &QuestDefinitions) -> String {
+    let mut active = vec!["Active quests".to_string()];
+    let mut finished = vec!["Finished quests".to_string()];
+
+    for progress in log.iter() {
+        let quest = match definitions.get(&progress.id) {
+            Some(quest) => quest,
+            None => continue,
+        };
+
+        match progress.status {
+            QuestStatus::Active => {
+                let stage = &quest.stages[progress.stage];
+                active.push(format!("  {}: {}", quest.title, stage.description));
+
+                for (objective, value) in stage.objectives.iter().zip(progress.objectives.iter()) {
+                    active.push(format!(
+                        "    {} {}/{}",
+                        objective.describe(),
+                        value,
+                        objective.required()
+                    ));
+                }
+            }
+            QuestStatus::Finished => finished.push(format!("  {}", quest.title)),
+        }
+    }
+
+    active.extend(finished);
+    active.join("\n")
+}
+
+pub fn flag_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let mut flags = world
+        .get_resource_mut::<WorldFlags>()
+        .ok_or("there are no world flags")?;
+
+    match (args.get(0), args.parse_optional::<i32>(1, "value")?) {
+        (None, _) => Ok(flags
+            .iter()
+            .map(|(name, value)| format!("{} = {}", name, value))
+            .collect::<Vec<_>>()
+            .join("\n")),
+        (Some(name), None) => Ok(format!("{} = {}", name, flags.get(name))),
+        (Some(name), Some(value)) => {
+            flags.set(name, value);
+            Ok(format!("{} = {}", name, value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{IntoSystem, ParallelSystemDescriptorCoercion, Transform, Vec3};
+
+    use crate::component::collider::Collider;
+    use crate::component::inventory::Inventory;
+    use crate::component::manfred::Manfred;
+    use crate::component::npc::Npc;
+    use crate::data::quest::{Objective, QuestDefinition, QuestDefinitions, QuestStage, Reward};
+    use crate::event::interaction::TalkedToEvent;
+    use crate::event::item::ItemPickedUpEvent;
+    use crate::event::trigger::TriggerEnteredEvent;
+    use crate::plugin::inventory::InventoryConfig;
+    use crate::plugin::quest::QuestConfig;
+    use crate::resource::quest_log::{QuestLog, QuestStatus};
+    use crate::resource::world_flags::WorldFlags;
+    use crate::system::position::FromXAndY;
+    use crate::system::quest::{
+        describe_quest_log, quest_progress_system, start_quests_system, talk_system,
+    };
+    use crate::test_support::test_world::{TestWorld, TestWorldBuilder};
+
+    fn definitions() -> QuestDefinitions {
+        QuestDefinitions {
+            quests: vec![
+                QuestDefinition {
+                    id: "apples".to_string(),
+                    title: "Apples".to_string(),
+                    requires_flag: None,
+                    stages: vec![
+                        QuestStage {
+                            description: "Pick apples".to_string(),
+                            objectives: vec![Objective::Collect {
+                                item: "apple".to_string(),
+                                count: 2,
+                            }],
+                        },
+                        QuestStage {
+                            description: "Bring them to the farmer".to_string(),
+                            objectives: vec![Objective::TalkTo("Farmer".to_string())],
+                        },
+                    ],
+                    rewards: vec![
+                        Reward::Item {
+                            item: "stick".to_string(),
+                            count: 1,
+                        },
+                        Reward::Flag {
+                            name: "apples_delivered".to_string(),
+                            value: 1,
+                        },
+                    ],
+                },
+                QuestDefinition {
+                    id: "well".to_string(),
+                    title: "Well".to_string(),
+                    requires_flag: Some("apples_delivered".to_string()),
+                    stages: vec![QuestStage {
+                        description: "Find the well".to_string(),
+                        objectives: vec![Objective::Reach("old well".to_string())],
+                    }],
+                    rewards: vec![],
+                },
+            ],
+        }
+    }
+
+    fn quest_world() -> TestWorld {
+        TestWorldBuilder::new()
+            .with_resource(QuestConfig::default())
+            .with_resource(definitions())
+            .with_resource(QuestLog::default())
+            .with_resource(WorldFlags::default())
+            .with_event::<TalkedToEvent>()
+            .with_event::<ItemPickedUpEvent>()
+            .with_event::<TriggerEnteredEvent>()
+            .with_system(start_quests_system.system().label("start"))
+            .with_system(talk_system.system().label("talk"))
+            .with_system(quest_progress_system.system().after("start").after("talk"))
+            .with_entity(
+                "manfred",
+                (
+                    Manfred::default(),
+                    Inventory::new(4),
+                    Collider::new(30.0, 20.0),
+                    Transform::from_translation(Vec3::compute_from_x_y(0.0, 0.0)),
+                ),
+            )
+            .with_entity(
+                "farmer",
+                (
+                    Npc {
+                        name: "Farmer".to_string(),
+                    },
+                    Collider::new(30.0, 20.0),
+                    Transform::from_translation(Vec3::compute_from_x_y(40.0, 0.0)),
+                ),
+            )
+            .build()
+    }
+
+    fn pick_up_apple(world: &mut TestWorld) {
+        let manfred = world.entity("manfred");
+        world.send_event(ItemPickedUpEvent {
+            picker: manfred,
+            item: "apple".to_string(),
+            count: 1,
+        });
+        world.run_step();
+    }
+
+    fn status_of(world: &TestWorld, id: &str) -> Option<QuestStatus> {
+        world
+            .resource::<QuestLog>()
+            .get(id)
+            .map(|progress| progress.status)
+    }
+
+    #[test]
+    fn quests_without_required_flag_start_right_away() {
+        let mut world = quest_world();
+
+        world.run_step();
+
+        assert_eq!(Some(QuestStatus::Active), status_of(&world, "apples"));
+        assert_eq!(None, status_of(&world, "well"));
+    }
+
+    #[test]
+    fn talking_before_the_objective_is_due_does_not_count() {
+        let mut world = quest_world();
+        world.run_step();
+
+        world.press(QuestConfig::default().talk_key);
+        world.run_step();
+
+        let progress = world.resource::<QuestLog>().get("apples").unwrap();
+        assert_eq!(0, progress.stage);
+    }
+
+    #[test]
+    fn talking_and_picking_up_items_use_different_keys() {
+        assert_ne!(
+            InventoryConfig::default().interact_key,
+            QuestConfig::default().talk_key
+        );
+    }
+
+    #[test]
+    fn finishing_a_quest_grants_rewards_and_starts_follow_ups() {
+        let mut world = quest_world();
+        world.run_step();
+
+        pick_up_apple(&mut world);
+        pick_up_apple(&mut world);
+        assert_eq!(1, world.resource::<QuestLog>().get("apples").unwrap().stage);
+
+        world.press(QuestConfig::default().talk_key);
+        world.run_step();
+
+        assert_eq!(Some(QuestStatus::Finished), status_of(&world, "apples"));
+        assert_eq!(1, world.get::<Inventory>("manfred").count("stick"));
+        assert!(world.resource::<WorldFlags>().is_set("apples_delivered"));
+
+        world.run_step();
+
+        assert_eq!(Some(QuestStatus::Active), status_of(&world, "well"));
+    }
+
+    #[test]
+    fn entering_trigger_zones_advances_reach_objectives() {
+        let mut world = quest_world();
+        world
+            .resource_mut::<WorldFlags>()
+            .set("apples_delivered", 1);
+        world.run_step();
+
+        let manfred = world.entity("manfred");
+        let farmer = world.entity("farmer");
+        world.send_event(TriggerEnteredEvent {
+            entity: manfred,
+            // any entity will do, the quests only look at the name of the zone
+            trigger: farmer,
+            zone: "old well".to_string(),
+        });
+        world.run_step();
+
+        assert_eq!(Some(QuestStatus::Finished), status_of(&world, "well"));
+    }
+
+    #[test]
+    fn quest_log_lists_objectives_of_active_quests() {
+        let definitions = definitions();
+        let mut log = QuestLog::default();
+        log.start(&definitions.quests[0]);
+
+        assert_eq!(
+            "Active quests\n  Apples: Pick apples\n    collect 2 apple 0/2\nFinished quests",
+            describe_quest_log(&log, &definitions)
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use std::fs;
+use std::path::Path;
+
+use bevy::prelude::{Transform, World};
+use ron::ser::PrettyConfig;
+
+use crate::component::equipment::Equipment;
+use crate::component::inventory::Inventory;
+use crate::component::manfred::Manfred;
+use crate::component::player::Player;
+use crate::component::Position;
+use crate::data::load_ron_file;
+use crate::data::save::{PlayerSave, SaveGame};
+use crate::plugin::save::SaveConfig;
+use crate::resource::console::ConsoleArgs;
+use crate::resource::level_manager::LevelManager;
+use crate::resource::quest_log::QuestLog;
+use crate::resource::world_clock::WorldClock;
+use crate::resource::world_flags::WorldFlags;
+use crate::system::level::switch_level;
+
+pub fn capture_save_game(world: &mut World) -> Result<SaveGame, String> {
+    let level = world
+        .get_resource::<LevelManager>()
+        .ok_or("there is no level loaded")?
+        .current()
+        .to_string();
+
+    let mut query = world.query::<(
+        &Manfred,
+        &Transform,
+        Option<&Player>,
+        Option<&Inventory>,
+        Option<&Equipment>,
+    )>();
+    let mut players: Vec<PlayerSave> = query
+        .iter(world)
+        .map(
+            |(manfred, transform, player, inventory, equipment)| PlayerSave {
+                index: player.map_or(0, |player| player.index),
+                position: transform.translation.truncate(),
+                view_direction: manfred.view_direction,
+                inventory: inventory.cloned(),
+                equipment: equipment.cloned(),
+            },
+        )
+        .collect();
+    if players.is_empty() {
+        return Err("there is no Manfred".to_string());
+    }
+    players.sort_by_key(|player| player.index);
+
+    Ok(SaveGame {
+        level,
+        players,
+        flags: world
+            .get_resource::<WorldFlags>()
+            .cloned()
+            .unwrap_or_default(),
+        quests: world
+            .get_resource::<QuestLog>()
+            .cloned()
+            .unwrap_or_default(),
+        clock: world
+            .get_resource::<WorldClock>()
+            .cloned()
+            .unwrap_or_default(),
+    })
+}
+
+/// Switches to the saved level and puts everything back as it was saved.
+pub fn restore_save_game(world: &mut World, save_game: &SaveGame) -> Result<(), String> {
+    switch_level(world, &save_game.level, None)?;
+    apply_save_game(world, save_game);
+    Ok(())
+}
+
+/// Restores the Manfreds and the resources from the save game, but not the level. Every Manfred
+/// gets what was saved for the same player, those of players that were not saved stay as they
+/// are.
+fn apply_save_game(world: &mut World, save_game: &SaveGame) {
+    let mut query = world.query::<(
+        &mut Manfred,
+        &mut Transform,
+        Option<&mut Position>,
+        Option<&Player>,
+        Option<&mut Inventory>,
+        Option<&mut Equipment>,
+    )>();
+    for (mut manfred, mut transform, position, player, inventory, equipment) in
+        query.iter_mut(world)
+    {
+        let index = player.map_or(0, |player| player.index);
+        let saved = match save_game.players.iter().find(|saved| saved.index == index) {
+            Some(saved) => saved,
+            None => continue,
+        };
+
+        let target = Position::new(
+            saved.position.x.round() as i32,
+            saved.position.y.round() as i32,
+        );
+        transform.translation = target.translation();
+        if let Some(mut position) = position {
+            *position = target;
+        }
+        manfred.view_direction = saved.view_direction;
+        if let (Some(mut inventory), Some(saved)) = (inventory, &saved.inventory) {
+            *inventory = saved.clone();
+        }
+        if let (Some(mut equipment), Some(saved)) = (equipment, &saved.equipment) {
+            *equipment = saved.clone();
+        }
+    }
+
+    world.insert_resource(save_game.flags.clone());
+    world.insert_resource(save_game.quests.clone());
+    world.insert_resource(save_game.clock.clone());
+}
+
+pub fn save_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let path = save_path(world, args);
+    let save_game = capture_save_game(world)?;
+
+    let serialized = ron::ser::to_string_pretty(&save_game, PrettyConfig::default())
+        .map_err(|error| format!("cannot serialize the save game: {}", error))?;
+    if let Some(directory) = Path::new(&path).parent() {
+        fs::create_dir_all(directory)
+            .map_err(|error| format!("cannot create {}: {}", directory.display(), error))?;
+    }
+    fs::write(&path, serialized).map_err(|error| format!("cannot write {}: {}", path, error))?;
+
+    Ok(format!("saved the game to {}", path))
+}
+
+pub fn load_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let path = save_path(world, args);
+    let save_game = load_ron_file::<SaveGame>(&path)?;
+
+    restore_save_game(world, &save_game)?;
+
+    Ok(format!("loaded the game from {}", path))
+}
+
+fn save_path(world: &World, args: &ConsoleArgs) -> String {
+    match args.get(0) {
+        Some(path) => path.to_string(),
+        None => world.get_resource::<SaveConfig>().unwrap().path.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{Transform, Vec2, Vec3};
+
+    use crate::component::equipment::Equipment;
+    use crate::component::inventory::Inventory;
+    use crate::component::manfred::Manfred;
+    use crate::component::player::Player;
+    use crate::data::item::EquipmentSlot;
+    use crate::resource::level_manager::LevelManager;
+    use crate::resource::quest_log::QuestLog;
+    use crate::resource::world_clock::WorldClock;
+    use crate::resource::world_flags::WorldFlags;
+    use crate::system::save::{apply_save_game, capture_save_game};
+    use crate::test_support::test_world::{TestWorld, TestWorldBuilder};
+    use crate::types::Direction;
+
+    fn world() -> TestWorld {
+        TestWorldBuilder::new()
+            .with_resource(LevelManager::new("assets/levels/meadow.ron"))
+            .with_resource(WorldFlags::default())
+            .with_resource(QuestLog::default())
+            .with_resource(WorldClock::new(0, 8.0))
+            .with_entity(
+                "manfred",
+                (
+                    Manfred::default(),
+                    Transform::from_translation(Vec3::new(30.0, 40.0, 0.0)),
+                    Inventory::new(4),
+                    Equipment::default(),
+                ),
+            )
+            .build()
+    }
+
+    #[test]
+    fn save_game_captures_manfred_and_the_world() {
+        let mut world = world();
+        world.get_mut::<Inventory>("manfred").add("stick", 2, 10);
+        world.resource_mut::<WorldFlags>().set("well_visited", 1);
+        world.resource_mut::<WorldClock>().advance(3600.0);
+
+        let save_game = capture_save_game(world.world_mut()).unwrap();
+
+        assert_eq!("assets/levels/meadow.ron", save_game.level);
+        assert_eq!(1, save_game.players.len());
+        assert_eq!(Vec2::new(30.0, 40.0), save_game.players[0].position);
+        assert_eq!(
+            Some(2),
+            save_game.players[0]
+                .inventory
+                .as_ref()
+                .map(|inventory| inventory.count("stick"))
+        );
+        assert!(save_game.flags.is_set("well_visited"));
+        assert_eq!(9.0, save_game.clock.hour());
+    }
+
+    #[test]
+    fn applying_a_save_game_restores_what_was_captured() {
+        let mut world = world();
+        world.get_mut::<Inventory>("manfred").add("apple", 1, 10);
+        world.get_mut::<Manfred>("manfred").view_direction = Direction::Up;
+        world
+            .get_mut::<Equipment>("manfred")
+            .put_on(EquipmentSlot::Back, "backpack");
+        world.resource_mut::<WorldClock>().advance(12.0 * 3600.0);
+        let save_game = capture_save_game(world.world_mut()).unwrap();
+
+        let mut other_world = self::world();
+        apply_save_game(other_world.world_mut(), &save_game);
+
+        assert_eq!(
+            save_game,
+            capture_save_game(other_world.world_mut()).unwrap()
+        );
+        assert_eq!(
+            Direction::Up,
+            other_world.get::<Manfred>("manfred").view_direction
+        );
+        assert_eq!(
+            Some("backpack"),
+            other_world
+                .get::<Equipment>("manfred")
+                .get(EquipmentSlot::Back)
+        );
+    }
+
+    fn co_op_world(players: usize) -> TestWorld {
+        let names = ["first", "second", "third"];
+        let mut builder = TestWorldBuilder::new()
+            .with_resource(LevelManager::new("assets/levels/meadow.ron"))
+            .with_resource(WorldFlags::default())
+            .with_resource(QuestLog::default());
+        for (index, name) in names.iter().enumerate().take(players) {
+            builder = builder.with_entity(
+                *name,
+                (
+                    Manfred::default(),
+                    Player { index },
+                    Transform::from_translation(Vec3::new(10.0 * index as f32, 0.0, 0.0)),
+                    Inventory::new(4),
+                ),
+            );
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn every_player_gets_back_their_own_manfred() {
+        let mut world = co_op_world(2);
+        world.get_mut::<Inventory>("second").add("stick", 2, 10);
+        world.get_mut::<Manfred>("second").view_direction = Direction::Left;
+        world.get_mut::<Transform>("second").translation = Vec3::new(70.0, -20.0, 0.0);
+        let save_game = capture_save_game(world.world_mut()).unwrap();
+
+        let mut other_world = co_op_world(3);
+        other_world
+            .get_mut::<Inventory>("third")
+            .add("apple", 1, 10);
+        apply_save_game(other_world.world_mut(), &save_game);
+
+        assert_eq!(
+            vec![0, 1],
+            save_game
+                .players
+                .iter()
+                .map(|player| player.index)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(0, other_world.get::<Inventory>("first").count("stick"));
+        assert_eq!(2, other_world.get::<Inventory>("second").count("stick"));
+        assert_eq!(
+            Direction::Left,
+            other_world.get::<Manfred>("second").view_direction
+        );
+        other_world.assert_position("second", 70.0, -20.0);
+        // nothing was saved for the third player
+        assert_eq!(1, other_world.get::<Inventory>("third").count("apple"));
+        other_world.assert_position("third", 20.0, 0.0);
+    }
+}
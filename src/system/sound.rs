@@ -0,0 +1,366 @@
+use std::cmp::Ordering;
+
+use bevy::log::error;
+use bevy::prelude::{
+    AssetServer, EventReader, EventWriter, Local, Query, Res, ResMut, Transform, Vec2, World,
+};
+use bevy_kira_audio::{Audio, AudioChannel};
+
+use crate::component::collider::Collider;
+use crate::component::surface::SurfaceArea;
+use crate::data::level::LevelDefinition;
+use crate::data::load_ron_file;
+use crate::data::sound::FootstepDefinitions;
+use crate::event::animation::AnimationEvent;
+use crate::event::sound::PlaySoundEvent;
+use crate::plugin::sound::SoundConfig;
+use crate::resource::console::ConsoleArgs;
+use crate::resource::sound::{AmbientTrack, SoundVolumes};
+use crate::types::surface::Surface;
+
+/// The name of the animation event sent when a foot touches the ground.
+pub const FOOTSTEP: &str = "footstep";
+
+const EFFECTS_CHANNEL: &str = "effects";
+const AMBIENT_CHANNEL: &str = "ambient";
+
+pub fn load_footstep_definitions(world: &mut World) {
+    let path = world
+        .get_resource::<SoundConfig>()
+        .unwrap()
+        .footsteps_path
+        .clone();
+
+    let definitions = load_ron_file::<FootstepDefinitions>(&path).unwrap_or_else(|message| {
+        error!("{}", message);
+        FootstepDefinitions::default()
+    });
+
+    world.insert_resource(definitions);
+}
+
+/// Turns footstep animation events into footstep sounds for the surface under the walker's feet.
+pub fn footstep_system(
+    definitions: Res<FootstepDefinitions>,
+    level: Option<Res<LevelDefinition>>,
+    mut animation_events: EventReader<AnimationEvent>,
+    mut sound_events: EventWriter<PlaySoundEvent>,
+    walkers: Query<(&Transform, Option<&Collider>)>,
+    areas: Query<(&Transform, &SurfaceArea)>,
+    mut steps: Local<usize>,
+) {
+    let default_surface = level.map_or(Surface::default(), |level| level.default_surface);
+
+    for event in animation_events
+        .iter()
+        .filter(|event| event.name == FOOTSTEP)
+    {
+        let (transform, collider) = match walkers.get(event.entity) {
+            Ok(walker) => walker,
+            Err(_) => continue,
+        };
+        let position = transform.translation.truncate();
+        let feet = collider.map_or(position, |collider| collider.center(position));
+
+        let surface = surface_at(
+            feet,
+            areas
+                .iter()
+                .map(|(transform, area)| (transform.translation.truncate(), area)),
+            default_surface,
+        );
+
+        let sounds = definitions.sounds_for(surface);
+        if !sounds.is_empty() {
+            sound_events.send(PlaySoundEvent {
+                sound: sounds[*steps % sounds.len()].clone(),
+            });
+            *steps += 1;
+        }
+    }
+}
+
+/// The surface of the smallest area containing the point, so e.g. a stone floor in the middle of a
+/// dirt path wins over the path.
+pub fn surface_at<'a>(
+    point: Vec2,
+    areas: impl Iterator<Item = (Vec2, &'a SurfaceArea)>,
+    default_surface: Surface,
+) -> Surface {
+    areas
+        .filter(|(position, area)| area.contains(*position, point))
+        .min_by(|(_, a), (_, b)| {
+            (a.size.x * a.size.y)
+                .partial_cmp(&(b.size.x * b.size.y))
+                .unwrap_or(Ordering::Equal)
+        })
+        .map_or(default_surface, |(_, area)| area.surface)
+}
+
+/// Follows the ambient track of the current level.
+pub fn ambient_track_system(level: Option<Res<LevelDefinition>>, mut track: ResMut<AmbientTrack>) {
+    if let Some(level) = level {
+        if level.is_changed() && track.sound != level.ambient {
+            track.sound = level.ambient.clone();
+        }
+    }
+}
+
+/// Sound is only played when the audio plugin was added, headless apps stay silent.
+pub fn play_sounds_system(
+    mut sound_events: EventReader<PlaySoundEvent>,
+    audio: Option<Res<Audio>>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in sound_events.iter() {
+        if let Some(audio) = audio.as_ref() {
+            audio.play_in_channel(
+                asset_server.load(event.sound.as_str()),
+                &AudioChannel::new(EFFECTS_CHANNEL.to_string()),
+            );
+        }
+    }
+}
+
+pub fn play_ambient_system(
+    track: Res<AmbientTrack>,
+    audio: Option<Res<Audio>>,
+    asset_server: Res<AssetServer>,
+) {
+    if let (true, Some(audio)) = (track.is_changed(), audio) {
+        let channel = AudioChannel::new(AMBIENT_CHANNEL.to_string());
+        audio.stop_channel(&channel);
+        if let Some(sound) = &track.sound {
+            audio.play_looped_in_channel(asset_server.load(sound.as_str()), &channel);
+        }
+    }
+}
+
+pub fn volume_system(volumes: Res<SoundVolumes>, audio: Option<Res<Audio>>) {
+    if let (true, Some(audio)) = (volumes.is_changed(), audio) {
+        audio.set_volume_in_channel(
+            volumes.effects,
+            &AudioChannel::new(EFFECTS_CHANNEL.to_string()),
+        );
+        audio.set_volume_in_channel(
+            volumes.ambient,
+            &AudioChannel::new(AMBIENT_CHANNEL.to_string()),
+        );
+    }
+}
+
+pub fn volume_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let mut volumes = world
+        .get_resource_mut::<SoundVolumes>()
+        .ok_or("there are no sound volumes")?;
+
+    let value = args.parse_optional::<f32>(1, "volume")?;
+    if let Some(value) = value {
+        if !(0.0..=1.0).contains(&value) {
+            return Err(format!("the volume must be from 0 to 1, not {}", value));
+        }
+    }
+
+    let volume = match args.get(0) {
+        None => {
+            return Ok(format!(
+                "effects = {}\nambient = {}",
+                volumes.effects, volumes.ambient
+            ))
+        }
+        Some(EFFECTS_CHANNEL) => &mut volumes.effects,
+        Some(AMBIENT_CHANNEL) => &mut volumes.ambient,
+        Some(channel) => {
+            return Err(format!(
+                "unknown channel '{}', known channels are: effects, ambient",
+                channel
+            ))
+        }
+    };
+    if let Some(value) = value {
+        *volume = value;
+    }
+
+    Ok(format!("{} = {}", args.get(0).unwrap(), volume))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{IntoSystem, Transform, Vec2, Vec3};
+
+    use crate::component::collider::Collider;
+    use crate::component::surface::SurfaceArea;
+    use crate::data::level::LevelDefinition;
+    use crate::data::sound::{FootstepDefinitions, SurfaceSounds};
+    use crate::event::animation::AnimationEvent;
+    use crate::event::sound::PlaySoundEvent;
+    use crate::resource::sound::AmbientTrack;
+    use crate::system::position::FromXAndY;
+    use crate::system::sound::{ambient_track_system, footstep_system, surface_at, FOOTSTEP};
+    use crate::test_support::test_world::{TestWorld, TestWorldBuilder};
+    use crate::types::surface::Surface;
+
+    fn definitions() -> FootstepDefinitions {
+        FootstepDefinitions {
+            surfaces: vec![
+                SurfaceSounds {
+                    surface: Surface::Grass,
+                    sounds: vec!["grass_1.wav".to_string(), "grass_2.wav".to_string()],
+                },
+                SurfaceSounds {
+                    surface: Surface::Wood,
+                    sounds: vec!["wood.wav".to_string()],
+                },
+            ],
+        }
+    }
+
+    fn world_with_manfred_at(x: f32, y: f32) -> TestWorld {
+        TestWorldBuilder::new()
+            .with_resource(definitions())
+            .with_resource(LevelDefinition::default())
+            .with_event::<AnimationEvent>()
+            .with_event::<PlaySoundEvent>()
+            .with_system(footstep_system.system())
+            .with_entity(
+                "manfred",
+                (
+                    Transform::from_translation(Vec3::compute_from_x_y(x, y)),
+                    Collider::new(30.0, 20.0).with_offset(0.0, -30.0),
+                ),
+            )
+            .with_entity(
+                "floor",
+                (
+                    Transform::from_translation(Vec3::compute_from_x_y(100.0, 0.0)),
+                    SurfaceArea {
+                        surface: Surface::Wood,
+                        size: Vec2::new(50.0, 50.0),
+                    },
+                ),
+            )
+            .build()
+    }
+
+    fn step(world: &mut TestWorld) -> Vec<String> {
+        let entity = world.entity("manfred");
+        world.send_event(AnimationEvent {
+            entity,
+            name: FOOTSTEP.to_string(),
+            frame: 2,
+        });
+        world.run_step();
+
+        world
+            .events::<PlaySoundEvent>()
+            .into_iter()
+            .map(|event| event.sound)
+            .collect()
+    }
+
+    #[test]
+    fn footsteps_alternate_between_the_sounds_of_the_surface() {
+        let mut world = world_with_manfred_at(0.0, 0.0);
+
+        assert_eq!(vec!["grass_1.wav"], step(&mut world));
+        assert_eq!(vec!["grass_2.wav"], step(&mut world));
+    }
+
+    #[test]
+    fn footsteps_sound_like_the_surface_under_manfreds_feet() {
+        // his feet are 30 below his center, on the wooden floor
+        let mut world = world_with_manfred_at(100.0, 30.0);
+
+        assert_eq!(vec!["wood.wav"], step(&mut world));
+    }
+
+    #[test]
+    fn other_animation_events_make_no_sound() {
+        let mut world = world_with_manfred_at(0.0, 0.0);
+        let entity = world.entity("manfred");
+
+        world.send_event(AnimationEvent {
+            entity,
+            name: "wave".to_string(),
+            frame: 2,
+        });
+        world.run_step();
+
+        assert!(world.events::<PlaySoundEvent>().is_empty());
+    }
+
+    #[test]
+    fn smallest_area_decides_the_surface() {
+        let path = SurfaceArea {
+            surface: Surface::Dirt,
+            size: Vec2::new(200.0, 40.0),
+        };
+        let stones = SurfaceArea {
+            surface: Surface::Stone,
+            size: Vec2::new(20.0, 20.0),
+        };
+        let areas = vec![(Vec2::ZERO, &path), (Vec2::new(50.0, 0.0), &stones)];
+
+        assert_eq!(
+            Surface::Stone,
+            surface_at(
+                Vec2::new(50.0, 5.0),
+                areas.clone().into_iter(),
+                Surface::Grass
+            )
+        );
+        assert_eq!(
+            Surface::Dirt,
+            surface_at(
+                Vec2::new(-50.0, 5.0),
+                areas.clone().into_iter(),
+                Surface::Grass
+            )
+        );
+        assert_eq!(
+            Surface::Grass,
+            surface_at(Vec2::new(0.0, 50.0), areas.into_iter(), Surface::Grass)
+        );
+    }
+
+    #[test]
+    fn areas_without_a_measurable_size_do_not_panic() {
+        let line = SurfaceArea {
+            surface: Surface::Stone,
+            size: Vec2::new(f32::INFINITY, 0.0),
+        };
+        let path = SurfaceArea {
+            surface: Surface::Dirt,
+            size: Vec2::new(200.0, 40.0),
+        };
+        let areas = vec![(Vec2::ZERO, &line), (Vec2::ZERO, &path)];
+
+        let surface = surface_at(Vec2::ZERO, areas.into_iter(), Surface::Grass);
+
+        assert_ne!(Surface::Grass, surface);
+    }
+
+    #[test]
+    fn ambient_track_follows_the_level() {
+        let mut world = TestWorldBuilder::new()
+            .with_resource(LevelDefinition {
+                ambient: Some("sounds/ambient/meadow.wav".to_string()),
+                ..Default::default()
+            })
+            .with_resource(AmbientTrack::default())
+            .with_system(ambient_track_system.system())
+            .build();
+
+        world.run_step();
+        assert_eq!(
+            Some("sounds/ambient/meadow.wav".to_string()),
+            world.resource::<AmbientTrack>().sound
+        );
+
+        world
+            .world_mut()
+            .insert_resource(LevelDefinition::default());
+        world.run_step();
+        assert_eq!(None, world.resource::<AmbientTrack>().sound);
+    }
+}
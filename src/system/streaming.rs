@@ -0,0 +1,109 @@
+use std::path::Path;
+
+use bevy::log::error;
+use bevy::prelude::{Commands, Entity, Query, Res, ResMut, Transform, With, World};
+use bevy::tasks::IoTaskPool;
+
+use crate::component::chunk::ChunkObject;
+use crate::component::manfred::Manfred;
+use crate::data::level::LevelDefinition;
+use crate::data::load_ron_file;
+use crate::plugin::streaming::StreamingConfig;
+use crate::resource::streaming::LoadedChunks;
+use crate::system::level::spawn_objects;
+
+/// Starts reading the files of the chunks within the load radius around Manfred in the
+/// background.
+pub fn request_chunks_system(
+    config: Res<StreamingConfig>,
+    level: Res<LevelDefinition>,
+    task_pool: Res<IoTaskPool>,
+    mut chunks: ResMut<LoadedChunks>,
+    manfreds: Query<&Transform, With<Manfred>>,
+) {
+    let streaming = match &level.streaming {
+        Some(streaming) => streaming,
+        None => return,
+    };
+
+    for transform in manfreds.iter() {
+        let center = streaming.chunk_at(transform.translation.truncate());
+
+        for chunk in center.around(config.load_radius) {
+            if chunks.is_requested(chunk) {
+                continue;
+            }
+
+            let path = streaming.chunk_path(chunk);
+            if Path::new(&path).exists() {
+                let task = task_pool.spawn(async move { load_ron_file::<LevelDefinition>(&path) });
+                chunks.start_loading(chunk, task);
+            } else {
+                chunks.mark_loaded(chunk);
+            }
+        }
+    }
+}
+
+/// Spawns the objects of the chunks whose files were read.
+pub fn spawn_chunks_system(world: &mut World) {
+    let finished = match world.get_resource_mut::<LoadedChunks>() {
+        Some(mut chunks) => chunks.take_finished(),
+        None => return,
+    };
+
+    for (chunk, result) in finished {
+        match result {
+            Ok(definition) => {
+                for entity in spawn_objects(world, &definition) {
+                    world.entity_mut(entity).insert(ChunkObject { chunk });
+                }
+            }
+            Err(message) => error!("{}", message),
+        }
+
+        world
+            .get_resource_mut::<LoadedChunks>()
+            .unwrap()
+            .mark_loaded(chunk);
+    }
+}
+
+/// Despawns the chunks that are farther away from Manfred than the unload radius.
+pub fn unload_chunks_system(
+    mut commands: Commands,
+    config: Res<StreamingConfig>,
+    level: Res<LevelDefinition>,
+    mut chunks: ResMut<LoadedChunks>,
+    manfreds: Query<&Transform, With<Manfred>>,
+    chunk_objects: Query<(Entity, &ChunkObject)>,
+) {
+    let streaming = match &level.streaming {
+        Some(streaming) => streaming,
+        None => return,
+    };
+
+    let centers: Vec<_> = manfreds
+        .iter()
+        .map(|transform| streaming.chunk_at(transform.translation.truncate()))
+        .collect();
+    if centers.is_empty() {
+        return;
+    }
+
+    for chunk in chunks.requested() {
+        let out_of_reach = centers
+            .iter()
+            .all(|center| center.distance(chunk) > config.unload_radius);
+
+        if out_of_reach {
+            chunks.unload(chunk);
+
+            for (entity, chunk_object) in chunk_objects.iter() {
+                if chunk_object.chunk == chunk {
+                    commands.entity(entity).despawn();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use bevy::prelude::{Entity, EventWriter, Query, Res, Transform, With};
+
+use crate::component::collider::Collider;
+use crate::component::elevation::Elevation;
+use crate::component::manfred::Manfred;
+use crate::component::trigger::{Occupant, TriggerZone};
+use crate::event::trigger::{TriggerEnteredEvent, TriggerExitedEvent, TriggerStayEvent};
+use crate::resource::game_time::GameTime;
+use crate::system::position::ground_position;
+
+/// Compares where Manfred is at the end of the tick with the occupants of every trigger zone.
+/// Manfred counts as inside when the center of his collider, i.e. his feet, is inside. While he
+/// jumps, that is the spot on the ground below him.
+#[allow(clippy::type_complexity)]
+pub fn trigger_zone_system(
+    game_time: Res<GameTime>,
+    mut entered_events: EventWriter<TriggerEnteredEvent>,
+    mut stay_events: EventWriter<TriggerStayEvent>,
+    mut exited_events: EventWriter<TriggerExitedEvent>,
+    mut zones: Query<(Entity, &Transform, &mut TriggerZone)>,
+    activators: Query<(Entity, &Transform, Option<&Collider>, Option<&Elevation>), With<Manfred>>,
+) {
+    for (trigger, zone_transform, mut zone) in zones.iter_mut() {
+        let zone_position = zone_transform.translation.truncate();
+        let mut occupants = vec![];
+
+        for (entity, transform, collider, elevation) in activators.iter() {
+            let position = ground_position(transform, elevation);
+            let point = collider.map_or(position, |collider| collider.center(position));
+            if !zone.contains(zone_position, point) {
+                continue;
+            }
+
+            match zone
+                .occupants()
+                .iter()
+                .find(|occupant| occupant.entity == entity)
+            {
+                Some(occupant) => {
+                    let seconds_inside = occupant.seconds_inside + game_time.delta_seconds();
+                    stay_events.send(TriggerStayEvent {
+                        entity,
+                        trigger,
+                        zone: zone.name.clone(),
+                        seconds_inside,
+                    });
+                    occupants.push(Occupant {
+                        entity,
+                        seconds_inside,
+                    });
+                }
+                None => {
+                    entered_events.send(TriggerEnteredEvent {
+                        entity,
+                        trigger,
+                        zone: zone.name.clone(),
+                    });
+                    occupants.push(Occupant {
+                        entity,
+                        seconds_inside: 0.0,
+                    });
+                }
+            }
+        }
+
+        for occupant in zone.occupants() {
+            if !occupants
+                .iter()
+                .any(|inside| inside.entity == occupant.entity)
+            {
+                exited_events.send(TriggerExitedEvent {
+                    entity: occupant.entity,
+                    trigger,
+                    zone: zone.name.clone(),
+                });
+            }
+        }
+
+        // keep change detection quiet for the many zones nobody is in
+        if !occupants.is_empty() || !zone.occupants().is_empty() {
+            zone.set_occupants(occupants);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{IntoSystem, ParallelSystemDescriptorCoercion, Transform, Vec3};
+
+    use crate::component::collider::Collider;
+    use crate::component::elevation::{Elevation, JumpConfig};
+    use crate::component::manfred::Manfred;
+    use crate::component::trigger::TriggerZone;
+    use crate::component::Position;
+    use crate::event::trigger::{TriggerEnteredEvent, TriggerExitedEvent, TriggerStayEvent};
+    use crate::plugin::movement::MovementConfig;
+    use crate::system::elevation::elevation_system;
+    use crate::system::position::{move_positions_system, FromXAndY};
+    use crate::system::trigger::trigger_zone_system;
+    use crate::test_support::test_world::{TestWorld, TestWorldBuilder};
+    use crate::types::Direction;
+    use crate::Velocity;
+
+    /// Manfred at the origin, walking right with a constant speed of 10 per tick, and a zone
+    /// from x = 25 to x = 55.
+    fn world_with_zone_ahead() -> TestWorld {
+        let mut velocity = Velocity::new(10);
+        for _ in 0..10 {
+            velocity.accelerate(Direction::Right);
+        }
+
+        TestWorldBuilder::new()
+            .with_resource(MovementConfig::default())
+            .with_event::<TriggerEnteredEvent>()
+            .with_event::<TriggerStayEvent>()
+            .with_event::<TriggerExitedEvent>()
+            .with_system(elevation_system.system().label("elevation"))
+            .with_system(
+                move_positions_system
+                    .system()
+                    .label("update_position")
+                    .after("elevation"),
+            )
+            .with_system(trigger_zone_system.system().after("update_position"))
+            .with_entity(
+                "manfred",
+                (
+                    Manfred::default(),
+                    velocity,
+                    Elevation::default(),
+                    Position::new(0, 0),
+                    Collider::new(30.0, 20.0).with_offset(0.0, -30.0),
+                    Transform::from_translation(Vec3::compute_from_x_y(0.0, 0.0)),
+                ),
+            )
+            .with_entity(
+                "zone",
+                (
+                    TriggerZone::new("old well", 30.0, 40.0),
+                    Transform::from_translation(Vec3::compute_from_x_y(40.0, -30.0)),
+                ),
+            )
+            .build()
+    }
+
+    #[test]
+    fn entering_is_reported_in_the_tick_the_position_crosses_the_bounds() {
+        let mut world = world_with_zone_ahead();
+        let manfred = world.entity("manfred");
+        let zone = world.entity("zone");
+
+        world.run_steps(2);
+        assert!(world.events::<TriggerEnteredEvent>().is_empty());
+
+        world.run_step();
+
+        world.assert_position("manfred", 30.0, 0.0);
+        assert_eq!(
+            vec![TriggerEnteredEvent {
+                entity: manfred,
+                trigger: zone,
+                zone: "old well".to_string(),
+            }],
+            world.events::<TriggerEnteredEvent>()
+        );
+        assert!(world.events::<TriggerStayEvent>().is_empty());
+    }
+
+    #[test]
+    fn staying_is_reported_with_the_time_inside() {
+        let mut world = world_with_zone_ahead();
+        world.run_steps(3);
+
+        world.run_step();
+
+        let stay_events = world.events::<TriggerStayEvent>();
+        assert_eq!(1, stay_events.len());
+        assert!(stay_events[0].seconds_inside > 0.0);
+        assert!(world.events::<TriggerEnteredEvent>().is_empty());
+    }
+
+    #[test]
+    fn leaving_is_reported_once() {
+        let mut world = world_with_zone_ahead();
+        let manfred = world.entity("manfred");
+        let zone = world.entity("zone");
+        world.run_steps(5);
+        assert!(world.events::<TriggerExitedEvent>().is_empty());
+
+        world.run_step();
+
+        world.assert_position("manfred", 60.0, 0.0);
+        assert_eq!(
+            vec![TriggerExitedEvent {
+                entity: manfred,
+                trigger: zone,
+                zone: "old well".to_string(),
+            }],
+            world.events::<TriggerExitedEvent>()
+        );
+
+        world.run_step();
+
+        assert!(world.events::<TriggerExitedEvent>().is_empty());
+        assert!(world.get::<TriggerZone>("zone").occupants().is_empty());
+    }
+
+    #[test]
+    fn jumping_inside_a_zone_does_not_leave_it() {
+        let mut world = world_with_zone_ahead();
+        world.run_steps(3);
+        world.get_mut::<Velocity>("manfred").stop();
+        world
+            .get_mut::<Elevation>("manfred")
+            .jump(JumpConfig::default().speed);
+
+        let mut highest = 0;
+        for _ in 0..20 {
+            world.run_step();
+            highest = highest.max(world.get::<Elevation>("manfred").height());
+
+            assert!(world.events::<TriggerExitedEvent>().is_empty());
+            assert!(world.events::<TriggerEnteredEvent>().is_empty());
+        }
+        assert!(highest > 20, "highest: {}", highest);
+        assert!(world.get::<Elevation>("manfred").is_grounded());
+    }
+
+    #[test]
+    fn despawned_occupants_exit() {
+        let mut world = world_with_zone_ahead();
+        world.run_steps(3);
+        let manfred = world.entity("manfred");
+
+        world.world_mut().despawn(manfred);
+        world.run_step();
+
+        assert_eq!(1, world.events::<TriggerExitedEvent>().len());
+    }
+}
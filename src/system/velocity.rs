@@ -1,259 +1,440 @@
-use bevy::prelude::{Input, KeyCode, Query, Res};
-
-use crate::{Direction, Manfred, Velocity};
+use bevy::prelude::{Axis, GamepadAxis, GamepadButton, Input, KeyCode, Query, Res, With, World};
+
+use crate::component::elevation::Elevation;
+use crate::component::facing::{Facing, FacingConfig};
+use crate::component::fidget::Fidgeting;
+use crate::component::manfred::Manfred;
+use crate::component::player::Player;
+use crate::plugin::movement::MovementConfig;
+use crate::resource::console::ConsoleArgs;
+use crate::types::easing::{Easing, MovementProfile};
+use crate::types::Direction;
+use crate::Velocity;
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Left,
+    Direction::Right,
+    Direction::Up,
+    Direction::Down,
+];
 
 pub fn velocity_control_system(
+    config: Res<MovementConfig>,
     keyboard_input: Res<Input<KeyCode>>,
-    mut query: Query<(&mut Velocity, &mut Manfred)>,
+    gamepad_buttons: Option<Res<Input<GamepadButton>>>,
+    gamepad_axes: Option<Res<Axis<GamepadAxis>>>,
+    mut query: Query<(
+        &mut Velocity,
+        &mut Manfred,
+        Option<&mut Facing>,
+        Option<&mut Fidgeting>,
+        Option<&mut Elevation>,
+        Option<&Player>,
+    )>,
 ) {
-    if let Some((mut velocity, mut manfred)) = query.iter_mut().next() {
-        handle_acceleration(keyboard_input, &mut velocity, &mut manfred)
-    };
+    for (mut velocity, mut manfred, mut facing, fidgeting, elevation, player) in query.iter_mut() {
+        let index = player.map_or(0, |player| player.index);
+        if let Some(bindings) = config.players.get(index) {
+            let is_pressed = |direction| {
+                bindings.is_pressed(
+                    direction,
+                    &keyboard_input,
+                    gamepad_buttons.as_deref(),
+                    gamepad_axes.as_deref(),
+                )
+            };
+            let jump = bindings.jump_pressed(&keyboard_input, gamepad_buttons.as_deref());
+            if let Some(mut elevation) = elevation.filter(|_| jump) {
+                elevation.jump(config.jump.speed);
+            }
+            if let Some(mut fidgeting) = fidgeting {
+                if jump || DIRECTIONS.iter().any(|direction| is_pressed(*direction)) {
+                    fidgeting.reset();
+                }
+            }
+
+            // without a facing of its own, there is nothing to remember between frames
+            let mut default_facing = Facing::default();
+            handle_acceleration(
+                is_pressed,
+                &mut velocity,
+                &mut manfred.view_direction,
+                facing.as_deref_mut().unwrap_or(&mut default_facing),
+                &config.facing,
+            );
+        }
+    }
 }
 
-fn handle_acceleration(
-    keyboard_input: Res<Input<KeyCode>>,
+/// Accelerates in every direction that is pressed and slows down in all others. Turns to face
+/// the direction of movement, as far as `facing` allows.
+pub fn handle_acceleration(
+    is_pressed: impl Fn(Direction) -> bool,
     velocity: &mut Velocity,
-    manfred: &mut Manfred,
+    view_direction: &mut Direction,
+    facing: &mut Facing,
+    config: &FacingConfig,
 ) {
-    match keyboard_input.pressed(KeyCode::A) {
-        true => velocity.accelerate(Direction::Left),
-        false => velocity.decelerate(Direction::Left),
+    for direction in DIRECTIONS.iter() {
+        match is_pressed(*direction) {
+            true => velocity.accelerate(*direction),
+            false => velocity.decelerate(*direction),
+        }
     }
 
-    match keyboard_input.pressed(KeyCode::D) {
-        true => velocity.accelerate(Direction::Right),
-        false => velocity.decelerate(Direction::Right),
+    *view_direction = facing.update(*view_direction, velocity, is_pressed, config);
+}
+
+pub fn max_speed_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let max_speed = args.parse::<u16>(0, "speed")?;
+
+    let mut query = world.query_filtered::<&mut Velocity, With<Manfred>>();
+    let mut manfred_found = false;
+    for mut velocity in query.iter_mut(world) {
+        velocity.set_max_speed(max_speed);
+        manfred_found = true;
+    }
+
+    if !manfred_found {
+        return Err("there is no Manfred".to_string());
+    }
+
+    if let Some(mut config) = world.get_resource_mut::<MovementConfig>() {
+        config.max_speed = max_speed;
     }
 
-    match keyboard_input.pressed(KeyCode::W) {
-        true => velocity.accelerate(Direction::Up),
-        false => velocity.decelerate(Direction::Up),
+    Ok(format!("Manfred's max speed is now {}", max_speed))
+}
+
+/// Sets the easing curves of every Manfred, e.g. `easing QuadraticIn Table([0,800,1000])`.
+pub fn easing_command(world: &mut World, args: &ConsoleArgs) -> Result<String, String> {
+    let acceleration = args.parse::<Easing>(0, "acceleration")?;
+    let deceleration = args
+        .parse_optional::<Easing>(1, "deceleration")?
+        .unwrap_or_else(|| acceleration.clone());
+    let profile = MovementProfile {
+        acceleration,
+        deceleration,
+    };
+
+    let mut query = world.query_filtered::<&mut Velocity, With<Manfred>>();
+    let mut manfred_found = false;
+    for mut velocity in query.iter_mut(world) {
+        velocity.set_profile(profile.clone());
+        manfred_found = true;
     }
 
-    match keyboard_input.pressed(KeyCode::S) {
-        true => velocity.accelerate(Direction::Down),
-        false => velocity.decelerate(Direction::Down),
+    if !manfred_found {
+        return Err("there is no Manfred".to_string());
     }
 
-    if velocity.is_moving() {
-        manfred.view_direction = velocity.get_direction();
+    let message = format!(
+        "Manfred speeds up {:?} and slows down {:?}",
+        profile.acceleration, profile.deceleration
+    );
+    if let Some(mut config) = world.get_resource_mut::<MovementConfig>() {
+        config.profile = profile;
     }
+
+    Ok(message)
 }
 
 #[cfg(test)]
 mod tests {
-    use bevy::prelude::Entity;
-
-    use crate::{
-        velocity_control_system, Direction, Input, IntoSystem, KeyCode, Manfred, Stage,
-        SystemStage, Velocity, World,
-    };
+    use bevy::prelude::{IntoSystem, KeyCode};
+    use rstest::*;
+
+    use crate::component::manfred::Manfred;
+    use crate::component::player::Player;
+    use crate::plugin::movement::MovementConfig;
+    use crate::resource::console::{execute_console_command, ConsoleCommand, ConsoleCommands};
+    use crate::system::velocity::{easing_command, max_speed_command, velocity_control_system};
+    use crate::test_support::test_world::{TestWorld, TestWorldBuilder};
+    use crate::types::easing::{Easing, MovementProfile};
+    use crate::types::Direction;
+    use crate::Velocity;
 
     #[test]
     fn when_no_key_pressed_then_does_not_move() {
-        let mut world = WorldWrapper::init();
+        let mut world = init_world();
 
         world.run_step();
 
-        let (manfred, velocity) = world.get_manfred_entity();
+        let velocity = world.get::<Velocity>("manfred");
         assert!(!velocity.is_moving());
-        assert_eq!(Direction::Down, manfred.view_direction);
+        world.assert_facing("manfred", Direction::Down);
     }
 
     #[test]
     fn when_a_pressed_then_accelerates_left() {
-        let mut world = WorldWrapper::init();
+        let mut world = init_world();
 
-        world.given_key_pressed(KeyCode::A);
+        world.press(KeyCode::A);
 
         world.run_step();
 
-        let (manfred, velocity) = world.get_manfred_entity();
+        let velocity = world.get::<Velocity>("manfred");
         let velocity_after_step_1 = velocity.clone();
         assert!(velocity_after_step_1.x() < 0);
         assert_eq!(0, velocity_after_step_1.y());
-        assert_eq!(Direction::Left, manfred.view_direction);
+        world.assert_facing("manfred", Direction::Left);
 
         world.run_step();
 
-        let (manfred, velocity) = world.get_manfred_entity();
+        let velocity = world.get::<Velocity>("manfred");
         assert!(velocity.x() < velocity_after_step_1.x());
         assert_eq!(0, velocity.y());
-        assert_eq!(Direction::Left, manfred.view_direction);
+        world.assert_facing("manfred", Direction::Left);
     }
 
     #[test]
     fn when_d_pressed_then_accelerates_right() {
-        let mut world = WorldWrapper::init();
+        let mut world = init_world();
 
-        world.given_key_pressed(KeyCode::D);
+        world.press(KeyCode::D);
 
         world.run_step();
 
-        let (manfred, velocity) = world.get_manfred_entity();
+        let velocity = world.get::<Velocity>("manfred");
         let velocity_after_step_1 = velocity.clone();
         assert!(velocity_after_step_1.x() > 0);
         assert_eq!(0, velocity_after_step_1.y());
-        assert_eq!(Direction::Right, manfred.view_direction);
+        world.assert_facing("manfred", Direction::Right);
 
         world.run_step();
 
-        let (manfred, velocity) = world.get_manfred_entity();
+        let velocity = world.get::<Velocity>("manfred");
         assert!(velocity.x() > velocity_after_step_1.x());
         assert_eq!(0, velocity.y());
-        assert_eq!(Direction::Right, manfred.view_direction);
+        world.assert_facing("manfred", Direction::Right);
     }
 
     #[test]
     fn when_s_pressed_then_accelerates_downwards() {
-        let mut world = WorldWrapper::init();
+        let mut world = init_world();
 
-        world.given_key_pressed(KeyCode::S);
+        world.press(KeyCode::S);
 
         world.run_step();
 
-        let (manfred, velocity) = world.get_manfred_entity();
+        let velocity = world.get::<Velocity>("manfred");
         let velocity_after_step_1 = velocity.clone();
         assert_eq!(0, velocity_after_step_1.x());
         assert!(velocity_after_step_1.y() < 0);
-        assert_eq!(Direction::Down, manfred.view_direction);
+        world.assert_facing("manfred", Direction::Down);
 
         world.run_step();
 
-        let (manfred, velocity) = world.get_manfred_entity();
+        let velocity = world.get::<Velocity>("manfred");
         assert_eq!(0, velocity.x());
         assert!(velocity.y() < velocity_after_step_1.y());
-        assert_eq!(Direction::Down, manfred.view_direction);
+        world.assert_facing("manfred", Direction::Down);
     }
 
     #[test]
     fn when_w_pressed_then_accelerates_upwards() {
-        let mut world = WorldWrapper::init();
+        let mut world = init_world();
 
-        world.given_key_pressed(KeyCode::W);
+        world.press(KeyCode::W);
 
         world.run_step();
 
-        let (manfred, velocity) = world.get_manfred_entity();
+        let velocity = world.get::<Velocity>("manfred");
         let velocity_after_step_1 = velocity.clone();
         assert_eq!(0, velocity_after_step_1.x());
         assert!(velocity_after_step_1.y() > 0);
-        assert_eq!(Direction::Up, manfred.view_direction);
+        world.assert_facing("manfred", Direction::Up);
 
         world.run_step();
 
-        let (manfred, velocity) = world.get_manfred_entity();
+        let velocity = world.get::<Velocity>("manfred");
         assert_eq!(0, velocity.x());
         assert!(velocity.y() > velocity_after_step_1.y());
-        assert_eq!(Direction::Up, manfred.view_direction);
+        world.assert_facing("manfred", Direction::Up);
     }
 
     #[test]
     fn when_w_and_then_d_pressed_then_accelerates_diagonally() {
-        let mut world = WorldWrapper::init();
+        let mut world = init_world();
 
-        world.given_key_pressed(KeyCode::W);
-        world.given_key_pressed(KeyCode::D);
+        world.press(KeyCode::W);
+        world.press(KeyCode::D);
 
         world.run_step();
 
-        let (manfred, velocity) = world.get_manfred_entity();
+        let velocity = world.get::<Velocity>("manfred");
         let velocity_after_step_1 = velocity.clone();
         assert!(velocity_after_step_1.x() > 0);
         assert!(velocity_after_step_1.y() > 0);
-        assert_eq!(Direction::Up, manfred.view_direction);
+        world.assert_facing("manfred", Direction::Up);
 
         world.run_step();
 
-        let (manfred, velocity) = world.get_manfred_entity();
+        let velocity = world.get::<Velocity>("manfred");
         assert!(velocity.x() > velocity_after_step_1.x());
         assert!(velocity.y() > velocity_after_step_1.y());
-        assert_eq!(Direction::Up, manfred.view_direction);
+        world.assert_facing("manfred", Direction::Up);
     }
 
     #[test]
     fn when_opposite_directions_pressed_then_does_not_move() {
-        let mut world = WorldWrapper::init();
+        let mut world = init_world();
 
-        world.given_key_pressed(KeyCode::A);
-        world.given_key_pressed(KeyCode::D);
+        world.press(KeyCode::A);
+        world.press(KeyCode::D);
 
         world.run_step();
 
-        let (manfred, velocity) = world.get_manfred_entity();
+        let velocity = world.get::<Velocity>("manfred");
         assert!(!velocity.is_moving());
-        assert_eq!(Direction::Down, manfred.view_direction);
+        world.assert_facing("manfred", Direction::Down);
     }
 
     #[test]
     fn when_button_is_released_then_stops_moving_but_keeps_view_direction() {
-        let mut world = WorldWrapper::init();
+        let mut world = init_world();
 
-        world.given_key_pressed(KeyCode::D);
+        world.press(KeyCode::D);
 
         world.run_step();
 
-        let (manfred, velocity) = world.get_manfred_entity();
+        let velocity = world.get::<Velocity>("manfred");
         assert!(velocity.is_moving());
-        assert_eq!(Direction::Right, manfred.view_direction);
+        world.assert_facing("manfred", Direction::Right);
 
-        world.given_key_released(KeyCode::D);
+        world.release(KeyCode::D);
 
         world.run_step();
 
-        let (manfred, velocity) = world.get_manfred_entity();
+        let velocity = world.get::<Velocity>("manfred");
         assert!(!velocity.is_moving());
-        assert_eq!(Direction::Right, manfred.view_direction);
+        world.assert_facing("manfred", Direction::Right);
     }
 
-    struct WorldWrapper {
-        manfred_id: Entity,
-        world: World,
-        system_stage: SystemStage,
+    #[test]
+    fn max_speed_command_changes_max_speed_of_manfred() {
+        let mut commands = ConsoleCommands::default();
+        commands.register(ConsoleCommand::new(
+            "max_speed",
+            "<speed>",
+            "",
+            max_speed_command,
+        ));
+        let mut world = TestWorldBuilder::new()
+            .with_resource(commands)
+            .with_entity("manfred", (Manfred::default(), Velocity::new(10)))
+            .build();
+
+        let result = execute_console_command(world.world_mut(), "max_speed 20");
+
+        assert!(result.is_ok());
+        assert_eq!(20, world.get::<Velocity>("manfred").max_speed());
     }
 
-    impl WorldWrapper {
-        fn init() -> WorldWrapper {
-            let mut world = World::default();
-
-            let mut system_stage = SystemStage::parallel();
-            system_stage.add_system(velocity_control_system.system());
-
-            world.insert_resource(Input::<KeyCode>::default());
+    #[test]
+    fn every_player_walks_with_their_own_keys() {
+        let mut world = TestWorldBuilder::new()
+            .with_resource(MovementConfig::co_op())
+            .with_system(velocity_control_system.system())
+            .with_entity(
+                "manfred",
+                (Manfred::default(), Velocity::new(100), Player { index: 0 }),
+            )
+            .with_entity(
+                "second manfred",
+                (Manfred::default(), Velocity::new(100), Player { index: 1 }),
+            )
+            .build();
+
+        world.press(KeyCode::D);
+        world.press(KeyCode::Up);
+        world.run_step();
 
-            let manfred_id = world
-                .spawn()
-                .insert(Manfred::default())
-                .insert(Velocity::new(100))
-                .id();
+        assert!(world.get::<Velocity>("manfred").x() > 0);
+        assert_eq!(0, world.get::<Velocity>("manfred").y());
+        world.assert_facing("manfred", Direction::Right);
+        assert_eq!(0, world.get::<Velocity>("second manfred").x());
+        assert!(world.get::<Velocity>("second manfred").y() > 0);
+        world.assert_facing("second manfred", Direction::Up);
+    }
 
-            WorldWrapper {
-                manfred_id,
-                world,
-                system_stage,
-            }
-        }
+    #[test]
+    fn players_without_bindings_do_not_move() {
+        let mut world = TestWorldBuilder::new()
+            .with_resource(MovementConfig::default())
+            .with_system(velocity_control_system.system())
+            .with_entity(
+                "second manfred",
+                (Manfred::default(), Velocity::new(100), Player { index: 1 }),
+            )
+            .build();
+
+        world.press(KeyCode::W);
+        world.press(KeyCode::Up);
+        world.run_step();
 
-        fn run_step(&mut self) {
-            self.system_stage.run(&mut self.world);
-        }
+        assert!(!world.get::<Velocity>("second manfred").is_moving());
+    }
 
-        fn get_manfred_entity(&self) -> (&Manfred, &Velocity) {
-            let manfred = self.world.get::<Manfred>(self.manfred_id).unwrap();
-            let velocity = self.world.get::<Velocity>(self.manfred_id).unwrap();
-            (manfred, velocity)
-        }
+    #[rstest]
+    #[case("easing CubicOut", Easing::CubicOut, Easing::CubicOut)]
+    #[case(
+        "easing QuadraticIn Table([0,800,1000])",
+        Easing::QuadraticIn,
+        Easing::Table(vec![0, 800, 1000])
+    )]
+    fn easing_command_sets_the_profile(
+        #[case] line: &str,
+        #[case] acceleration: Easing,
+        #[case] deceleration: Easing,
+    ) {
+        let mut commands = ConsoleCommands::default();
+        commands.register(ConsoleCommand::new(
+            "easing",
+            "<acceleration> [deceleration]",
+            "",
+            easing_command,
+        ));
+        let mut world = TestWorldBuilder::new()
+            .with_resource(commands)
+            .with_resource(MovementConfig::default())
+            .with_entity("manfred", (Manfred::default(), Velocity::new(10)))
+            .build();
+
+        let result = execute_console_command(world.world_mut(), line);
+
+        assert!(result.is_ok(), "{:?}", result);
+        let expected = MovementProfile {
+            acceleration,
+            deceleration,
+        };
+        assert_eq!(&expected, world.get::<Velocity>("manfred").profile());
+        assert_eq!(expected, world.resource::<MovementConfig>().profile);
+    }
 
-        fn given_key_pressed(&mut self, key: KeyCode) {
-            let mut input_resource = self.world.get_resource_mut::<Input<KeyCode>>().unwrap();
-            input_resource.press(key);
-        }
+    #[test]
+    fn easing_command_rejects_unknown_curves() {
+        let mut commands = ConsoleCommands::default();
+        commands.register(ConsoleCommand::new("easing", "", "", easing_command));
+        let mut world = TestWorldBuilder::new()
+            .with_resource(commands)
+            .with_entity("manfred", (Manfred::default(), Velocity::new(10)))
+            .build();
+
+        let result = execute_console_command(world.world_mut(), "easing Bouncy");
+
+        assert!(result.is_err());
+        assert_eq!(
+            &MovementProfile::default(),
+            world.get::<Velocity>("manfred").profile()
+        );
+    }
 
-        fn given_key_released(&mut self, key: KeyCode) {
-            let mut input_resource = self.world.get_resource_mut::<Input<KeyCode>>().unwrap();
-            input_resource.update();
-            input_resource.release(key);
-        }
+    fn init_world() -> TestWorld {
+        TestWorldBuilder::new()
+            .with_resource(MovementConfig::default())
+            .with_system(velocity_control_system.system())
+            .with_entity("manfred", (Manfred::default(), Velocity::new(100)))
+            .build()
     }
 }
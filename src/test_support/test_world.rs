@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use bevy::app::Events;
+use bevy::ecs::component::Component;
+use bevy::ecs::schedule::SystemDescriptor;
+use bevy::prelude::{Bundle, Entity, Input, KeyCode, Mut, Stage, SystemStage, Transform, World};
+
+use crate::component::manfred::Manfred;
+use crate::resource::game_time::GameTime;
+use crate::types::Direction;
+
+const DEFAULT_FRAME_DURATION: f32 = 1.0 / 60.0;
+
+/// Builds a [TestWorld]: a bare `World` with the systems under test and named entities.
+/// The keyboard input and the [GameTime] are always available as resources.
+pub struct TestWorldBuilder {
+    world: World,
+    system_stage: SystemStage,
+    entities: HashMap<&'static str, Entity>,
+    event_updaters: Vec<fn(&mut World)>,
+    frame_duration: f32,
+}
+
+impl Default for TestWorldBuilder {
+    fn default() -> Self {
+        let mut world = World::default();
+        world.insert_resource(Input::<KeyCode>::default());
+        world.insert_resource(GameTime::default());
+
+        TestWorldBuilder {
+            world,
+            system_stage: SystemStage::parallel(),
+            entities: HashMap::new(),
+            event_updaters: vec![],
+            frame_duration: DEFAULT_FRAME_DURATION,
+        }
+    }
+}
+
+impl TestWorldBuilder {
+    pub fn new() -> TestWorldBuilder {
+        TestWorldBuilder::default()
+    }
+
+    pub fn with_system(mut self, system: impl Into<SystemDescriptor>) -> Self {
+        self.system_stage.add_system(system);
+        self
+    }
+
+    pub fn with_resource<T: Component>(mut self, resource: T) -> Self {
+        self.world.insert_resource(resource);
+        self
+    }
+
+    /// Registers the event type `T`. Like in a real app, events live for two frames.
+    pub fn with_event<T: Component>(mut self) -> Self {
+        self.world.insert_resource(Events::<T>::default());
+        self.event_updaters.push(update_events::<T>);
+        self
+    }
+
+    pub fn with_entity(mut self, name: &'static str, bundle: impl Bundle) -> Self {
+        let entity = self.world.spawn().insert_bundle(bundle).id();
+        self.entities.insert(name, entity);
+        self
+    }
+
+    pub fn with_frame_duration(mut self, seconds: f32) -> Self {
+        self.frame_duration = seconds;
+        self
+    }
+
+    pub fn build(self) -> TestWorld {
+        TestWorld {
+            world: self.world,
+            system_stage: self.system_stage,
+            entities: self.entities,
+            event_updaters: self.event_updaters,
+            frame_duration: self.frame_duration,
+            pending_presses: vec![],
+            pending_releases: vec![],
+        }
+    }
+}
+
+fn update_events<T: Component>(world: &mut World) {
+    world.get_resource_mut::<Events<T>>().unwrap().update();
+}
+
+/// Runs systems frame by frame the way bevy would, including the input and time bookkeeping.
+pub struct TestWorld {
+    world: World,
+    system_stage: SystemStage,
+    entities: HashMap<&'static str, Entity>,
+    event_updaters: Vec<fn(&mut World)>,
+    frame_duration: f32,
+    pending_presses: Vec<KeyCode>,
+    pending_releases: Vec<KeyCode>,
+}
+
+impl TestWorld {
+    pub fn run_step(&mut self) {
+        {
+            let mut input = self.world.get_resource_mut::<Input<KeyCode>>().unwrap();
+            input.update();
+            for key in self.pending_presses.drain(..) {
+                input.press(key);
+            }
+            for key in self.pending_releases.drain(..) {
+                input.release(key);
+            }
+        }
+
+        let frame_duration = self.frame_duration;
+        self.world
+            .get_resource_mut::<GameTime>()
+            .unwrap()
+            .advance(frame_duration);
+
+        for update in self.event_updaters.iter() {
+            update(&mut self.world);
+        }
+
+        self.system_stage.run(&mut self.world);
+    }
+
+    pub fn run_steps(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.run_step();
+        }
+    }
+
+    /// Runs as many frames as are needed for at least `seconds` of game time to pass.
+    pub fn run_for_seconds(&mut self, seconds: f32) {
+        let steps = (seconds / self.frame_duration).ceil() as usize;
+        self.run_steps(steps);
+    }
+
+    pub fn set_frame_duration(&mut self, seconds: f32) {
+        self.frame_duration = seconds;
+    }
+
+    /// The key counts as just pressed in the next frame and as pressed until it is released.
+    pub fn press(&mut self, key: KeyCode) {
+        self.pending_presses.push(key);
+    }
+
+    /// The key counts as just released in the next frame.
+    pub fn release(&mut self, key: KeyCode) {
+        self.pending_releases.push(key);
+    }
+
+    /// Presses the key and keeps it pressed for the given number of frames.
+    pub fn hold(&mut self, key: KeyCode, frames: usize) {
+        self.press(key);
+        self.run_steps(frames);
+    }
+
+    pub fn entity(&self, name: &str) -> Entity {
+        *self
+            .entities
+            .get(name)
+            .unwrap_or_else(|| panic!("no entity named '{}' in the test world", name))
+    }
+
+    pub fn spawn(&mut self, name: &'static str, bundle: impl Bundle) -> Entity {
+        let entity = self.world.spawn().insert_bundle(bundle).id();
+        self.entities.insert(name, entity);
+        entity
+    }
+
+    pub fn get<T: Component>(&self, name: &str) -> &T {
+        self.world
+            .get::<T>(self.entity(name))
+            .unwrap_or_else(|| panic!("'{}' has no {}", name, std::any::type_name::<T>()))
+    }
+
+    pub fn get_mut<T: Component>(&mut self, name: &str) -> Mut<T> {
+        let entity = self.entity(name);
+        self.world
+            .get_mut::<T>(entity)
+            .unwrap_or_else(|| panic!("'{}' has no {}", name, std::any::type_name::<T>()))
+    }
+
+    pub fn resource<T: Component>(&self) -> &T {
+        self.world.get_resource::<T>().unwrap()
+    }
+
+    pub fn resource_mut<T: Component>(&mut self) -> Mut<T> {
+        self.world.get_resource_mut::<T>().unwrap()
+    }
+
+    pub fn send_event<T: Component>(&mut self, event: T) {
+        self.world
+            .get_resource_mut::<Events<T>>()
+            .unwrap()
+            .send(event);
+    }
+
+    /// The events of type `T` that were sent during the last frame.
+    pub fn events<T: Component + Clone>(&self) -> Vec<T> {
+        self.world
+            .get_resource::<Events<T>>()
+            .unwrap()
+            .iter_current_update_events()
+            .cloned()
+            .collect()
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    pub fn assert_position(&self, name: &str, expected_x: f32, expected_y: f32) {
+        let translation = self.get::<Transform>(name).translation;
+        assert_eq!(
+            (expected_x, expected_y),
+            (translation.x, translation.y),
+            "position of '{}'",
+            name
+        );
+    }
+
+    pub fn assert_facing(&self, name: &str, expected_direction: Direction) {
+        assert_eq!(
+            expected_direction,
+            self.get::<Manfred>(name).view_direction,
+            "view direction of '{}'",
+            name
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{Input, IntoSystem, KeyCode, Res, ResMut};
+
+    use crate::resource::game_time::GameTime;
+    use crate::test_support::test_world::TestWorldBuilder;
+
+    #[derive(Default)]
+    struct InputLog {
+        frames: Vec<(bool, bool, bool)>,
+    }
+
+    fn log_space_key(input: Res<Input<KeyCode>>, mut log: ResMut<InputLog>) {
+        log.frames.push((
+            input.just_pressed(KeyCode::Space),
+            input.pressed(KeyCode::Space),
+            input.just_released(KeyCode::Space),
+        ));
+    }
+
+    #[test]
+    fn press_hold_and_release_follow_input_update_semantics() {
+        let mut world = TestWorldBuilder::new()
+            .with_resource(InputLog::default())
+            .with_system(log_space_key.system())
+            .build();
+
+        world.hold(KeyCode::Space, 3);
+        world.release(KeyCode::Space);
+        world.run_steps(2);
+
+        assert_eq!(
+            vec![
+                (true, true, false),
+                (false, true, false),
+                (false, true, false),
+                (false, false, true),
+                (false, false, false),
+            ],
+            world.resource::<InputLog>().frames
+        );
+    }
+
+    #[test]
+    fn run_for_seconds_advances_game_time() {
+        let mut world = TestWorldBuilder::new().with_frame_duration(0.25).build();
+
+        world.run_for_seconds(1.0);
+
+        let game_time = world.resource::<GameTime>();
+        assert_eq!(4, game_time.frames());
+        assert_eq!(1.0, game_time.seconds_since_startup());
+    }
+}
@@ -0,0 +1,404 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use serde::{Deserialize, Serialize};
+
+/// An integer that always stays within `[MIN, MAX]`. The operators saturate at the bounds, the
+/// `checked_` variants fail and the `wrapping_` variants wrap around when leaving the range.
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Copy, Clone, Serialize, Deserialize)]
+#[serde(try_from = "i32", into = "i32")]
+pub struct Bounded<const MIN: i32, const MAX: i32> {
+    value: i32,
+}
+
+/// Clamps the value into `[min, max]`. `LimitedInt` saturates the same way, its bounds only
+/// follow from a single limit.
+pub fn saturate(value: i64, min: i32, max: i32) -> i32 {
+    assert!(min <= max, "the bounds [{}, {}] are empty", min, max);
+    value.clamp(min as i64, max as i64) as i32
+}
+
+impl<const MIN: i32, const MAX: i32> Bounded<MIN, MAX> {
+    // evaluated at compile time, so they do not compile for empty bounds
+    pub const LOWEST: Self = Self::bound(MIN);
+    pub const HIGHEST: Self = Self::bound(MAX);
+
+    /// Clamps the value into the range.
+    pub fn new(value: i32) -> Bounded<MIN, MAX> {
+        Self::saturating(value as i64)
+    }
+
+    /// Fails if the value is outside of the range.
+    pub fn try_new(value: i32) -> Result<Bounded<MIN, MAX>, String> {
+        Self::checked(value as i64)
+            .ok_or_else(|| format!("{} is not within the bounds [{}, {}]", value, MIN, MAX))
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    /// Converts into a bounded type with other bounds, clamping the value into them.
+    pub fn convert<const OTHER_MIN: i32, const OTHER_MAX: i32>(
+        self,
+    ) -> Bounded<OTHER_MIN, OTHER_MAX> {
+        Bounded::new(self.value)
+    }
+
+    pub fn try_convert<const OTHER_MIN: i32, const OTHER_MAX: i32>(
+        self,
+    ) -> Result<Bounded<OTHER_MIN, OTHER_MAX>, String> {
+        Bounded::try_new(self.value)
+    }
+
+    pub fn checked_add(self, rhs: i32) -> Option<Self> {
+        Self::checked(self.value as i64 + rhs as i64)
+    }
+
+    pub fn checked_sub(self, rhs: i32) -> Option<Self> {
+        Self::checked(self.value as i64 - rhs as i64)
+    }
+
+    pub fn checked_mul(self, rhs: i32) -> Option<Self> {
+        Self::checked(self.value as i64 * rhs as i64)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Self::checked(-(self.value as i64))
+    }
+
+    pub fn wrapping_add(self, rhs: i32) -> Self {
+        Self::wrapping(self.value as i64 + rhs as i64)
+    }
+
+    pub fn wrapping_sub(self, rhs: i32) -> Self {
+        Self::wrapping(self.value as i64 - rhs as i64)
+    }
+
+    pub fn wrapping_mul(self, rhs: i32) -> Self {
+        Self::wrapping(self.value as i64 * rhs as i64)
+    }
+
+    pub fn wrapping_neg(self) -> Self {
+        Self::wrapping(-(self.value as i64))
+    }
+
+    // all arithmetic is done in i64, where neither sums nor products of two i32 can overflow
+
+    const fn bound(value: i32) -> Self {
+        assert!(MIN <= MAX, "the bounds are empty");
+        Bounded { value }
+    }
+
+    fn saturating(value: i64) -> Self {
+        Bounded {
+            value: saturate(value, MIN, MAX),
+        }
+    }
+
+    fn checked(value: i64) -> Option<Self> {
+        if (MIN as i64..=MAX as i64).contains(&value) {
+            Some(Bounded {
+                value: value as i32,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn wrapping(value: i64) -> Self {
+        assert!(MIN <= MAX, "the bounds [{}, {}] are empty", MIN, MAX);
+        let size = MAX as i64 - MIN as i64 + 1;
+        Bounded {
+            value: (MIN as i64 + (value - MIN as i64).rem_euclid(size)) as i32,
+        }
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> Default for Bounded<MIN, MAX> {
+    /// Zero, or the bound closest to it.
+    fn default() -> Self {
+        Bounded::new(0)
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> Display for Bounded<MIN, MAX> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> From<Bounded<MIN, MAX>> for i32 {
+    fn from(bounded: Bounded<MIN, MAX>) -> Self {
+        bounded.value
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> TryFrom<i32> for Bounded<MIN, MAX> {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        Bounded::try_new(value)
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> Add<i32> for Bounded<MIN, MAX> {
+    type Output = Self;
+
+    fn add(self, rhs: i32) -> Self::Output {
+        Self::saturating(self.value as i64 + rhs as i64)
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> Sub<i32> for Bounded<MIN, MAX> {
+    type Output = Self;
+
+    fn sub(self, rhs: i32) -> Self::Output {
+        Self::saturating(self.value as i64 - rhs as i64)
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> Mul<i32> for Bounded<MIN, MAX> {
+    type Output = Self;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Self::saturating(self.value as i64 * rhs as i64)
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> Neg for Bounded<MIN, MAX> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::saturating(-(self.value as i64))
+    }
+}
+
+impl<const MIN: i32, const MAX: i32, const OTHER_MIN: i32, const OTHER_MAX: i32>
+    Add<Bounded<OTHER_MIN, OTHER_MAX>> for Bounded<MIN, MAX>
+{
+    type Output = Self;
+
+    fn add(self, rhs: Bounded<OTHER_MIN, OTHER_MAX>) -> Self::Output {
+        self + rhs.value
+    }
+}
+
+impl<const MIN: i32, const MAX: i32, const OTHER_MIN: i32, const OTHER_MAX: i32>
+    Sub<Bounded<OTHER_MIN, OTHER_MAX>> for Bounded<MIN, MAX>
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Bounded<OTHER_MIN, OTHER_MAX>) -> Self::Output {
+        self - rhs.value
+    }
+}
+
+impl<const MIN: i32, const MAX: i32, const OTHER_MIN: i32, const OTHER_MAX: i32>
+    Mul<Bounded<OTHER_MIN, OTHER_MAX>> for Bounded<MIN, MAX>
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Bounded<OTHER_MIN, OTHER_MAX>) -> Self::Output {
+        self * rhs.value
+    }
+}
+
+impl<const MIN: i32, const MAX: i32, Rhs> AddAssign<Rhs> for Bounded<MIN, MAX>
+where
+    Self: Add<Rhs, Output = Self>,
+{
+    fn add_assign(&mut self, rhs: Rhs) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const MIN: i32, const MAX: i32, Rhs> SubAssign<Rhs> for Bounded<MIN, MAX>
+where
+    Self: Sub<Rhs, Output = Self>,
+{
+    fn sub_assign(&mut self, rhs: Rhs) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const MIN: i32, const MAX: i32, Rhs> MulAssign<Rhs> for Bounded<MIN, MAX>
+where
+    Self: Mul<Rhs, Output = Self>,
+{
+    fn mul_assign(&mut self, rhs: Rhs) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> PartialEq<i32> for Bounded<MIN, MAX> {
+    fn eq(&self, other: &i32) -> bool {
+        &self.value == other
+    }
+}
+
+impl<const MIN: i32, const MAX: i32> PartialOrd<i32> for Bounded<MIN, MAX> {
+    fn partial_cmp(&self, other: &i32) -> Option<Ordering> {
+        Some(self.value.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use quickcheck::{Arbitrary, Gen};
+    use quickcheck_macros::quickcheck;
+    use rstest::*;
+
+    use crate::types::bounded::Bounded;
+
+    type Percent = Bounded<0, 100>;
+    type Offset = Bounded<-3, 7>;
+
+    impl<const MIN: i32, const MAX: i32> Arbitrary for Bounded<MIN, MAX> {
+        fn arbitrary(g: &mut Gen) -> Self {
+            Bounded::new(i32::arbitrary(g))
+        }
+    }
+
+    fn in_range<const MIN: i32, const MAX: i32>(bounded: Bounded<MIN, MAX>) -> bool {
+        (MIN..=MAX).contains(&bounded.value())
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(5, 5)]
+    #[case(7, 7)]
+    #[case(8, 7)]
+    #[case(-3, -3)]
+    #[case(-4, -3)]
+    #[case(i32::MIN, -3)]
+    #[case(i32::MAX, 7)]
+    fn new_clamps_into_the_bounds(#[case] value: i32, #[case] expected: i32) {
+        assert_eq!(expected, Offset::new(value).value());
+    }
+
+    #[test]
+    fn adding_a_large_negative_number_stops_at_the_lower_bound() {
+        let mut under_test = Offset::new(2);
+
+        under_test += -100;
+
+        assert_eq!(Offset::LOWEST, under_test);
+    }
+
+    #[rstest]
+    #[case(Percent::new(90), 20, 100, None, 9)]
+    #[case(Percent::new(10), -20, 0, None, 91)]
+    #[case(Percent::new(10), 5, 15, Some(15), 15)]
+    fn addition_variants(
+        #[case] under_test: Percent,
+        #[case] summand: i32,
+        #[case] saturated: i32,
+        #[case] checked: Option<i32>,
+        #[case] wrapped: i32,
+    ) {
+        assert_eq!(saturated, (under_test + summand).value());
+        assert_eq!(checked, under_test.checked_add(summand).map(|b| b.value()));
+        assert_eq!(wrapped, under_test.wrapping_add(summand).value());
+    }
+
+    #[test]
+    fn negation_saturates_for_asymmetric_bounds() {
+        assert_eq!(Offset::new(-3), -Offset::new(7));
+        assert_eq!(None, Offset::new(7).checked_neg());
+        assert_eq!(Offset::new(4), Offset::new(7).wrapping_neg());
+    }
+
+    #[test]
+    fn bounded_values_combine() {
+        let mut under_test = Percent::new(40);
+
+        under_test += Offset::new(7);
+        assert_eq!(47, under_test);
+
+        under_test -= Offset::new(-3);
+        assert_eq!(50, under_test);
+
+        under_test *= Offset::new(3);
+        assert_eq!(Percent::HIGHEST, under_test);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_bounds_are_rejected() {
+        Bounded::<5, 1>::new(3);
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Ok(Percent::new(42)), Percent::try_from(42));
+        assert!(Percent::try_from(101).is_err());
+        assert_eq!(42, i32::from(Percent::new(42)));
+        assert_eq!(Offset::HIGHEST, Percent::new(42).convert::<-3, 7>());
+        assert!(Percent::new(42).try_convert::<-3, 7>().is_err());
+        assert_eq!(Offset::new(0), Offset::default());
+        assert_eq!(Bounded::<5, 9>::new(5), Bounded::<5, 9>::default());
+        assert_eq!("42", Percent::new(42).to_string());
+    }
+
+    #[test]
+    fn serde_round_trip_rejects_out_of_bounds_values() {
+        let serialized = ron::to_string(&Percent::new(42)).unwrap();
+
+        assert_eq!("42", serialized);
+        assert_eq!(Percent::new(42), ron::from_str(&serialized).unwrap());
+        assert!(ron::from_str::<Percent>("101").is_err());
+    }
+
+    #[quickcheck]
+    fn saturating_arithmetic_stays_in_range(under_test: Offset, operand: i32, other: Percent) {
+        assert!(in_range(under_test + operand));
+        assert!(in_range(under_test - operand));
+        assert!(in_range(under_test * operand));
+        assert!(in_range(-under_test));
+        assert!(in_range(under_test + other));
+        assert!(in_range(under_test - other));
+        assert!(in_range(under_test * other));
+    }
+
+    #[quickcheck]
+    fn wrapping_arithmetic_stays_in_range(under_test: Offset, operand: i32) {
+        assert!(in_range(under_test.wrapping_add(operand)));
+        assert!(in_range(under_test.wrapping_sub(operand)));
+        assert!(in_range(under_test.wrapping_mul(operand)));
+        assert!(in_range(under_test.wrapping_neg()));
+    }
+
+    #[quickcheck]
+    fn checked_arithmetic_is_exact_or_nothing(under_test: Offset, operand: i32) {
+        let exact = under_test.value() as i64 + operand as i64;
+
+        match under_test.checked_add(operand) {
+            Some(sum) => assert_eq!(exact, sum.value() as i64),
+            None => assert!(exact < -3 || exact > 7),
+        }
+    }
+
+    #[quickcheck]
+    fn saturating_agrees_with_clamping_the_exact_result(under_test: Percent, operand: i32) {
+        let exact = under_test.value() as i64 * operand as i64;
+
+        assert_eq!(exact.clamp(0, 100), (under_test * operand).value() as i64);
+    }
+
+    #[quickcheck]
+    fn full_range_does_not_overflow(under_test: Bounded<{ i32::MIN }, { i32::MAX }>, operand: i32) {
+        assert_eq!(
+            under_test.value().saturating_add(operand),
+            (under_test + operand).value()
+        );
+        assert_eq!(
+            under_test.value().wrapping_mul(operand),
+            under_test.wrapping_mul(operand).value()
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use crate::types::Direction;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// A 64 bit FNV-1a hash. Unlike the std hashers, its result is specified and does not depend on
+/// the platform, so checksums of different machines and builds can be compared.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Checksum {
+    value: u64,
+}
+
+impl Checksum {
+    pub fn new() -> Checksum {
+        Checksum {
+            value: FNV_OFFSET_BASIS,
+        }
+    }
+
+    pub fn add_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.value ^= *byte as u64;
+            self.value = self.value.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub fn add_i32(&mut self, value: i32) {
+        self.add_bytes(&value.to_le_bytes());
+    }
+
+    pub fn add_direction(&mut self, direction: Direction) {
+        self.add_bytes(&[match direction {
+            Direction::Up => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Right => 3,
+        }]);
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+impl Default for Checksum {
+    fn default() -> Self {
+        Checksum::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::types::checksum::Checksum;
+    use crate::types::Direction;
+
+    #[rstest]
+    #[case(b"", 0xcbf2_9ce4_8422_2325)]
+    #[case(b"a", 0xaf63_dc4c_8601_ec8c)]
+    #[case(b"foobar", 0x8594_4171_f739_67e8)]
+    fn matches_the_fnv_1a_reference_values(#[case] bytes: &[u8], #[case] expected: u64) {
+        let mut under_test = Checksum::new();
+
+        under_test.add_bytes(bytes);
+
+        assert_eq!(expected, under_test.value());
+    }
+
+    #[test]
+    fn order_matters() {
+        let mut first = Checksum::new();
+        first.add_i32(1);
+        first.add_i32(2);
+        let mut second = Checksum::new();
+        second.add_i32(2);
+        second.add_i32(1);
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn directions_are_told_apart() {
+        let checksums: Vec<u64> = vec![
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ]
+        .into_iter()
+        .map(|direction| {
+            let mut checksum = Checksum::new();
+            checksum.add_direction(direction);
+            checksum.value()
+        })
+        .collect();
+
+        for (index, checksum) in checksums.iter().enumerate() {
+            assert!(!checksums[index + 1..].contains(checksum));
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use bevy::prelude::Vec2;
+
+/// Identifies a square chunk of a streamed level. Chunk (0, 0) reaches from the origin to
+/// (chunk size, chunk size).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct ChunkCoordinate {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl ChunkCoordinate {
+    pub fn new(x: i32, y: i32) -> ChunkCoordinate {
+        ChunkCoordinate { x, y }
+    }
+
+    pub fn containing(position: Vec2, chunk_size: f32) -> ChunkCoordinate {
+        ChunkCoordinate {
+            x: (position.x / chunk_size).floor() as i32,
+            y: (position.y / chunk_size).floor() as i32,
+        }
+    }
+
+    /// The number of chunks to walk to get to the other chunk, counting diagonal steps as one.
+    pub fn distance(&self, other: ChunkCoordinate) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// All chunks whose distance to this one is at most the radius, this one included.
+    pub fn around(&self, radius: i32) -> Vec<ChunkCoordinate> {
+        (-radius..=radius)
+            .flat_map(|y| (-radius..=radius).map(move |x| (x, y)))
+            .map(|(x, y)| ChunkCoordinate::new(self.x + x, self.y + y))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Vec2;
+    use quickcheck_macros::quickcheck;
+    use rstest::*;
+
+    use crate::types::chunk::ChunkCoordinate;
+
+    #[rstest]
+    #[case(0.0, 0.0, 0, 0)]
+    #[case(399.0, 399.0, 0, 0)]
+    #[case(400.0, 0.0, 1, 0)]
+    #[case(-1.0, 0.0, -1, 0)]
+    #[case(-400.0, -401.0, -1, -2)]
+    fn chunk_containing_position(
+        #[case] x: f32,
+        #[case] y: f32,
+        #[case] expected_x: i32,
+        #[case] expected_y: i32,
+    ) {
+        assert_eq!(
+            ChunkCoordinate::new(expected_x, expected_y),
+            ChunkCoordinate::containing(Vec2::new(x, y), 400.0)
+        );
+    }
+
+    #[quickcheck]
+    fn chunks_around_are_within_radius(x: i8, y: i8, radius: u8) {
+        let center = ChunkCoordinate::new(x as i32, y as i32);
+        let radius = (radius % 4) as i32;
+
+        let around = center.around(radius);
+
+        assert_eq!(((2 * radius + 1) * (2 * radius + 1)) as usize, around.len());
+        assert!(around.contains(&center));
+        assert!(around.iter().all(|chunk| chunk.distance(center) <= radius));
+    }
+}
@@ -0,0 +1,175 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// How the acceleration steps map to speed. Every curve starts standing and reaches the max speed
+/// with the last step, only the way in between differs.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Easing {
+    Linear,
+    /// slow at first, fast at the end
+    QuadraticIn,
+    /// fast at first, slow at the end
+    QuadraticOut,
+    CubicIn,
+    CubicOut,
+    /// Speeds in permille of the max speed at evenly spaced points from the first to the last
+    /// step, interpolated in between. The first and the last point are always treated as 0 and
+    /// 1000, points above 1000 as 1000.
+    Table(Vec<u16>),
+}
+
+impl Easing {
+    /// The speed after `step` of `steps` acceleration steps. Integer arithmetic only, so it is the
+    /// same on every machine.
+    pub fn speed(&self, step: i32, steps: i32, max_speed: i32) -> i32 {
+        if step <= 0 || steps <= 0 {
+            return 0;
+        }
+        if step >= steps {
+            return max_speed;
+        }
+
+        let (step, steps, max_speed) = (step as i128, steps as i128, max_speed as i128);
+        let remaining = steps - step;
+        let speed = match self {
+            Easing::Linear => max_speed * step / steps,
+            Easing::QuadraticIn => max_speed * step.pow(2) / steps.pow(2),
+            Easing::QuadraticOut => max_speed * (steps.pow(2) - remaining.pow(2)) / steps.pow(2),
+            Easing::CubicIn => max_speed * step.pow(3) / steps.pow(3),
+            Easing::CubicOut => max_speed * (steps.pow(3) - remaining.pow(3)) / steps.pow(3),
+            Easing::Table(points) if points.len() < 2 => max_speed * step / steps,
+            Easing::Table(points) => {
+                let intervals = points.len() as i128 - 1;
+                let position = step * intervals;
+                let index = (position / steps) as usize;
+                let offset = position % steps;
+
+                let point = |index: usize| match index {
+                    0 => 0,
+                    index if index == points.len() - 1 => 1000,
+                    index => points[index].min(1000) as i128,
+                };
+                // permille times steps, to keep the fraction between the points
+                let scaled_permille =
+                    point(index) * steps + (point(index + 1) - point(index)) * offset;
+
+                max_speed * scaled_permille / (1000 * steps)
+            }
+        };
+
+        speed as i32
+    }
+}
+
+impl FromStr for Easing {
+    type Err = String;
+
+    /// Parses the RON notation, e.g. `CubicIn` or `Table([0, 800, 1000])`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        ron::from_str(text).map_err(|error| error.to_string())
+    }
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::Linear
+    }
+}
+
+/// The easing curves while speeding up and while slowing down.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MovementProfile {
+    pub acceleration: Easing,
+    pub deceleration: Easing,
+}
+
+impl MovementProfile {
+    /// Starts gently and comes to a halt gently.
+    pub fn smooth() -> MovementProfile {
+        MovementProfile {
+            acceleration: Easing::QuadraticIn,
+            deceleration: Easing::QuadraticOut,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+    use rstest::*;
+
+    use crate::types::easing::Easing;
+
+    fn all_curves() -> Vec<Easing> {
+        vec![
+            Easing::Linear,
+            Easing::QuadraticIn,
+            Easing::QuadraticOut,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::Table(vec![0, 100, 600, 1000]),
+            Easing::Table(vec![]),
+        ]
+    }
+
+    #[rstest]
+    #[case(Easing::Linear, vec![0, 25, 50, 75, 100])]
+    #[case(Easing::QuadraticIn, vec![0, 6, 25, 56, 100])]
+    #[case(Easing::QuadraticOut, vec![0, 43, 75, 93, 100])]
+    #[case(Easing::CubicIn, vec![0, 1, 12, 42, 100])]
+    #[case(Easing::CubicOut, vec![0, 57, 87, 98, 100])]
+    #[case(Easing::Table(vec![0, 500, 1000]), vec![0, 25, 50, 75, 100])]
+    #[case(Easing::Table(vec![0, 800, 900, 1000]), vec![0, 60, 85, 92, 100])]
+    #[case(Easing::Table(vec![300, 1000, 500]), vec![0, 50, 100, 100, 100])]
+    #[case(Easing::Table(vec![0, 5000, 1000]), vec![0, 50, 100, 100, 100])]
+    fn speed_along_the_curve(#[case] under_test: Easing, #[case] expected: Vec<i32>) {
+        let speeds: Vec<i32> = (0..=4).map(|step| under_test.speed(step, 4, 100)).collect();
+
+        assert_eq!(expected, speeds);
+    }
+
+    #[quickcheck]
+    fn every_curve_starts_standing_and_ends_at_max_speed(steps: u16, max_speed: u16) {
+        let steps = steps.max(1) as i32;
+
+        for curve in all_curves() {
+            assert_eq!(0, curve.speed(0, steps, max_speed as i32));
+            assert_eq!(
+                max_speed as i32,
+                curve.speed(steps, steps, max_speed as i32)
+            );
+        }
+    }
+
+    #[quickcheck]
+    fn monotonic_curves_never_slow_down_with_more_steps(steps: u8, max_speed: u16) {
+        let steps = steps.max(1) as i32;
+
+        for curve in all_curves() {
+            for step in 1..=steps {
+                assert!(
+                    curve.speed(step - 1, steps, max_speed as i32)
+                        <= curve.speed(step, steps, max_speed as i32),
+                    "{:?} at step {}",
+                    curve,
+                    step
+                );
+            }
+        }
+    }
+
+    #[quickcheck]
+    fn no_table_exceeds_max_speed(points: Vec<u16>, step: u8, steps: u8, max_speed: u16) {
+        let under_test = Easing::Table(points);
+
+        let speed = under_test.speed(step as i32, steps as i32, max_speed as i32);
+
+        assert!(
+            speed <= max_speed as i32,
+            "{:?} at step {}",
+            under_test,
+            step
+        );
+    }
+}
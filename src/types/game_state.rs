@@ -0,0 +1,5 @@
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum GameState {
+    Playing,
+    GameOver,
+}
@@ -0,0 +1,204 @@
+use bevy::prelude::{
+    Axis, Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Input, KeyCode,
+};
+
+use crate::types::Direction;
+
+/// How far a stick has to be pushed to count as pressed.
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// The keys and the gamepad one player walks with. Both can be used at the same time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputBindings {
+    pub up: KeyCode,
+    pub down: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub jump: KeyCode,
+    pub gamepad: Option<Gamepad>,
+}
+
+impl InputBindings {
+    /// W, A, S, D, space and the first gamepad.
+    pub fn player_one() -> InputBindings {
+        InputBindings {
+            up: KeyCode::W,
+            down: KeyCode::S,
+            left: KeyCode::A,
+            right: KeyCode::D,
+            jump: KeyCode::Space,
+            gamepad: Some(Gamepad(0)),
+        }
+    }
+
+    /// The arrow keys, right control and the second gamepad.
+    pub fn player_two() -> InputBindings {
+        InputBindings {
+            up: KeyCode::Up,
+            down: KeyCode::Down,
+            left: KeyCode::Left,
+            right: KeyCode::Right,
+            jump: KeyCode::RControl,
+            gamepad: Some(Gamepad(1)),
+        }
+    }
+
+    pub fn key(&self, direction: Direction) -> KeyCode {
+        match direction {
+            Direction::Up => self.up,
+            Direction::Down => self.down,
+            Direction::Left => self.left,
+            Direction::Right => self.right,
+        }
+    }
+
+    /// Whether the player wants to walk in the given direction, by key, d-pad or left stick.
+    /// Gamepad input is optional, as it is missing without the gilrs plugin, e.g. in tests.
+    pub fn is_pressed(
+        &self,
+        direction: Direction,
+        keys: &Input<KeyCode>,
+        buttons: Option<&Input<GamepadButton>>,
+        axes: Option<&Axis<GamepadAxis>>,
+    ) -> bool {
+        if keys.pressed(self.key(direction)) {
+            return true;
+        }
+
+        let gamepad = match self.gamepad {
+            Some(gamepad) => gamepad,
+            None => return false,
+        };
+
+        let (button, axis, sign) = match direction {
+            Direction::Up => (GamepadButtonType::DPadUp, GamepadAxisType::LeftStickY, 1.0),
+            Direction::Down => (
+                GamepadButtonType::DPadDown,
+                GamepadAxisType::LeftStickY,
+                -1.0,
+            ),
+            Direction::Left => (
+                GamepadButtonType::DPadLeft,
+                GamepadAxisType::LeftStickX,
+                -1.0,
+            ),
+            Direction::Right => (
+                GamepadButtonType::DPadRight,
+                GamepadAxisType::LeftStickX,
+                1.0,
+            ),
+        };
+
+        let button_pressed = buttons.map_or(false, |buttons| {
+            buttons.pressed(GamepadButton(gamepad, button))
+        });
+        let stick_pushed = axes
+            .and_then(|axes| axes.get(GamepadAxis(gamepad, axis)))
+            .map_or(false, |value| value * sign > STICK_THRESHOLD);
+
+        button_pressed || stick_pushed
+    }
+
+    /// Whether the player wants to jump, by key or the lower face button. Holding it down only
+    /// jumps once.
+    pub fn jump_pressed(
+        &self,
+        keys: &Input<KeyCode>,
+        buttons: Option<&Input<GamepadButton>>,
+    ) -> bool {
+        let button_pressed = match (self.gamepad, buttons) {
+            (Some(gamepad), Some(buttons)) => {
+                buttons.just_pressed(GamepadButton(gamepad, GamepadButtonType::South))
+            }
+            _ => false,
+        };
+
+        keys.just_pressed(self.jump) || button_pressed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{
+        Axis, Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Input,
+        KeyCode,
+    };
+    use rstest::*;
+
+    use crate::types::input::InputBindings;
+    use crate::types::Direction;
+
+    #[rstest]
+    #[case(KeyCode::W, Direction::Up, true)]
+    #[case(KeyCode::W, Direction::Down, false)]
+    #[case(KeyCode::Up, Direction::Up, false)]
+    #[case(KeyCode::A, Direction::Left, true)]
+    fn keys_of_the_player_count(
+        #[case] key: KeyCode,
+        #[case] direction: Direction,
+        #[case] expected: bool,
+    ) {
+        let mut keys = Input::<KeyCode>::default();
+        keys.press(key);
+
+        assert_eq!(
+            expected,
+            InputBindings::player_one().is_pressed(direction, &keys, None, None)
+        );
+    }
+
+    #[test]
+    fn d_pad_of_the_own_gamepad_counts() {
+        let mut buttons = Input::<GamepadButton>::default();
+        buttons.press(GamepadButton(Gamepad(1), GamepadButtonType::DPadLeft));
+        let keys = Input::<KeyCode>::default();
+
+        let player_one = InputBindings::player_one();
+        let player_two = InputBindings::player_two();
+        assert!(!player_one.is_pressed(Direction::Left, &keys, Some(&buttons), None));
+        assert!(player_two.is_pressed(Direction::Left, &keys, Some(&buttons), None));
+    }
+
+    #[rstest]
+    #[case(0.8, Direction::Right, true)]
+    #[case(0.3, Direction::Right, false)]
+    #[case(-0.8, Direction::Left, true)]
+    #[case(-0.8, Direction::Right, false)]
+    fn pushed_stick_counts(
+        #[case] value: f32,
+        #[case] direction: Direction,
+        #[case] expected: bool,
+    ) {
+        let mut axes = Axis::<GamepadAxis>::default();
+        axes.set(GamepadAxis(Gamepad(0), GamepadAxisType::LeftStickX), value);
+        let keys = Input::<KeyCode>::default();
+
+        assert_eq!(
+            expected,
+            InputBindings::player_one().is_pressed(direction, &keys, None, Some(&axes))
+        );
+    }
+
+    #[test]
+    fn jumping_needs_a_fresh_press() {
+        let mut keys = Input::<KeyCode>::default();
+        let player_one = InputBindings::player_one();
+
+        keys.press(KeyCode::Space);
+        assert!(player_one.jump_pressed(&keys, None));
+
+        keys.clear();
+        assert!(!player_one.jump_pressed(&keys, None));
+        assert!(!InputBindings::player_two().jump_pressed(&keys, None));
+    }
+
+    #[test]
+    fn lower_face_button_of_the_own_gamepad_jumps() {
+        let mut buttons = Input::<GamepadButton>::default();
+        buttons.press(GamepadButton(Gamepad(1), GamepadButtonType::South));
+        let keys = Input::<KeyCode>::default();
+
+        assert!(!InputBindings::player_one().jump_pressed(&keys, Some(&buttons)));
+        assert!(InputBindings::player_two().jump_pressed(&keys, Some(&buttons)));
+    }
+}
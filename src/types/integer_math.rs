@@ -0,0 +1,152 @@
+/// The largest integer whose square is not greater than `value`.
+pub fn integer_sqrt(value: u128) -> u128 {
+    let mut remainder = value;
+    let mut root = 0;
+    let mut bit = 1 << 126;
+
+    while bit > remainder {
+        bit >>= 2;
+    }
+
+    while bit != 0 {
+        if remainder >= root + bit {
+            remainder -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+
+    root
+}
+
+/// The integer nearest to the root of `value`.
+pub fn integer_sqrt_rounded(value: u128) -> u128 {
+    let floor = integer_sqrt(value);
+    // the root is at least floor + 1/2 exactly when value >= floor² + floor + 1/4
+    match value - floor * floor > floor {
+        true => floor + 1,
+        false => floor,
+    }
+}
+
+/// Computes `numerator / denominator` rounded to the nearest integer, halves away from zero. The
+/// denominator must be positive.
+pub fn divide_rounded(numerator: i64, denominator: i64) -> i64 {
+    assert!(denominator > 0, "cannot divide by {}", denominator);
+
+    let magnitude = numerator.unsigned_abs() as u128;
+    let denominator = denominator as u128;
+    let rounded = (magnitude * 2 + denominator) / (denominator * 2);
+
+    numerator.signum() * rounded as i64
+}
+
+/// Computes `numerator / sqrt(square)` rounded to the nearest integer, halves away from zero like
+/// `f32::round`, without any floating point arithmetic. The numerator must be smaller than 2^62 in
+/// magnitude.
+pub fn divide_by_sqrt_rounded(numerator: i64, square: u64) -> i64 {
+    assert!(square > 0, "cannot divide by the root of zero");
+
+    let magnitude = numerator.unsigned_abs() as u128;
+    let numerator_square = magnitude * magnitude;
+    let square = square as u128;
+
+    // floor(a / sqrt(s)) == floor(sqrt(floor(a² / s)))
+    let floor = integer_sqrt(numerator_square / square);
+    // a / sqrt(s) >= floor + 1/2 <=> 4a² >= (2 floor + 1)² s
+    let rounded = if 4 * numerator_square >= (2 * floor + 1) * (2 * floor + 1) * square {
+        floor + 1
+    } else {
+        floor
+    };
+
+    numerator.signum() * rounded as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+    use rstest::*;
+
+    use crate::types::integer_math::{
+        divide_by_sqrt_rounded, divide_rounded, integer_sqrt, integer_sqrt_rounded,
+    };
+
+    #[quickcheck]
+    fn integer_sqrt_is_the_floor_of_the_root(value: u64) {
+        let value = value as u128;
+        let root = integer_sqrt(value);
+
+        assert!(root * root <= value);
+        assert!((root + 1) * (root + 1) > value);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(1, 1)]
+    #[case(3, 1)]
+    #[case(4, 2)]
+    #[case(99, 9)]
+    #[case(100, 10)]
+    #[case(u128::MAX, u64::MAX as u128)]
+    fn integer_sqrt_of(#[case] value: u128, #[case] expected: u128) {
+        assert_eq!(expected, integer_sqrt(value));
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(2, 1)]
+    #[case(3, 2)]
+    #[case(20, 4)]
+    #[case(21, 5)]
+    #[case(u64::MAX as u128, 1 << 32)]
+    fn integer_sqrt_rounded_of(#[case] value: u128, #[case] expected: u128) {
+        assert_eq!(expected, integer_sqrt_rounded(value));
+    }
+
+    #[rstest]
+    #[case(7, 2, 4)]
+    #[case(-7, 2, -4)]
+    #[case(5, 3, 2)]
+    #[case(-4, 3, -1)]
+    #[case(0, 5, 0)]
+    fn divide_rounded_of(#[case] numerator: i64, #[case] denominator: i64, #[case] expected: i64) {
+        assert_eq!(expected, divide_rounded(numerator, denominator));
+    }
+
+    #[rstest]
+    #[case(480, 32, 85)]
+    #[case(-360, 25, -72)]
+    #[case(50, 50, 7)]
+    #[case(-30, 34, -5)]
+    #[case(5, 4, 3)]
+    #[case(-5, 4, -3)]
+    #[case(0, 7, 0)]
+    fn divide_by_sqrt_rounded_of(
+        #[case] numerator: i64,
+        #[case] square: u64,
+        #[case] expected: i64,
+    ) {
+        assert_eq!(expected, divide_by_sqrt_rounded(numerator, square));
+    }
+
+    #[quickcheck]
+    fn divide_by_sqrt_rounded_matches_floating_point_away_from_halves(numerator: i32, square: u16) {
+        if square == 0 {
+            return;
+        }
+
+        let exact = numerator as f64 / (square as f64).sqrt();
+        // too close to a half for f64 to tell which way it rounds
+        if (exact.abs().fract() - 0.5).abs() < 1e-6 {
+            return;
+        }
+
+        assert_eq!(
+            exact.round() as i64,
+            divide_by_sqrt_rounded(numerator as i64, square as u64)
+        );
+    }
+}
@@ -1,19 +1,18 @@
 use std::cmp::Ordering;
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
+use crate::types::bounded::saturate;
+
+/// An integer within `[-LIMIT, LIMIT]`, which saturates like `Bounded`. The symmetric bounds
+/// cannot be derived from one const parameter on stable Rust, so it is not a `Bounded` itself.
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone)]
 pub struct LimitedInt<const LIMIT: u16> {
     value: i32,
 }
 
 impl<const LIMIT: u16> LimitedInt<LIMIT> {
-    pub fn new(mut value: i32) -> LimitedInt<LIMIT> {
-        if value > LIMIT as i32 {
-            value = LIMIT as i32;
-        } else if value < -(LIMIT as i32) {
-            value = -(LIMIT as i32);
-        };
-        LimitedInt { value }
+    pub fn new(value: i32) -> LimitedInt<LIMIT> {
+        Self::saturating(value as i64)
     }
 
     pub fn value(&self) -> i32 {
@@ -26,6 +25,12 @@ impl<const LIMIT: u16> LimitedInt<LIMIT> {
             Ordering::Equal | Ordering::Greater => self.value,
         }
     }
+
+    fn saturating(value: i64) -> Self {
+        LimitedInt {
+            value: saturate(value, -(LIMIT as i32), LIMIT as i32),
+        }
+    }
 }
 
 impl<const LIMIT: u16> Add<i32> for LimitedInt<LIMIT> {
@@ -39,12 +44,7 @@ impl<const LIMIT: u16> Add<i32> for LimitedInt<LIMIT> {
 
 impl<const LIMIT: u16> AddAssign<i32> for LimitedInt<LIMIT> {
     fn add_assign(&mut self, rhs: i32) {
-        let new_value = &self.value + rhs;
-        self.value = if new_value <= LIMIT as i32 {
-            new_value
-        } else {
-            LIMIT as i32
-        };
+        *self = Self::saturating(self.value as i64 + rhs as i64);
     }
 }
 
@@ -59,12 +59,7 @@ impl<const LIMIT: u16> Sub<i32> for LimitedInt<LIMIT> {
 
 impl<const LIMIT: u16> SubAssign<i32> for LimitedInt<LIMIT> {
     fn sub_assign(&mut self, rhs: i32) {
-        let new_value = &self.value - rhs;
-        self.value = if new_value >= -(LIMIT as i32) {
-            new_value
-        } else {
-            -(LIMIT as i32)
-        };
+        *self = Self::saturating(self.value as i64 - rhs as i64);
     }
 }
 
@@ -153,4 +148,36 @@ mod tests {
 
         assert_eq!(-3, under_test.value())
     }
+
+    #[rstest]
+    #[case(2, -7, -3)]
+    #[case(0, i32::MIN, -3)]
+    #[case(0, i32::MAX, 3)]
+    fn addition_stays_within_both_limits(
+        #[case] initial_value: i32,
+        #[case] summand: i32,
+        #[case] expected: i32,
+    ) {
+        let mut under_test = LimitedInt::<3>::new(initial_value);
+
+        under_test += summand;
+
+        assert_eq!(expected, under_test.value())
+    }
+
+    #[rstest]
+    #[case(-2, -7, 3)]
+    #[case(0, i32::MIN, 3)]
+    #[case(0, i32::MAX, -3)]
+    fn subtraction_stays_within_both_limits(
+        #[case] initial_value: i32,
+        #[case] subtrahend: i32,
+        #[case] expected: i32,
+    ) {
+        let mut under_test = LimitedInt::<3>::new(initial_value);
+
+        under_test -= subtrahend;
+
+        assert_eq!(expected, under_test.value())
+    }
 }
@@ -1,9 +1,31 @@
+use bevy::prelude::Vec2;
+use serde::{Deserialize, Serialize};
+
+pub mod bounded;
+pub mod checksum;
+pub mod chunk;
+pub mod easing;
+pub mod game_state;
+pub mod input;
+pub mod integer_math;
 pub mod limited_int;
+pub mod surface;
 
-#[derive(Debug, Clone, Eq, PartialEq, Copy, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Copy, Hash, Serialize, Deserialize)]
 pub enum Direction {
     Up,
     Down,
     Left,
     Right,
 }
+
+impl Direction {
+    pub fn unit_vector(&self) -> Vec2 {
+        match self {
+            Direction::Up => Vec2::new(0.0, 1.0),
+            Direction::Down => Vec2::new(0.0, -1.0),
+            Direction::Left => Vec2::new(-1.0, 0.0),
+            Direction::Right => Vec2::new(1.0, 0.0),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// What the ground is made of, which decides how footsteps sound.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Surface {
+    Grass,
+    Dirt,
+    Wood,
+    Stone,
+}
+
+impl Default for Surface {
+    fn default() -> Self {
+        Surface::Grass
+    }
+}
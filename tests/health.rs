@@ -0,0 +1,83 @@
+use bevy::asset::AssetPlugin;
+use bevy::prelude::{
+    AddAsset, App, AppBuilder, Entity, Font, Input, KeyCode, MinimalPlugins, State, Transform, Vec3,
+};
+
+use rustymanfred::component::health::Health;
+use rustymanfred::component::manfred::Manfred;
+use rustymanfred::component::Position;
+use rustymanfred::plugin::health::HealthPlugin;
+use rustymanfred::plugin::movement::{MovementConfig, MovementPlugin};
+use rustymanfred::system::position::FromXAndY;
+use rustymanfred::types::game_state::GameState;
+use rustymanfred::Velocity;
+
+fn health_app() -> (AppBuilder, Entity) {
+    let mut app = App::build();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin)
+        .add_asset::<Font>()
+        .insert_resource(Input::<KeyCode>::default())
+        .add_plugin(MovementPlugin {
+            config: MovementConfig {
+                max_speed: 10,
+                ..Default::default()
+            },
+        })
+        .add_plugin(HealthPlugin::default());
+
+    let manfred_id = app
+        .world_mut()
+        .spawn()
+        .insert(Manfred::default())
+        .insert(Health::new(5))
+        .insert(Velocity::new(10))
+        .insert(Position::new(0, 0))
+        .insert(Transform::from_translation(Vec3::compute_from_x_y(
+            0.0, 0.0,
+        )))
+        .id();
+
+    (app, manfred_id)
+}
+
+#[test]
+fn running_out_of_health_ends_the_game_and_stops_manfred() {
+    let (mut app, manfred_id) = health_app();
+    app.world_mut()
+        .get_resource_mut::<Input<KeyCode>>()
+        .unwrap()
+        .press(KeyCode::D);
+    app.app.update();
+    app.app.update();
+    assert!(app.world().get::<Velocity>(manfred_id).unwrap().is_moving());
+
+    app.world_mut()
+        .get_mut::<Health>(manfred_id)
+        .unwrap()
+        .take_damage(5);
+    app.app.update();
+    app.app.update();
+
+    let state = app.world().get_resource::<State<GameState>>().unwrap();
+    assert_eq!(&GameState::GameOver, state.current());
+    assert!(!app.world().get::<Velocity>(manfred_id).unwrap().is_moving());
+
+    let x = app
+        .world()
+        .get::<Transform>(manfred_id)
+        .unwrap()
+        .translation
+        .x;
+    app.app.update();
+    app.app.update();
+
+    assert_eq!(
+        x,
+        app.world()
+            .get::<Transform>(manfred_id)
+            .unwrap()
+            .translation
+            .x
+    );
+}
@@ -0,0 +1,86 @@
+use bevy::prelude::{App, AppBuilder, Entity, Input, KeyCode, Transform, Vec3};
+
+use rustymanfred::component::manfred::Manfred;
+use rustymanfred::component::Position;
+use rustymanfred::plugin::movement::{MovementConfig, MovementPlugin};
+use rustymanfred::resource::console::execute_console_command;
+use rustymanfred::system::position::FromXAndY;
+use rustymanfred::types::Direction;
+use rustymanfred::Velocity;
+
+fn movement_app() -> (AppBuilder, Entity) {
+    let mut app = App::build();
+    app.insert_resource(Input::<KeyCode>::default())
+        .add_plugin(MovementPlugin {
+            config: MovementConfig {
+                max_speed: 10,
+                ..Default::default()
+            },
+        });
+
+    let manfred_id = app
+        .world_mut()
+        .spawn()
+        .insert(Manfred::default())
+        .insert(Velocity::new(10))
+        .insert(Position::new(0, 0))
+        .insert(Transform::from_translation(Vec3::compute_from_x_y(
+            0.0, 0.0,
+        )))
+        .id();
+
+    (app, manfred_id)
+}
+
+#[test]
+fn manfred_walks_right_while_d_is_pressed() {
+    let (mut app, manfred_id) = movement_app();
+
+    app.world_mut()
+        .get_resource_mut::<Input<KeyCode>>()
+        .unwrap()
+        .press(KeyCode::D);
+
+    app.app.update();
+    app.app.update();
+
+    let transform = app.world().get::<Transform>(manfred_id).unwrap();
+    assert!(transform.translation.x > 0.0);
+    assert_eq!(0.0, transform.translation.y);
+
+    let manfred = app.world().get::<Manfred>(manfred_id).unwrap();
+    assert_eq!(Direction::Right, manfred.view_direction);
+}
+
+#[test]
+fn manfred_stands_still_without_input() {
+    let (mut app, manfred_id) = movement_app();
+
+    app.app.update();
+
+    let transform = app.world().get::<Transform>(manfred_id).unwrap();
+    assert_eq!(0.0, transform.translation.x);
+    assert_eq!(0.0, transform.translation.y);
+    assert!(!app.world().get::<Velocity>(manfred_id).unwrap().is_moving());
+}
+
+#[test]
+fn movement_plugin_registers_console_commands() {
+    let (mut app, manfred_id) = movement_app();
+
+    let teleported = execute_console_command(app.world_mut(), "teleport 10 20");
+    let max_speed_changed = execute_console_command(app.world_mut(), "max_speed 3");
+
+    assert!(teleported.is_ok());
+    assert!(max_speed_changed.is_ok());
+    let transform = app.world().get::<Transform>(manfred_id).unwrap();
+    assert_eq!(Vec3::compute_from_x_y(10.0, 20.0), transform.translation);
+    assert_eq!(
+        &Position::new(10, 20),
+        app.world().get::<Position>(manfred_id).unwrap()
+    );
+    assert_eq!(
+        3,
+        app.world().get::<Velocity>(manfred_id).unwrap().max_speed()
+    );
+}
@@ -0,0 +1,103 @@
+use std::net::SocketAddr;
+use std::thread;
+use std::time::Duration;
+
+use rustymanfred::component::Position;
+use rustymanfred::net::message::{ClientMessage, ServerMessage};
+use rustymanfred::net::prediction::Prediction;
+use rustymanfred::net::server::Server;
+use rustymanfred::net::simulation::{MovementRules, PlayerInput, SimulatedPlayer};
+use rustymanfred::net::socket::NetSocket;
+use rustymanfred::types::easing::MovementProfile;
+
+fn rules() -> MovementRules {
+    MovementRules {
+        max_speed: 5,
+        profile: MovementProfile::smooth(),
+        ..Default::default()
+    }
+}
+
+fn localhost() -> NetSocket {
+    NetSocket::bind("127.0.0.1:0", None).unwrap()
+}
+
+/// Runs server updates until the client received a message, packets on localhost take a moment.
+fn exchange(
+    server: &mut Server,
+    server_socket: &mut NetSocket,
+    client_socket: &mut NetSocket,
+) -> Vec<ServerMessage> {
+    for _ in 0..200 {
+        server.update(server_socket);
+        let messages: Vec<ServerMessage> = client_socket
+            .receive::<ServerMessage>()
+            .into_iter()
+            .map(|(_, message)| message)
+            .collect();
+        if !messages.is_empty() {
+            return messages;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("the server did not answer");
+}
+
+#[test]
+fn predicted_movement_matches_the_server_over_udp() {
+    let mut server = Server::new(rules(), Position::default());
+    let mut server_socket = localhost();
+    let server_address: SocketAddr = server_socket.local_addr().unwrap();
+    let mut client_socket = localhost();
+
+    client_socket.send(server_address, &ClientMessage::Connect);
+    let welcome = exchange(&mut server, &mut server_socket, &mut client_socket);
+    assert_eq!(
+        ServerMessage::Welcome {
+            id: 1,
+            rules: rules()
+        },
+        welcome[0]
+    );
+
+    let mut prediction = Prediction::default();
+    let mut local = SimulatedPlayer::new(Position::default(), &rules());
+    let right = PlayerInput {
+        right: true,
+        ..Default::default()
+    };
+    for _ in 0..5 {
+        prediction.record(right);
+        local.step(right, &rules());
+    }
+    client_socket.send(
+        server_address,
+        &ClientMessage::Input {
+            inputs: prediction.newest(5),
+        },
+    );
+
+    let mut acknowledged = 0;
+    let mut reconciled = None;
+    while acknowledged < 5 {
+        for message in exchange(&mut server, &mut server_socket, &mut client_socket) {
+            if let ServerMessage::State {
+                acknowledged: server_acknowledged,
+                players,
+            } = message
+            {
+                acknowledged = server_acknowledged;
+                reconciled = Some(prediction.reconcile(
+                    acknowledged,
+                    SimulatedPlayer::from_state(&players[0], &rules()),
+                    &rules(),
+                ));
+            }
+        }
+    }
+
+    let reconciled = reconciled.unwrap();
+    assert!(reconciled.position.x > 0);
+    assert_eq!(local.position, reconciled.position);
+    assert_eq!(0, prediction.pending());
+}
@@ -0,0 +1,33 @@
+use bevy::asset::AssetPlugin;
+use bevy::prelude::{AddAsset, App, Assets, MinimalPlugins, Texture, TextureAtlas};
+
+use rustymanfred::data::load_ron_file;
+use rustymanfred::data::palette::PaletteDefinitions;
+use rustymanfred::plugin::animation::AnimationConfig;
+use rustymanfred::resource::palette::PaletteAtlases;
+use rustymanfred::system::palette::palette_atlas;
+
+#[test]
+fn every_palette_is_recoloured_only_once() {
+    let mut app = App::build();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin)
+        .add_asset::<Texture>()
+        .add_asset::<TextureAtlas>()
+        .insert_resource(AnimationConfig::default())
+        .insert_resource(
+            load_ron_file::<PaletteDefinitions>("assets/palettes/palettes.ron").unwrap(),
+        )
+        .init_resource::<PaletteAtlases>();
+    let world = app.world_mut();
+
+    let first = palette_atlas(world, "farmer").unwrap();
+    let second = palette_atlas(world, "farmer").unwrap();
+    let other = palette_atlas(world, "sailor").unwrap();
+
+    assert_eq!(first, second);
+    assert_ne!(first, other);
+    assert_eq!(2, world.get_resource::<PaletteAtlases>().unwrap().len());
+    assert_eq!(2, world.get_resource::<Assets<Texture>>().unwrap().len());
+    assert!(palette_atlas(world, "clown").is_err());
+}
@@ -0,0 +1,128 @@
+use std::thread;
+use std::time::Duration;
+
+use bevy::asset::AssetPlugin;
+use bevy::prelude::{
+    AddAsset, App, AppBuilder, ColorMaterial, Entity, Input, KeyCode, MinimalPlugins, Texture,
+    TextureAtlas, Transform, Vec3, With,
+};
+
+use rustymanfred::component::chunk::ChunkObject;
+use rustymanfred::component::manfred::Manfred;
+use rustymanfred::plugin::animation::AnimationConfig;
+use rustymanfred::plugin::level::{LevelConfig, LevelPlugin};
+use rustymanfred::plugin::movement::{MovementConfig, MovementPlugin};
+use rustymanfred::plugin::streaming::{StreamingConfig, StreamingPlugin};
+use rustymanfred::plugin::trigger::TriggerPlugin;
+use rustymanfred::resource::game_time::GameTime;
+use rustymanfred::system::position::FromXAndY;
+use rustymanfred::types::chunk::ChunkCoordinate;
+
+/// The wilderness is split into chunks of 400 x 400 and Manfred starts at (200, 200).
+fn wilderness_app() -> AppBuilder {
+    let mut app = App::build();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin)
+        .add_asset::<ColorMaterial>()
+        .add_asset::<Texture>()
+        .add_asset::<TextureAtlas>()
+        .insert_resource(Input::<KeyCode>::default())
+        .init_resource::<GameTime>()
+        .insert_resource(AnimationConfig::default())
+        .add_plugin(MovementPlugin {
+            config: MovementConfig {
+                max_speed: 10,
+                ..Default::default()
+            },
+        })
+        .add_plugin(TriggerPlugin)
+        .add_plugin(LevelPlugin {
+            config: LevelConfig {
+                path: "assets/levels/wilderness.ron".to_string(),
+                ..Default::default()
+            },
+        })
+        .add_plugin(StreamingPlugin {
+            config: StreamingConfig {
+                load_radius: 1,
+                unload_radius: 1,
+            },
+        });
+    app
+}
+
+/// Updates the app until the condition holds, giving the background tasks time to read the
+/// chunk files.
+fn update_until(app: &mut AppBuilder, condition: impl Fn(&mut AppBuilder) -> bool) {
+    for _ in 0..500 {
+        app.app.update();
+        if condition(app) {
+            return;
+        }
+        thread::sleep(Duration::from_millis(2));
+    }
+    panic!("condition was not met in time");
+}
+
+fn chunk_objects(app: &mut AppBuilder, chunk: ChunkCoordinate) -> Vec<Entity> {
+    let world = app.world_mut();
+    world
+        .query::<(Entity, &ChunkObject)>()
+        .iter(world)
+        .filter(|(_, chunk_object)| chunk_object.chunk == chunk)
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+fn manfred_x(app: &mut AppBuilder) -> f32 {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&Transform, With<Manfred>>()
+        .iter(world)
+        .next()
+        .unwrap()
+        .translation
+        .x
+}
+
+#[test]
+fn chunks_are_streamed_in_and_out_while_walking_across_a_chunk_border() {
+    let mut app = wilderness_app();
+
+    update_until(&mut app, |app| {
+        !chunk_objects(app, ChunkCoordinate::new(-1, 0)).is_empty()
+            && !chunk_objects(app, ChunkCoordinate::new(1, 0)).is_empty()
+    });
+    assert!(!chunk_objects(&mut app, ChunkCoordinate::new(0, 0)).is_empty());
+    assert!(chunk_objects(&mut app, ChunkCoordinate::new(2, 0)).is_empty());
+
+    app.world_mut()
+        .get_resource_mut::<Input<KeyCode>>()
+        .unwrap()
+        .press(KeyCode::D);
+    update_until(&mut app, |app| manfred_x(app) >= 400.0);
+
+    update_until(&mut app, |app| {
+        !chunk_objects(app, ChunkCoordinate::new(2, 0)).is_empty()
+    });
+    assert!(chunk_objects(&mut app, ChunkCoordinate::new(-1, 0)).is_empty());
+    assert!(!chunk_objects(&mut app, ChunkCoordinate::new(0, 0)).is_empty());
+    assert!(!chunk_objects(&mut app, ChunkCoordinate::new(1, 0)).is_empty());
+}
+
+#[test]
+fn chunk_objects_are_depth_sorted_in_level_coordinates() {
+    let mut app = wilderness_app();
+    update_until(&mut app, |app| {
+        !chunk_objects(app, ChunkCoordinate::new(1, 0)).is_empty()
+    });
+
+    let world = app.world_mut();
+    for (transform, _) in world.query::<(&Transform, &ChunkObject)>().iter(world) {
+        let translation = transform.translation;
+        assert_eq!(
+            Vec3::compute_from_x_y(translation.x, translation.y),
+            translation
+        );
+    }
+}